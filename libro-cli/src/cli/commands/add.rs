@@ -1,15 +1,77 @@
-use crate::lib::db_operations::Database;
-use crate::lib::errors::LibroResult;
-use crate::utils::database::get_db_path;
+use crate::utils::database::open_store;
+use crate::utils::date::parse_and_validate_date;
 use crate::utils::error_handler::{print_info, print_success};
 use crate::utils::input::prompt_for_book_with_review;
+use crate::utils::output::{is_machine_readable, print_record};
+use libro_core::errors::{validation, LibroError, LibroResult};
+use libro_core::models::{NewBook, NewBookWithReview, NewReview};
+use std::fs::File;
+use std::io::{self, BufReader};
 
-/// Add a new book
-pub fn run() -> LibroResult<()> {
+/// Book and review fields given on the command line
+#[derive(Debug, Clone, Default)]
+pub struct AddOptions {
+    pub title: Option<String>,
+    pub authors: Vec<String>,
+    pub translators: Vec<String>,
+    pub genre: Option<String>,
+    pub pages: Option<i32>,
+    pub year: Option<i32>,
+    pub rating: Option<i32>,
+    pub date: Option<String>,
+    pub review: Option<String>,
+    /// Read a `NewBookWithReview` JSON document from this file ('-' for stdin)
+    pub from_json: Option<String>,
+}
+
+impl AddOptions {
+    /// No book fields given at all, so fall back to the prompts
+    fn is_interactive(&self) -> bool {
+        self.from_json.is_none()
+            && self.title.is_none()
+            && self.authors.is_empty()
+            && self.translators.is_empty()
+            && self.genre.is_none()
+            && self.pages.is_none()
+            && self.year.is_none()
+            && self.rating.is_none()
+            && self.date.is_none()
+            && self.review.is_none()
+    }
+}
+
+/// Add a new book.
+///
+/// Prompts interactively unless book fields or `--from-json` are given, in
+/// which case the created IDs are printed to stdout as JSON (or in the
+/// `--format` given).
+pub fn run(options: AddOptions) -> LibroResult<()> {
+    if options.is_interactive() {
+        return run_interactive();
+    }
+
+    let input = match &options.from_json {
+        Some(source) => read_json(source)?,
+        None => from_flags(options)?,
+    };
+    validate(&input)?;
+
+    let mut db = open_store()?;
+    let result = db.add_book_with_review(&input)?;
+
+    if is_machine_readable() {
+        print_record(&result)?;
+    } else {
+        println!("{}", serde_json::to_string(&result)?);
+    }
+    Ok(())
+}
+
+fn run_interactive() -> LibroResult<()> {
     print_info("Adding a new book to your library");
 
     // Initialize database connection
-    let mut db = Database::new(&get_db_path())?;
+    let mut db = open_store()?;
 
     // Get book information from user
     let book_with_review = prompt_for_book_with_review()?;
@@ -29,6 +91,94 @@ pub fn run() -> LibroResult<()> {
             result.book_id
         ));
     }
+    if is_machine_readable() {
+        print_record(&result)?;
+    }
+
+    Ok(())
+}
+
+fn read_json(source: &str) -> LibroResult<NewBookWithReview> {
+    let input = if source == "-" {
+        serde_json::from_reader(io::stdin().lock())?
+    } else {
+        serde_json::from_reader(BufReader::new(File::open(source)?))?
+    };
+    Ok(input)
+}
+
+fn from_flags(options: AddOptions) -> LibroResult<NewBookWithReview> {
+    let has_review = options.rating.is_some() || options.date.is_some() || options.review.is_some();
+
+    let review = if has_review {
+        let rating = options
+            .rating
+            .ok_or_else(|| LibroError::invalid_input("--rating is required for a review"))?;
+        let date_read = options
+            .date
+            .as_deref()
+            .map(parse_and_validate_date)
+            .transpose()?;
+        Some(NewReview {
+            book_id: 0, // set when the book is created
+            date_read,
+            rating,
+            review: options.review.unwrap_or_default(),
+        })
+    } else {
+        None
+    };
+
+    Ok(NewBookWithReview {
+        book: NewBook {
+            title: options.title.unwrap_or_default(),
+            authors: options.authors,
+            translators: options.translators,
+            pages: options.pages,
+            pub_year: options.year,
+            genre: options
+                .genre
+                .ok_or_else(|| LibroError::invalid_input("--genre is required"))?,
+        },
+        review,
+    })
+}
+
+/// Apply the same rules as the interactive prompts
+fn validate(input: &NewBookWithReview) -> LibroResult<()> {
+    let book = &input.book;
+    validation::validate_non_empty(&book.title, "Title")?;
+    if book.authors.is_empty() {
+        return Err(LibroError::invalid_field(
+            "authors",
+            "required",
+            "At least one author is required",
+        ));
+    }
+    for (field, names, label) in [
+        ("authors", &book.authors, "Author"),
+        ("translators", &book.translators, "Translator"),
+    ] {
+        if names.iter().any(|name| name.trim().is_empty()) {
+            return Err(LibroError::invalid_field(
+                field,
+                "required",
+                format!("{} names cannot be empty", label),
+            ));
+        }
+    }
+    validation::validate_non_empty(&book.genre, "Genre")?;
+    if let Some(pages) = book.pages {
+        validation::validate_pages(pages)?;
+    }
+    if let Some(year) = book.pub_year {
+        validation::validate_year(year)?;
+    }
+
+    if let Some(review) = &input.review {
+        validation::validate_rating(review.rating)?;
+        validation::validate_non_empty(&review.review, "Review text")?;
+    }
 
     Ok(())
 }
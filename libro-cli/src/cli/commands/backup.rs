@@ -0,0 +1,75 @@
+use crate::utils::database::{default_backup_dir, get_db_path, require_sqlite};
+use crate::utils::error_handler::{print_info, print_success};
+use crate::utils::output::{is_machine_readable, print_record};
+use libro_core::backup::{backup_database, create_rotated_backup};
+use libro_core::errors::LibroResult;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// What `backup` wrote, printed with a machine-readable `--format`
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupResult {
+    pub database: String,
+    pub backup: String,
+    pub removed: usize,
+}
+
+impl BackupResult {
+    fn print(database: &str, backup: &Path, removed: usize) -> LibroResult<()> {
+        if is_machine_readable() {
+            print_record(&BackupResult {
+                database: database.to_string(),
+                backup: backup.display().to_string(),
+                removed,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Back up the database with SQLite's online backup API.
+///
+/// Without a destination, or when the destination is a directory, a
+/// timestamped backup is written there and older ones beyond `keep` are
+/// removed. Any other destination is written as-is without rotation.
+pub fn run(dest: Option<PathBuf>, keep: usize) -> LibroResult<()> {
+    require_sqlite("backup")?;
+    let db_path = get_db_path();
+
+    let dir = match dest {
+        Some(path) if !is_directory_target(&path) => {
+            backup_database(Path::new(&db_path), &path)?;
+            print_success(&format!("Backed up {} to {}", db_path, path.display()));
+            return BackupResult::print(&db_path, &path, 0);
+        }
+        Some(path) => path,
+        None => default_backup_dir(&db_path),
+    };
+
+    let summary = create_rotated_backup(
+        Path::new(&db_path),
+        &dir,
+        keep,
+        chrono::Local::now().naive_local(),
+    )?;
+
+    print_success(&format!(
+        "Backed up {} to {}",
+        db_path,
+        summary.path.display()
+    ));
+    if !summary.removed.is_empty() {
+        print_info(&format!(
+            "Removed {} old backup(s), keeping the newest {}",
+            summary.removed.len(),
+            keep
+        ));
+    }
+
+    BackupResult::print(&db_path, &summary.path, summary.removed.len())
+}
+
+/// Existing directories and paths ending in a separator are backup directories
+fn is_directory_target(path: &Path) -> bool {
+    path.is_dir() || path.to_string_lossy().ends_with(std::path::MAIN_SEPARATOR)
+}
@@ -1,14 +1,64 @@
-use crate::lib::db_operations::Database;
-use crate::lib::errors::LibroResult;
-use crate::lib::models::BookFilter;
-use crate::utils::database::get_db_path;
+use crate::utils::database::open_store;
 use crate::utils::error_handler::{print_info, validation::validate_year_option};
-use crate::utils::output::{Displayable, OutputFormat};
+use crate::utils::output::{output_format, Displayable, OutputFormat};
+use clap::ValueEnum;
 use console::style;
+use libro_core::date::parse_date;
+use libro_core::errors::LibroResult;
+use libro_core::models::{BookFilter, ExtendedBook};
+use libro_core::stats::{retain_since, sort_by_date, BookDate};
 
-/// Browse and search books
-pub fn run(query: Option<String>, year: Option<u32>, json: bool) -> LibroResult<()> {
-    let db = Database::new(&get_db_path())?;
+/// Timestamp to list books by, newest first
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum SortBy {
+    /// When the book was added
+    #[default]
+    Added,
+    /// When the book's details last changed
+    Modified,
+}
+
+impl From<SortBy> for BookDate {
+    fn from(sort: SortBy) -> Self {
+        match sort {
+            SortBy::Added => BookDate::Added,
+            SortBy::Modified => BookDate::Modified,
+        }
+    }
+}
+
+/// `--sort`, `--added-since` and `--modified-since`, shared by browse and report
+#[derive(Debug, Clone, Default)]
+pub struct DateOptions {
+    pub sort: Option<SortBy>,
+    pub added_since: Option<String>,
+    pub modified_since: Option<String>,
+}
+
+impl DateOptions {
+    /// Drop books added or modified before the given days, then sort if asked
+    pub fn apply(&self, books: &mut Vec<ExtendedBook>) -> LibroResult<()> {
+        if let Some(since) = &self.added_since {
+            retain_since(books, BookDate::Added, parse_date(since)?);
+        }
+        if let Some(since) = &self.modified_since {
+            retain_since(books, BookDate::Modified, parse_date(since)?);
+        }
+        if let Some(sort) = self.sort {
+            sort_by_date(books, sort.into());
+        }
+        Ok(())
+    }
+}
+
+/// Browse and search books (`json` is shorthand for `--format json`)
+pub fn run(
+    query: Option<String>,
+    year: Option<u32>,
+    dates: DateOptions,
+    json: bool,
+) -> LibroResult<()> {
+    let db = open_store()?;
 
     // Validate year input
     let filter_year = validate_year_option(year)?;
@@ -34,6 +84,19 @@ pub fn run(query: Option<String>, year: Option<u32>, json: bool) -> LibroResult<
             book.book.genre.to_lowercase().contains(&search_lower)
         });
     }
+    dates.apply(&mut books)?;
+
+    // Machine-readable formats print the matches only, even when there are none
+    let requested = if json {
+        OutputFormat::Json
+    } else {
+        output_format()
+    };
+    if requested.is_machine_readable() {
+        print!("{}", books.display(&requested)?.trim_end());
+        println!();
+        return Ok(());
+    }
 
     if books.is_empty() {
         if let Some(q) = &query {
@@ -59,9 +122,7 @@ pub fn run(query: Option<String>, year: Option<u32>, json: bool) -> LibroResult<
     }
 
     // Determine output format
-    let format = if json {
-        OutputFormat::Json
-    } else if books.len() == 1 {
+    let format = if books.len() == 1 {
         OutputFormat::Table
     } else {
         OutputFormat::Summary
@@ -72,7 +133,7 @@ pub fn run(query: Option<String>, year: Option<u32>, json: bool) -> LibroResult<
     println!("{}", output);
 
     // Show summary
-    if !json && books.len() > 1 {
+    if books.len() > 1 {
         println!(
             "\n{}",
             style(&format!("Found {} book(s)", books.len())).dim()
@@ -0,0 +1,216 @@
+use crate::utils::database::{backend, get_db_path, open_backend, store_exists, store_path};
+use crate::utils::error_handler::print_success;
+use crate::utils::library::list_libraries;
+use clap::{Command, ValueEnum};
+use clap_complete::Shell;
+use libro_core::errors::{validation::max_rating, LibroResult};
+use libro_core::models::BookFilter;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Values listed by the hidden `__complete` command
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum CompletionKind {
+    /// Book IDs with their titles
+    Books,
+    /// Review IDs with the book title and rating
+    Reviews,
+    /// Author and translator names
+    Writers,
+    /// Library names
+    Libraries,
+}
+
+/// Print a completion script for `shell`.
+///
+/// The static script generated from the clap definition is followed by a
+/// shell-specific wrapper that asks `libro-cli __complete` for book IDs,
+/// review IDs, writer names and library names.
+pub fn completions(shell: Shell, mut command: Command) -> LibroResult<()> {
+    let name = command.get_name().to_string();
+    let mut buffer = Vec::new();
+    clap_complete::generate(shell, &mut command, &name, &mut buffer);
+    let mut script = String::from_utf8_lossy(&buffer).into_owned();
+
+    match shell {
+        Shell::Bash => script.push_str(BASH_DYNAMIC),
+        Shell::Fish => script.push_str(FISH_DYNAMIC),
+        Shell::Zsh => {
+            // Our wrapper takes over the generated registration block
+            if let Some(start) = script.rfind(ZSH_REGISTRATION) {
+                script.truncate(start);
+            }
+            script.push_str(ZSH_DYNAMIC);
+        }
+        _ => {}
+    }
+
+    io::stdout().lock().write_all(script.as_bytes())?;
+    Ok(())
+}
+
+/// Print the man page, or write a page per command into `out`
+pub fn man(command: Command, out: Option<PathBuf>) -> LibroResult<()> {
+    match out {
+        Some(dir) => {
+            fs::create_dir_all(&dir)?;
+            clap_mangen::generate_to(command, &dir)?;
+            print_success(&format!("Man pages written to {}", dir.display()));
+        }
+        None => clap_mangen::Man::new(command).render(&mut io::stdout().lock())?,
+    }
+    Ok(())
+}
+
+/// Print completion candidates, one per line as `value<TAB>description`.
+///
+/// Completion runs on every TAB, so a missing or unreadable database simply
+/// yields no candidates instead of an error.
+pub fn candidates(kind: CompletionKind) -> LibroResult<()> {
+    let mut out = io::stdout().lock();
+    for (value, description) in candidate_list(kind).unwrap_or_default() {
+        if description.is_empty() {
+            writeln!(out, "{}", one_line(&value))?;
+        } else {
+            writeln!(out, "{}\t{}", one_line(&value), one_line(&description))?;
+        }
+    }
+    Ok(())
+}
+
+fn candidate_list(kind: CompletionKind) -> LibroResult<Vec<(String, String)>> {
+    if kind == CompletionKind::Libraries {
+        return Ok(list_libraries()?
+            .into_iter()
+            .map(|library| (library.name, library.path.display().to_string()))
+            .collect());
+    }
+
+    // Never create a database just to complete a command line
+    let backend = backend();
+    let path = store_path(get_db_path(), backend);
+    if !store_exists(&path, backend) {
+        return Ok(Vec::new());
+    }
+    let db = open_backend(&path, backend)?;
+
+    Ok(match kind {
+        CompletionKind::Books => db
+            .get_books(&BookFilter::default())?
+            .into_iter()
+            .filter_map(|book| Some((book.book.id?.to_string(), book.book.title)))
+            .collect(),
+        CompletionKind::Reviews => db
+            .get_books(&BookFilter::default())?
+            .iter()
+            .flat_map(|book| {
+                book.reviews.iter().filter_map(move |review| {
+                    Some((
+                        review.id?.to_string(),
+                        format!("{} ({}/{})", book.book.title, review.rating, max_rating()),
+                    ))
+                })
+            })
+            .collect(),
+        CompletionKind::Writers => db
+            .get_writers()?
+            .into_iter()
+            .map(|writer| writer.name)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|name| (name, String::new()))
+            .collect(),
+        CompletionKind::Libraries => unreachable!("handled above"),
+    })
+}
+
+/// Tabs and newlines would split a candidate line
+fn one_line(text: &str) -> String {
+    text.replace(['\t', '\n', '\r'], " ")
+}
+
+const BASH_DYNAMIC: &str = r#"
+# Dynamic completion of book IDs, review IDs, writers and libraries
+_libro-cli_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}" kind=""
+    case "${prev}" in
+        --author|--translator) kind=writers ;;
+        --library) kind=libraries ;;
+        --edit) kind=reviews ;;
+        review) [[ "${COMP_WORDS[1]}" == review ]] && kind=books ;;
+        book) [[ "${COMP_WORDS[1]}" == delete ]] && kind=books ;;
+        switch|remove) [[ "${COMP_WORDS[1]}" == library ]] && kind=libraries ;;
+    esac
+    if [[ "${COMP_WORDS[1]}" == delete && "${prev}" == review ]]; then
+        kind=reviews
+    fi
+    if [[ -n "${kind}" ]]; then
+        local IFS=$'\n'
+        COMPREPLY=($(compgen -W "$(libro-cli __complete "${kind}" 2>/dev/null | cut -f1)" -- "${cur}"))
+        return 0
+    fi
+    _libro-cli "$@"
+}
+if [[ "${BASH_VERSINFO[0]}" -eq 4 && "${BASH_VERSINFO[1]}" -ge 4 || "${BASH_VERSINFO[0]}" -gt 4 ]]; then
+    complete -F _libro-cli_dynamic -o nosort -o bashdefault -o default libro-cli
+else
+    complete -F _libro-cli_dynamic -o bashdefault -o default libro-cli
+fi
+"#;
+
+/// Start of the block clap_complete ends zsh scripts with
+const ZSH_REGISTRATION: &str = "if [ \"$funcstack[1]\" = \"_libro-cli\" ]";
+
+/// Keeps the generated `_libro-cli` as `_libro-cli_static` and replaces it
+/// with a wrapper, so dynamic values also work when the script is autoloaded
+/// from `$fpath`
+const ZSH_DYNAMIC: &str = r#"# Dynamic completion of book IDs, review IDs, writers and libraries
+functions[_libro-cli_static]=$functions[_libro-cli]
+_libro-cli_candidates() {
+    local -a candidates
+    candidates=("${(@f)$(libro-cli __complete "$1" 2>/dev/null)}")
+    candidates=("${(@)${(@)candidates//:/\\:}//$'\t'/:}")
+    _describe -t "$1" "$1" candidates
+}
+_libro-cli() {
+    local prev="${words[CURRENT-1]}"
+    case "${prev}" in
+        --author|--translator) _libro-cli_candidates writers; return ;;
+        --library) _libro-cli_candidates libraries; return ;;
+        --edit) _libro-cli_candidates reviews; return ;;
+    esac
+    if (( CURRENT == 3 )) && [[ "${words[2]}" == review ]]; then
+        _libro-cli_candidates books; return
+    fi
+    if (( CURRENT == 4 )) && [[ "${words[2]}" == delete ]]; then
+        case "${words[3]}" in
+            book) _libro-cli_candidates books; return ;;
+            review) _libro-cli_candidates reviews; return ;;
+        esac
+    fi
+    if (( CURRENT == 4 )) && [[ "${words[2]}" == library && "${words[3]}" == (switch|remove) ]]; then
+        _libro-cli_candidates libraries; return
+    fi
+    _libro-cli_static "$@"
+}
+
+if [ "$funcstack[1]" = "_libro-cli" ]; then
+    _libro-cli "$@"
+else
+    compdef _libro-cli libro-cli
+fi
+"#;
+
+const FISH_DYNAMIC: &str = r#"
+# Dynamic completion of book IDs, review IDs, writers and libraries
+complete -c libro-cli -n "__fish_seen_subcommand_from review; and not __fish_seen_subcommand_from delete" -f -a "(libro-cli __complete books 2>/dev/null)"
+complete -c libro-cli -n "__fish_seen_subcommand_from delete; and __fish_seen_subcommand_from book" -f -a "(libro-cli __complete books 2>/dev/null)"
+complete -c libro-cli -n "__fish_seen_subcommand_from delete; and __fish_seen_subcommand_from review" -f -a "(libro-cli __complete reviews 2>/dev/null)"
+complete -c libro-cli -n "__fish_seen_subcommand_from library; and __fish_seen_subcommand_from switch remove" -f -a "(libro-cli __complete libraries 2>/dev/null)"
+complete -c libro-cli -l author -x -a "(libro-cli __complete writers 2>/dev/null)"
+complete -c libro-cli -l translator -x -a "(libro-cli __complete writers 2>/dev/null)"
+complete -c libro-cli -l library -x -a "(libro-cli __complete libraries 2>/dev/null)"
+complete -c libro-cli -l edit -x -a "(libro-cli __complete reviews 2>/dev/null)"
+"#;
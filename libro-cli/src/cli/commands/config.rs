@@ -0,0 +1,72 @@
+use crate::utils::config::{config_path, load_from, save_to};
+use crate::utils::error_handler::print_success;
+use crate::utils::output::{is_machine_readable, print_record, print_records};
+use libro_core::errors::LibroResult;
+use serde::Serialize;
+
+/// A config key and its value, printed with a machine-readable `--format`
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: String,
+}
+
+/// Print one config value, or the whole config file when no key is given.
+///
+/// With a machine-readable `--format` values are printed as key/value
+/// records, and without a key every key is listed.
+pub fn get(key: Option<String>) -> LibroResult<()> {
+    let config = load_from(&config_path())?;
+    if is_machine_readable() {
+        return match key {
+            Some(key) => print_record(&ConfigEntry {
+                value: config.get(&key)?,
+                key,
+            }),
+            None => {
+                let entries: Vec<ConfigEntry> = config
+                    .entries()?
+                    .into_iter()
+                    .map(|(key, value)| ConfigEntry { key, value })
+                    .collect();
+                print_records(&entries)
+            }
+        };
+    }
+
+    match key {
+        Some(key) => println!("{}", config.get(&key)?),
+        None => print!("{}", config.to_toml()?),
+    }
+    Ok(())
+}
+
+/// Change one config value and write the file back
+pub fn set(key: String, value: String) -> LibroResult<()> {
+    let path = config_path();
+    let mut config = load_from(&path)?;
+    config.set(&key, &value)?;
+    save_to(&path, &config)?;
+
+    print_success(&format!("Set {} in {}", key, path.display()));
+    if is_machine_readable() {
+        print_record(&ConfigEntry {
+            value: config.get(&key)?,
+            key,
+        })?;
+    }
+    Ok(())
+}
+
+/// Print where the config file is read from
+pub fn path() -> LibroResult<()> {
+    let path = config_path().display().to_string();
+    if is_machine_readable() {
+        return print_record(&ConfigEntry {
+            key: "path".to_string(),
+            value: path,
+        });
+    }
+    println!("{}", path);
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use crate::utils::config::Backend;
+use crate::utils::database::{backend, get_db_path, open_backend, store_exists, store_path};
+use crate::utils::error_handler::{print_info, print_success};
+use crate::utils::output::{is_machine_readable, print_record};
+use libro_core::errors::{LibroError, LibroResult};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Outcome of `convert` as a flat record for `--format`
+#[derive(Debug, Clone, Serialize)]
+pub struct ConvertResult {
+    pub from: Backend,
+    pub to: Backend,
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    pub books: usize,
+    pub writers: usize,
+    pub reviews: usize,
+}
+
+/// Copy the current library into another backend, keeping every ID.
+///
+/// The source is read with the configured backend; the destination defaults
+/// to where `to` would keep the same library and must not exist yet.
+pub fn run(to: Backend, out: Option<PathBuf>) -> LibroResult<()> {
+    let from = backend();
+    let db_path = get_db_path();
+    let source = store_path(&db_path, from);
+    let destination = out.unwrap_or_else(|| store_path(&db_path, to));
+
+    if !store_exists(&source, from) {
+        return Err(LibroError::invalid_input(format!(
+            "No {} library at {}",
+            from.as_str(),
+            source.display()
+        )));
+    }
+    if destination == source {
+        return Err(LibroError::invalid_input(format!(
+            "The library already uses the {} backend at {}; give --out to copy it elsewhere",
+            to.as_str(),
+            source.display()
+        )));
+    }
+    if !is_unused(&destination)? {
+        return Err(LibroError::invalid_input(format!(
+            "{} already exists; convert only writes to a new location",
+            destination.display()
+        )));
+    }
+
+    let archive = open_backend(&source, from)?.export_archive()?;
+    if let Some(parent) = destination.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    open_backend(&destination, to)?.load_archive(&archive)?;
+
+    let result = ConvertResult {
+        from,
+        to,
+        source,
+        destination,
+        books: archive.books.len(),
+        writers: archive.writers.len(),
+        reviews: archive.reviews.len(),
+    };
+    print_success(&format!(
+        "Converted {} book(s), {} writer(s) and {} review(s) to {}",
+        result.books,
+        result.writers,
+        result.reviews,
+        result.destination.display()
+    ));
+    if to != from && result.destination == store_path(&db_path, to) {
+        print_info(&format!(
+            "Run `libro-cli config set database.backend {}` to use it",
+            to.as_str()
+        ));
+    }
+    if is_machine_readable() {
+        print_record(&result)?;
+    }
+
+    Ok(())
+}
+
+/// A missing path or an empty directory
+fn is_unused(path: &Path) -> LibroResult<bool> {
+    if !path.exists() {
+        return Ok(true);
+    }
+    Ok(path.is_dir() && fs::read_dir(path)?.next().is_none())
+}
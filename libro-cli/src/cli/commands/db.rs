@@ -0,0 +1,165 @@
+use crate::utils::database::open_store;
+use crate::utils::error_handler::{print_info, print_success};
+use crate::utils::input::select_from_list;
+use crate::utils::output::{is_machine_readable, print_record, print_records};
+use clap::ValueEnum;
+use console::style;
+use libro_core::backup::validate_backup;
+use libro_core::db_operations::Database;
+use libro_core::errors::LibroResult;
+use libro_core::merge::{diff_libraries, plan_merge, BookConflict, DiffEntry, DiffKind, Side};
+use libro_core::models::BookFilter;
+use libro_core::store::LibraryStore;
+use serde::Serialize;
+use std::path::Path;
+
+/// How `db merge` settles books that differ on both sides
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum MergeStrategy {
+    /// Take the copy edited most recently
+    PreferNewer,
+    /// Always keep the local copy
+    PreferLocal,
+    /// Ask for each conflicting book
+    Interactive,
+}
+
+/// One `db diff` entry as a flat row for `--format`
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffRow {
+    pub section: &'static str,
+    pub kind: DiffKind,
+    pub label: String,
+    pub details: String,
+}
+
+/// Show the differences between two databases
+pub fn diff(a: &Path, b: &Path) -> LibroResult<()> {
+    let a_db = open_existing(a)?;
+    let b_db = open_existing(b)?;
+
+    let diff = diff_libraries(
+        &a_db.get_books(&BookFilter::default())?,
+        &a_db.get_writers()?,
+        &b_db.get_books(&BookFilter::default())?,
+        &b_db.get_writers()?,
+    );
+
+    if is_machine_readable() {
+        let sections = [
+            ("books", &diff.books),
+            ("writers", &diff.writers),
+            ("reviews", &diff.reviews),
+        ];
+        let rows: Vec<DiffRow> = sections
+            .into_iter()
+            .flat_map(|(section, entries)| {
+                entries.iter().map(move |entry| DiffRow {
+                    section,
+                    kind: entry.kind,
+                    label: entry.label.clone(),
+                    details: entry.details.join("; "),
+                })
+            })
+            .collect();
+        return print_records(&rows);
+    }
+
+    if diff.is_empty() {
+        print_info("The databases contain the same books, writers and reviews");
+        return Ok(());
+    }
+
+    print_section("📚 Books", &diff.books);
+    print_section("✍️  Writers", &diff.writers);
+    print_section("📝 Reviews", &diff.reviews);
+
+    Ok(())
+}
+
+/// Merge another database into the current one
+pub fn merge(from: &Path, strategy: MergeStrategy) -> LibroResult<()> {
+    let other = open_existing(from)?;
+    let mut db = open_store()?;
+
+    let local_books = db.get_books(&BookFilter::default())?;
+    let other_books = other.get_books(&BookFilter::default())?;
+
+    let plan = plan_merge(&local_books, &other_books, |conflict| match strategy {
+        MergeStrategy::PreferNewer => Ok(conflict.newer_side()),
+        MergeStrategy::PreferLocal => Ok(Side::Local),
+        MergeStrategy::Interactive => ask(conflict),
+    })?;
+
+    let summary = db.apply_merge(&plan)?;
+
+    print_success(&format!(
+        "Merged {}: {} book(s) added, {} updated; {} review(s) added, {} updated; {} new writer(s)",
+        from.display(),
+        summary.books_added,
+        summary.books_updated,
+        summary.reviews_added,
+        summary.reviews_updated,
+        summary.writers_created
+    ));
+    if plan.conflicts > 0 {
+        print_info(&format!(
+            "{} conflict(s): {} kept local, {} taken from {}",
+            plan.conflicts,
+            plan.kept_local,
+            plan.conflicts - plan.kept_local,
+            from.display()
+        ));
+    }
+    if is_machine_readable() {
+        print_record(&summary)?;
+    }
+
+    Ok(())
+}
+
+/// Validate and open a database that must already exist
+fn open_existing(path: &Path) -> LibroResult<Database> {
+    validate_backup(path)?;
+    Database::new(&path.to_string_lossy())
+}
+
+fn ask(conflict: &BookConflict) -> LibroResult<Side> {
+    println!();
+    println!(
+        "{} {}",
+        style("⚠️  Conflict:").bold().yellow(),
+        conflict.local.book.title
+    );
+    for difference in &conflict.differences {
+        println!("   {}", difference);
+    }
+
+    let choice = select_from_list("Keep which version?", &["Local", "Other"])?;
+    Ok(if choice == 0 {
+        Side::Local
+    } else {
+        Side::Other
+    })
+}
+
+fn print_section(title: &str, entries: &[DiffEntry]) {
+    if entries.is_empty() {
+        return;
+    }
+
+    println!("{}", style(title).bold().cyan());
+    println!("{}", "─".repeat(50));
+    for entry in entries {
+        let marker = match entry.kind {
+            DiffKind::Added => style("+").green().bold(),
+            DiffKind::Removed => style("-").red().bold(),
+            DiffKind::Changed => style("~").yellow().bold(),
+        };
+        println!("{} {}", marker, entry.label);
+        for detail in &entry.details {
+            println!("    {}", style(detail).dim());
+        }
+    }
+    println!();
+}
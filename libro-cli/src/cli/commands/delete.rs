@@ -0,0 +1,156 @@
+use crate::utils::database::open_store;
+use crate::utils::date::format_date;
+use crate::utils::error_handler::{print_info, print_success};
+use crate::utils::input::confirm;
+use crate::utils::output::{is_machine_readable, print_record};
+use console::style;
+use libro_core::errors::{validation::max_rating, LibroError, LibroResult};
+use libro_core::models::BookFilter;
+use serde::Serialize;
+
+/// What `delete` removed (or would remove), printed with a machine-readable `--format`
+#[derive(Debug, Clone, Serialize)]
+pub struct DeleteResult {
+    pub target: &'static str,
+    pub id: i64,
+    pub title: String,
+    pub reviews: usize,
+    pub orphaned_writers: usize,
+    pub deleted: bool,
+}
+
+impl DeleteResult {
+    fn print(self) -> LibroResult<()> {
+        if is_machine_readable() {
+            print_record(&self)?;
+        }
+        Ok(())
+    }
+}
+
+/// Print a preview line to stdout, or to stderr when stdout carries data
+fn preview(line: &str) {
+    if is_machine_readable() {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Move a book to the trash; its reviews and writers come back if it is restored
+pub fn book(id: i64, yes: bool, dry_run: bool) -> LibroResult<()> {
+    let mut db = open_store()?;
+
+    let book = db
+        .get_books(&BookFilter {
+            id: Some(id),
+            year: None,
+        })?
+        .into_iter()
+        .next()
+        .ok_or(LibroError::BookNotFound { id })?;
+    let orphaned = db.orphaned_writers_for_book(id)?;
+
+    preview(
+        &style("🗑️  The following will be moved to the trash")
+            .bold()
+            .red()
+            .to_string(),
+    );
+    preview(&"─".repeat(50));
+    preview(&format!("Book #{}: {}", id, book.book.title));
+    preview(&format!("Reviews: {}", book.reviews.len()));
+    if orphaned.is_empty() {
+        preview("Writers only on this book: none");
+    } else {
+        let names: Vec<&str> = orphaned.iter().map(|w| w.name.as_str()).collect();
+        preview(&format!("Writers only on this book: {}", names.join(", ")));
+    }
+    preview("");
+
+    let result = DeleteResult {
+        target: "book",
+        id,
+        title: book.book.title.clone(),
+        reviews: book.reviews.len(),
+        orphaned_writers: orphaned.len(),
+        deleted: !dry_run,
+    };
+
+    if dry_run {
+        print_info("Dry run: nothing was deleted");
+        return result.print();
+    }
+    if !yes && !confirm(&format!("Delete '{}'?", book.book.title))? {
+        return Err(LibroError::UserCancelled);
+    }
+
+    db.delete_book(id)?;
+    print_success(&format!(
+        "Moved book #{} with {} review(s) to the trash",
+        id,
+        book.reviews.len()
+    ));
+    print_info(&format!(
+        "Bring it back with `libro-cli trash restore book {}`",
+        id
+    ));
+
+    result.print()
+}
+
+/// Move a single review to the trash
+pub fn review(id: i64, yes: bool, dry_run: bool) -> LibroResult<()> {
+    let mut db = open_store()?;
+
+    let review = db.get_review(id)?;
+    let title = db
+        .get_books(&BookFilter {
+            id: Some(review.book_id),
+            year: None,
+        })?
+        .into_iter()
+        .next()
+        .map(|b| b.book.title)
+        .unwrap_or_default();
+
+    preview(
+        &style("🗑️  The following will be moved to the trash")
+            .bold()
+            .red()
+            .to_string(),
+    );
+    preview(&"─".repeat(50));
+    preview(&format!("Review #{} of '{}'", id, title));
+    preview(&format!("Rating: {}/{}", review.rating, max_rating()));
+    if let Some(date) = review.date_read {
+        preview(&format!("Date read: {}", format_date(&date)));
+    }
+    preview("");
+
+    let result = DeleteResult {
+        target: "review",
+        id,
+        title: title.clone(),
+        reviews: 1,
+        orphaned_writers: 0,
+        deleted: !dry_run,
+    };
+
+    if dry_run {
+        print_info("Dry run: nothing was deleted");
+        return result.print();
+    }
+    if !yes && !confirm(&format!("Delete this review of '{}'?", title))? {
+        return Err(LibroError::UserCancelled);
+    }
+
+    db.delete_review(id)?;
+    print_success(&format!("Moved review #{} to the trash", id));
+    print_info(&format!(
+        "Bring it back with `libro-cli trash restore review {}`",
+        id
+    ));
+
+    result.print()
+}
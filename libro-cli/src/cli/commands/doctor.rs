@@ -0,0 +1,91 @@
+use crate::utils::database::{get_db_path, require_sqlite};
+use crate::utils::error_handler::{print_info, print_success};
+use crate::utils::output::{is_machine_readable, print_records};
+use console::style;
+use libro_core::doctor::{diagnose, repair, CheckCategory, Issue};
+use libro_core::errors::{LibroError, LibroResult};
+use rusqlite::Connection;
+use std::path::Path;
+
+/// Check the database for corruption and data problems, optionally
+/// applying the safe repairs
+pub fn run(fix: bool) -> LibroResult<()> {
+    require_sqlite("doctor")?;
+    let db_path = get_db_path();
+    if !Path::new(&db_path).is_file() {
+        return Err(LibroError::invalid_input(format!(
+            "Database not found: {}",
+            db_path
+        )));
+    }
+    let mut conn = Connection::open(&db_path)?;
+
+    let mut issues = diagnose(&conn)?;
+    if fix && issues.iter().any(|issue| issue.fixable) {
+        let summary = repair(&mut conn)?;
+        print_success(&format!(
+            "Repaired {} problem(s): removed {} dangling link(s) and {} writer(s) without books, renamed the genre of {} book(s)",
+            summary.total(),
+            summary.dangling_links_removed,
+            summary.orphaned_writers_removed,
+            summary.genres_renamed
+        ));
+        issues = diagnose(&conn)?;
+    }
+
+    if is_machine_readable() {
+        return print_records(&issues);
+    }
+
+    println!(
+        "{} {}",
+        style("🩺 Checking").bold().cyan(),
+        style(&db_path).dim()
+    );
+    println!("{}", "─".repeat(50));
+    for category in CheckCategory::ALL {
+        print_category(category, &issues);
+    }
+    println!();
+
+    let fixable = issues.iter().filter(|issue| issue.fixable).count();
+    if issues.is_empty() {
+        print_success("No problems found");
+    } else if fixable > 0 {
+        print_info(&format!(
+            "Found {} problem(s); run `libro-cli doctor --fix` to repair {} of them",
+            issues.len(),
+            fixable
+        ));
+    } else {
+        print_info(&format!(
+            "Found {} problem(s) that need fixing by hand",
+            issues.len()
+        ));
+    }
+
+    Ok(())
+}
+
+fn print_category(category: CheckCategory, issues: &[Issue]) {
+    let found: Vec<&Issue> = issues
+        .iter()
+        .filter(|issue| issue.category == category)
+        .collect();
+
+    if found.is_empty() {
+        println!("{} {}", style("✔").green().bold(), category.title());
+        return;
+    }
+
+    println!(
+        "{} {} ({})",
+        style("⚠").yellow().bold(),
+        category.title(),
+        found.len()
+    );
+    for issue in found {
+        let note = if issue.fixable { " [--fix]" } else { "" };
+        println!("   • {}{}", issue.message, style(note).dim());
+    }
+}
@@ -0,0 +1,95 @@
+use crate::utils::database::open_store;
+use crate::utils::error_handler::print_success;
+use crate::utils::markdown::export_vault;
+use crate::utils::output::OutputFormat;
+use libro_core::archive::ArchiveEncoding;
+use libro_core::errors::{LibroError, LibroResult};
+use libro_core::models::BookFilter;
+use libro_core::store::LibraryStore;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+/// Output formats supported by `export`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    /// Single JSON archive document
+    Json,
+    /// Newline-delimited JSON archive, one record per line
+    Ndjson,
+    /// Obsidian-style Markdown vault (requires --out <dir>)
+    Markdown,
+}
+
+impl TryFrom<OutputFormat> for ExportFormat {
+    type Error = LibroError;
+
+    /// Pick the archive format from `--format`; a JSON archive by default
+    fn try_from(format: OutputFormat) -> LibroResult<Self> {
+        match format {
+            OutputFormat::Table | OutputFormat::Json => Ok(ExportFormat::Json),
+            OutputFormat::Ndjson => Ok(ExportFormat::Ndjson),
+            OutputFormat::Markdown => Ok(ExportFormat::Markdown),
+            other => Err(LibroError::invalid_input(format!(
+                "export supports --format json, ndjson or markdown, not {}",
+                other.name()
+            ))),
+        }
+    }
+}
+
+/// Export the whole library
+pub fn run(format: ExportFormat, out: Option<PathBuf>) -> LibroResult<()> {
+    let db = open_store()?;
+
+    let encoding = match format {
+        ExportFormat::Json => ArchiveEncoding::Json,
+        ExportFormat::Ndjson => ArchiveEncoding::Ndjson,
+        ExportFormat::Markdown => return export_markdown(db.as_ref(), out),
+    };
+
+    let archive = db.export_archive()?;
+
+    match out {
+        Some(path) => {
+            let mut writer = BufWriter::new(File::create(&path)?);
+            archive.write_to(&mut writer, encoding)?;
+            writer.flush()?;
+            print_success(&format!(
+                "Exported {} book(s), {} writer(s) and {} review(s) to {}",
+                archive.books.len(),
+                archive.writers.len(),
+                archive.reviews.len(),
+                path.display()
+            ));
+        }
+        None => {
+            let stdout = io::stdout();
+            let mut writer = stdout.lock();
+            archive.write_to(&mut writer, encoding)?;
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write one note per book and author into the `--out` directory
+fn export_markdown(db: &dyn LibraryStore, out: Option<PathBuf>) -> LibroResult<()> {
+    let dir = out.ok_or_else(|| {
+        LibroError::invalid_input("Markdown export needs an output directory (--out <dir>)")
+    })?;
+
+    let books = db.get_books(&BookFilter::default())?;
+    let summary = export_vault(&books, &dir)?;
+
+    print_success(&format!(
+        "Markdown vault written to {}: {} created, {} updated, {} unchanged",
+        dir.display(),
+        summary.created,
+        summary.updated,
+        summary.unchanged
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::utils::database::open_store;
+use crate::utils::error_handler::{print_info, print_success};
+use crate::utils::output::{is_machine_readable, print_records};
+use console::style;
+use libro_core::errors::{LibroError, LibroResult};
+use libro_core::history::HistoryEntry;
+use libro_core::models::BookFilter;
+use libro_core::store::LibraryStore;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// One history entry as a flat record for `--format`
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryRow {
+    pub id: i64,
+    pub recorded_at: String,
+    pub operation: String,
+    pub books: String,
+    pub changes: String,
+    pub undone: bool,
+}
+
+/// List recorded changes, newest first, optionally only those touching a book
+pub fn run(book: Option<i64>) -> LibroResult<()> {
+    let db = open_store()?;
+    if let Some(id) = book {
+        let exists = !db
+            .get_books(&BookFilter {
+                id: Some(id),
+                year: None,
+            })?
+            .is_empty();
+        // A deleted book still has history, so only refuse IDs never recorded
+        if !exists && db.history(Some(id))?.is_empty() {
+            return Err(LibroError::BookNotFound { id });
+        }
+    }
+
+    let rows = history_rows(db.as_ref(), &db.history(book)?)?;
+    if is_machine_readable() {
+        return print_records(&rows);
+    }
+    if rows.is_empty() {
+        print_info("No changes recorded yet");
+        return Ok(());
+    }
+
+    for row in &rows {
+        let line = format!(
+            "#{:<4} {}  {:<14} {}",
+            row.id, row.recorded_at, row.operation, row.books
+        );
+        if row.undone {
+            println!("{} {}", style(line).dim(), style("(undone)").yellow());
+        } else {
+            println!("{}", line);
+        }
+        println!("      {}", style(&row.changes).dim());
+    }
+    Ok(())
+}
+
+/// Revert the last `count` changes that are not undone yet
+pub fn undo(count: usize) -> LibroResult<()> {
+    if count == 0 {
+        return Err(LibroError::invalid_field(
+            "count",
+            "min:1",
+            "Give at least one change to undo",
+        ));
+    }
+
+    let mut db = open_store()?;
+    let entries = db.undo(count)?;
+    let rows = history_rows(db.as_ref(), &entries)?;
+    for row in &rows {
+        print_success(&format!(
+            "Undid #{} {} {}",
+            row.id, row.operation, row.books
+        ));
+    }
+    if entries.len() < count {
+        print_info(&format!(
+            "Only {} change(s) were left to undo",
+            entries.len()
+        ));
+    }
+    if is_machine_readable() {
+        print_records(&rows)?;
+    }
+    Ok(())
+}
+
+/// Flatten entries, naming books by the title recorded in the entry or,
+/// failing that, their current title
+fn history_rows(db: &dyn LibraryStore, entries: &[HistoryEntry]) -> LibroResult<Vec<HistoryRow>> {
+    let titles: HashMap<i64, String> = db
+        .get_books(&BookFilter::default())?
+        .into_iter()
+        .filter_map(|b| Some((b.book.id?, b.book.title)))
+        .collect();
+
+    Ok(entries
+        .iter()
+        .map(|entry| {
+            let books: Vec<String> = entry
+                .book_ids()
+                .into_iter()
+                .map(
+                    |id| match entry.book_title(id).or(titles.get(&id).map(String::as_str)) {
+                        Some(title) => format!("#{} {}", id, title),
+                        None => format!("#{}", id),
+                    },
+                )
+                .collect();
+            HistoryRow {
+                id: entry.id,
+                recorded_at: entry.recorded_at.clone(),
+                operation: entry.operation.clone(),
+                books: books.join(", "),
+                changes: entry.describe_changes(),
+                undone: entry.undone,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,59 @@
+use crate::utils::database::open_store;
+use crate::utils::error_handler::print_success;
+use crate::utils::output::{is_machine_readable, print_record, OutputFormat};
+use libro_core::archive::{Archive, ArchiveEncoding};
+use libro_core::errors::{LibroError, LibroResult};
+use std::fs::File;
+use std::io::{self, BufReader};
+
+/// Archive formats accepted by `import`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    /// Single JSON archive document
+    Json,
+    /// Newline-delimited JSON archive, one record per line
+    Ndjson,
+}
+
+impl TryFrom<OutputFormat> for ImportFormat {
+    type Error = LibroError;
+
+    /// Pick the archive format from `--format`; a JSON archive by default
+    fn try_from(format: OutputFormat) -> LibroResult<Self> {
+        match format {
+            OutputFormat::Table | OutputFormat::Json => Ok(ImportFormat::Json),
+            OutputFormat::Ndjson => Ok(ImportFormat::Ndjson),
+            other => Err(LibroError::invalid_input(format!(
+                "import reads --format json or ndjson, not {}",
+                other.name()
+            ))),
+        }
+    }
+}
+
+/// Import an archive produced by `export`
+pub fn run(file: String, format: ImportFormat) -> LibroResult<()> {
+    let encoding = match format {
+        ImportFormat::Json => ArchiveEncoding::Json,
+        ImportFormat::Ndjson => ArchiveEncoding::Ndjson,
+    };
+
+    let archive = if file == "-" {
+        Archive::read_from(io::stdin().lock(), encoding)?
+    } else {
+        Archive::read_from(BufReader::new(File::open(&file)?), encoding)?
+    };
+
+    let mut db = open_store()?;
+    let summary = db.import_archive(&archive)?;
+
+    print_success(&format!(
+        "Imported {} book(s) and {} review(s); {} new writer(s), {} existing writer(s) reused",
+        summary.books, summary.reviews, summary.writers_created, summary.writers_reused
+    ));
+    if is_machine_readable() {
+        print_record(&summary)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use crate::utils::database::{backend, db_source, open_backend, DbSource};
+use crate::utils::error_handler::{print_info, print_success};
+use crate::utils::input::confirm;
+use crate::utils::library::{
+    active_library, ensure_exists, library_exists, library_store_path, list_libraries,
+    set_active_library, validate_name, LibraryInfo, DEFAULT_LIBRARY,
+};
+use crate::utils::output::{is_machine_readable, print_record, print_records};
+use console::style;
+use libro_core::errors::{LibroError, LibroResult};
+use std::fs;
+use std::path::Path;
+
+/// Print a library as a record when `--format` is machine-readable
+fn print_library(name: &str, active: bool) -> LibroResult<()> {
+    if is_machine_readable() {
+        print_record(&LibraryInfo {
+            name: name.to_string(),
+            path: library_store_path(name),
+            active,
+        })?;
+    }
+    Ok(())
+}
+
+/// List the libraries, marking the active one
+pub fn list() -> LibroResult<()> {
+    let libraries = list_libraries()?;
+    if is_machine_readable() {
+        return print_records(&libraries);
+    }
+
+    let width = libraries.iter().map(|l| l.name.len()).max().unwrap_or(0);
+
+    for library in &libraries {
+        let marker = if library.active { "*" } else { " " };
+        println!(
+            "{} {:width$}  {}",
+            style(marker).green().bold(),
+            library.name,
+            style(library.path.display()).dim(),
+            width = width
+        );
+    }
+
+    if let DbSource::Path(path) = db_source() {
+        print_info(&format!(
+            "The database is set explicitly for this run: {}",
+            path
+        ));
+    }
+    Ok(())
+}
+
+/// Create an empty library, optionally making it the active one
+pub fn create(name: String, switch_to: bool) -> LibroResult<()> {
+    validate_name(&name)?;
+    if library_exists(&name) {
+        return Err(LibroError::invalid_input(format!(
+            "Library '{}' already exists",
+            name
+        )));
+    }
+
+    let path = library_store_path(&name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    open_backend(&path, backend())?;
+    print_success(&format!("Created library '{}' at {}", name, path.display()));
+
+    if switch_to {
+        set_active_library(&name)?;
+        print_success(&format!("Switched to library '{}'", name));
+    }
+    print_library(&name, switch_to)
+}
+
+/// Make a library the active one for later runs
+pub fn switch(name: String) -> LibroResult<()> {
+    ensure_exists(&name)?;
+    set_active_library(&name)?;
+    print_success(&format!("Switched to library '{}'", name));
+    print_library(&name, true)
+}
+
+/// Delete a library's database file or files directory
+pub fn remove(name: String, yes: bool) -> LibroResult<()> {
+    ensure_exists(&name)?;
+    if name == DEFAULT_LIBRARY {
+        return Err(LibroError::invalid_input(
+            "The default library cannot be removed",
+        ));
+    }
+    if name == active_library() {
+        return Err(LibroError::invalid_input(format!(
+            "Library '{}' is active; switch to another library first",
+            name
+        )));
+    }
+
+    let path = library_store_path(&name);
+    if !yes
+        && !confirm(&format!(
+            "Delete library '{}' ({})? This cannot be undone",
+            name,
+            path.display()
+        ))?
+    {
+        return Err(LibroError::UserCancelled);
+    }
+
+    if path.is_dir() {
+        fs::remove_dir_all(&path)?;
+    } else {
+        remove_database_files(&path)?;
+    }
+    print_success(&format!("Removed library '{}'", name));
+    if is_machine_readable() {
+        print_record(&LibraryInfo {
+            name,
+            path,
+            active: false,
+        })?;
+    }
+    Ok(())
+}
+
+/// Remove a SQLite database along with its journal files
+fn remove_database_files(path: &Path) -> LibroResult<()> {
+    fs::remove_file(path)?;
+    for suffix in ["-wal", "-shm", "-journal"] {
+        let mut name = path.as_os_str().to_os_string();
+        name.push(suffix);
+        let sidecar = Path::new(&name);
+        if sidecar.exists() {
+            fs::remove_file(sidecar)?;
+        }
+    }
+    Ok(())
+}
@@ -1,4 +1,18 @@
 pub mod add;
+pub mod backup;
 pub mod browse;
+pub mod completions;
+pub mod config;
+pub mod convert;
+pub mod db;
+pub mod delete;
+pub mod doctor;
+pub mod export;
+pub mod history;
+pub mod import;
+pub mod library;
 pub mod report;
+pub mod restore;
 pub mod review;
+pub mod site;
+pub mod trash;
@@ -1,25 +1,64 @@
-use crate::lib::db_operations::Database;
-use crate::lib::errors::LibroResult;
-use crate::lib::models::BookFilter;
-use crate::utils::database::get_db_path;
+use crate::cli::commands::browse::DateOptions;
+use crate::utils::database::open_store;
 use crate::utils::date::{format_date, relative_date_description};
-use crate::utils::error_handler::{print_info, validation::validate_year_option};
-use crate::utils::output::format_reading_stats;
-use chrono::Datelike;
+use crate::utils::error_handler::{print_info, print_success, validation::validate_year_option};
+use crate::utils::markdown::{format_change, format_rating, render_year_in_review};
+use crate::utils::output::{
+    format_reading_stats, is_machine_readable, output_format, print_record, print_records,
+    BookRecord, OutputFormat,
+};
+use crate::utils::svg::{render_bar_chart, render_line_chart, ChartData};
+use chrono::Local;
 use console::style;
-use std::collections::HashMap;
+use libro_core::errors::{validation::max_rating, LibroError, LibroResult};
+use libro_core::models::{BookFilter, ExtendedBook};
+use libro_core::stats::{
+    author_titles, latest_books, latest_reviews, reading_pace, year_in_review, BookDate,
+    LibraryStats, YearInReview,
+};
+use std::path::PathBuf;
+
+/// Which report sections were requested on the command line
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    pub authors: bool,
+    pub books: bool,
+    pub reviews: bool,
+    pub years: bool,
+    pub genres: bool,
+    pub ratings: bool,
+    pub pages: bool,
+    pub pace: bool,
+    /// Year-in-review summary for this year instead of a single section
+    pub wrapped: Option<u32>,
+    pub year: Option<u32>,
+    pub limit: u32,
+    pub svg: Option<PathBuf>,
+    pub dates: DateOptions,
+}
 
 /// Generate reading reports and summaries
-pub fn run(
-    show_authors: bool,
-    show_books: bool,
-    show_reviews: bool,
-    year: Option<u32>,
-    years: bool,
-    limit: u32,
-) -> LibroResult<()> {
+pub fn run(options: ReportOptions) -> LibroResult<()> {
+    let machine_readable = is_machine_readable();
+    let record_options = options.clone();
+    let ReportOptions {
+        authors: show_authors,
+        books: show_books,
+        reviews: show_reviews,
+        years,
+        genres: show_genres,
+        ratings: show_ratings,
+        pages: show_pages,
+        pace: show_pace,
+        wrapped,
+        year,
+        limit,
+        svg,
+        dates,
+    } = options;
+
     // Initialize database connection
-    let db = Database::new(&get_db_path())?;
+    let db = open_store()?;
 
     // Validate year input
     let filter_year = validate_year_option(year)?;
@@ -31,7 +70,60 @@ pub fn run(
     };
 
     // Query books
-    let books = db.get_books(&filter)?;
+    let mut books = db.get_books(&filter)?;
+    let by = dates.sort.unwrap_or_default().into();
+    dates.apply(&mut books)?;
+
+    // --wrapped는 --year 대신 자체 연도와 그 전해를 봄
+    if let Some(wrapped_year) = validate_year_option(wrapped)? {
+        let wrapped = year_in_review(&books, wrapped_year);
+        return match output_format() {
+            OutputFormat::Markdown => {
+                print!("{}", render_year_in_review(&wrapped));
+                Ok(())
+            }
+            _ if machine_readable => {
+                not_tabular("report --wrapped")?;
+                print_record(&wrapped)
+            }
+            _ => {
+                show_wrapped_summary(&wrapped);
+                Ok(())
+            }
+        };
+    }
+
+    let stats = LibraryStats::from_books(&books);
+
+    if let Some(path) = svg {
+        // SVG 차트는 책이 없어도 빈 차트로 생성
+        let svg = if years {
+            render_line_chart(&ChartData::new("Books Read per Year", year_points(&stats)))
+        } else if show_authors {
+            render_bar_chart(&ChartData::new("Top Authors", author_points(&stats, limit)))
+        } else if show_genres {
+            render_bar_chart(&ChartData::new("Books per Genre", genre_points(&stats)))
+        } else if show_ratings {
+            render_bar_chart(&ChartData::new(
+                "Rating Distribution",
+                rating_points(&stats),
+            ))
+        } else if show_pages {
+            render_bar_chart(&ChartData::new("Books by Length", page_points(&stats)))
+        } else {
+            return Err(LibroError::invalid_input(
+                "--svg needs one of --years, --authors, --genres, --ratings or --pages",
+            ));
+        };
+
+        std::fs::write(&path, svg)?;
+        print_success(&format!("Chart written to {}", path.display()));
+        return Ok(());
+    }
+
+    if machine_readable {
+        return print_report_records(&books, &stats, &record_options);
+    }
 
     if books.is_empty() {
         print_info("No books found for generating reports");
@@ -41,67 +133,67 @@ pub fn run(
     // Generate reports based on flags
     if show_authors {
         // Author statistics
-        show_authors_summary(&books, limit);
+        show_authors_summary(&books, &stats, limit);
     } else if show_books {
         // Latest books
-        show_books_summary(&books, limit);
+        show_books_summary(&books, by, limit);
     } else if show_reviews {
         // Latest reviews
         show_reviews_summary(&books, limit);
+    } else if show_genres {
+        show_genres_summary(&stats);
+    } else if show_ratings {
+        print_bar_chart("⭐ Rating Distribution", &rating_points(&stats));
+        if let Some(average) = stats.totals.average_rating {
+            println!(
+                "\nAverage: {:.1}/{} over {} review(s)",
+                average, stats.totals.rating_scale, stats.totals.total_reviews
+            );
+        }
+    } else if show_pages {
+        show_pages_summary(&stats);
+    } else if show_pace {
+        show_pace_summary(&books, limit);
     } else if years {
         // Years chart - show reading stats with year breakdown
-        let reading_stats = format_reading_stats(&books);
-        println!("{}", reading_stats);
+        println!("{}", format_reading_stats(&stats));
 
         // Additional year-by-year breakdown based on reading dates
         println!("\n📅 Year-by-Year Reading Chart (by read date):");
         println!("{}", "═".repeat(50));
 
-        let mut year_counts = std::collections::HashMap::new();
-        for book in &books {
-            for review in &book.reviews {
-                if let Some(date_read) = review.date_read {
-                    let read_year = date_read.year();
-                    *year_counts.entry(read_year).or_insert(0) += 1;
-                }
-            }
-        }
-
-        if year_counts.is_empty() {
+        if stats.years.is_empty() {
             println!("No reading dates available for chart generation.");
         } else {
-            let mut years: Vec<_> = year_counts.iter().collect();
-            years.sort_by_key(|(year, _)| *year);
-
-            for (year, count) in years {
-                let bar = "█".repeat(*count);
+            for count in &stats.years {
                 println!(
                     "{}: {} ({} book{})",
-                    year,
-                    bar,
-                    count,
-                    if *count == 1 { "" } else { "s" }
+                    count.year,
+                    "█".repeat(count.books),
+                    count.books,
+                    if count.books == 1 { "" } else { "s" }
                 );
             }
         }
     } else {
         // Default: general reading statistics
-        let reading_stats = format_reading_stats(&books);
-        println!("{}", reading_stats);
+        println!("{}", format_reading_stats(&stats));
     }
 
     Ok(())
 }
 
 /// Show latest books summary
-fn show_books_summary(books: &[crate::lib::models::ExtendedBook], limit: u32) {
-    let mut sorted_books = books.to_vec();
-    sorted_books.sort_by(|a, b| b.book.id.cmp(&a.book.id));
-    sorted_books.truncate(limit as usize);
+fn show_books_summary(books: &[ExtendedBook], by: BookDate, limit: u32) {
+    let sorted_books = latest_books(books, by, limit);
+    let heading = match by {
+        BookDate::Added => "Latest",
+        BookDate::Modified => "Recently Modified",
+    };
 
     println!(
         "{}",
-        style(&format!("📚 Latest {} Books", sorted_books.len()))
+        style(&format!("📚 {} {} Books", heading, sorted_books.len()))
             .bold()
             .green()
     );
@@ -127,6 +219,10 @@ fn show_books_summary(books: &[crate::lib::models::ExtendedBook], limit: u32) {
 
         print!(" • 🏷️ {}", &book.book.genre);
 
+        if let Some(stamp) = by.of(book) {
+            print!(" • 🕒 {}", stamp);
+        }
+
         println!();
 
         if i < sorted_books.len() - 1 {
@@ -136,7 +232,7 @@ fn show_books_summary(books: &[crate::lib::models::ExtendedBook], limit: u32) {
 }
 
 /// Show latest reviews summary
-fn show_reviews_summary(books: &[crate::lib::models::ExtendedBook], limit: u32) {
+fn show_reviews_summary(books: &[ExtendedBook], limit: u32) {
     let mut reviews_with_books = Vec::new();
     for book in books {
         for review in &book.reviews {
@@ -149,7 +245,7 @@ fn show_reviews_summary(books: &[crate::lib::models::ExtendedBook], limit: u32)
         return;
     }
 
-    reviews_with_books.sort_by(|a, b| b.0.id.cmp(&a.0.id));
+    reviews_with_books.sort_by(|a, b| (&b.0.created_at, b.0.id).cmp(&(&a.0.created_at, a.0.id)));
     reviews_with_books.truncate(limit as usize);
 
     println!(
@@ -165,10 +261,11 @@ fn show_reviews_summary(books: &[crate::lib::models::ExtendedBook], limit: u32)
         let stars = "⭐".repeat(review.rating as usize);
 
         println!(
-            "{}. {} {}/5 - {}",
+            "{}. {} {}/{} - {}",
             style(&format!("{:2}", i + 1)).dim(),
             style(&stars).yellow(),
             review.rating,
+            max_rating(),
             style(&book.book.title).bold()
         );
 
@@ -209,69 +306,385 @@ fn show_reviews_summary(books: &[crate::lib::models::ExtendedBook], limit: u32)
 }
 
 /// Show authors summary
-fn show_authors_summary(books: &[crate::lib::models::ExtendedBook], limit: u32) {
-    let mut author_stats: HashMap<String, usize> = HashMap::new();
-
-    for book in books {
-        for author in &book.authors {
-            *author_stats.entry(author.name.clone()).or_insert(0) += 1;
-        }
-    }
-
-    let mut sorted_authors: Vec<_> = author_stats.into_iter().collect();
-    sorted_authors.sort_by(|a, b| b.1.cmp(&a.1));
-    sorted_authors.truncate(limit as usize);
+fn show_authors_summary(books: &[ExtendedBook], stats: &LibraryStats, limit: u32) {
+    let top_authors = &stats.authors[..stats.authors.len().min(limit as usize)];
 
     println!(
         "{}",
-        style(&format!("👥 Top {} Authors", sorted_authors.len()))
+        style(&format!("👥 Top {} Authors", top_authors.len()))
             .bold()
             .green()
     );
     println!("{}", "═".repeat(50));
 
-    for (i, (author_name, book_count)) in sorted_authors.iter().enumerate() {
+    for (i, count) in top_authors.iter().enumerate() {
         println!(
             "{}. {}",
             style(&format!("{:2}", i + 1)).dim(),
-            style(author_name).bold()
+            style(&count.author).bold()
         );
 
         println!(
             "   📚 {} book{}",
-            book_count,
-            if *book_count == 1 { "" } else { "s" }
+            count.books,
+            if count.books == 1 { "" } else { "s" }
         );
 
-        let author_books: Vec<_> = books
-            .iter()
-            .filter(|book| book.authors.iter().any(|a| a.name == *author_name))
-            .take(3)
-            .collect();
-
-        for (j, book) in author_books.iter().enumerate() {
-            let prefix = if j == author_books.len() - 1 && author_books.len() < *book_count {
+        let titles = author_titles(books, &count.author);
+        let shown = titles.len().min(3);
+        for (j, title) in titles[..shown].iter().enumerate() {
+            let prefix = if j == shown - 1 && shown == titles.len() {
                 "└─"
             } else {
                 "├─"
             };
-            println!(
-                "   {} {}",
-                style(prefix).dim(),
-                style(&book.book.title).dim()
-            );
+            println!("   {} {}", style(prefix).dim(), style(title).dim());
         }
 
-        if author_books.len() < *book_count {
+        if shown < titles.len() {
             println!(
                 "   {} {} more...",
                 style("└─").dim(),
-                style(&format!("and {} ", book_count - author_books.len())).dim()
+                style(&format!("and {} ", titles.len() - shown)).dim()
             );
         }
 
-        if i < sorted_authors.len() - 1 {
+        if i < top_authors.len() - 1 {
             println!();
         }
     }
 }
+
+/// Show books, pages and average rating per genre, then translated vs original
+fn show_genres_summary(stats: &LibraryStats) {
+    print_bar_chart("🏷️  Books per Genre", &genre_points(stats));
+    println!();
+
+    for count in &stats.genres {
+        let rating = count
+            .average_rating
+            .map(|r| format!(" • ⭐ {:.1}/{}", r, stats.totals.rating_scale))
+            .unwrap_or_default();
+        println!(
+            "{} {} book{} • 📄 {} pages{}",
+            style(&count.genre).bold(),
+            count.books,
+            if count.books == 1 { "" } else { "s" },
+            count.pages,
+            rating
+        );
+    }
+
+    let translated = stats.totals.translated_books;
+    println!(
+        "\n🌐 Translated: {} • Original: {}",
+        translated,
+        stats.totals.total_books - translated
+    );
+}
+
+/// Show books per length bucket with the average, longest and shortest
+fn show_pages_summary(stats: &LibraryStats) {
+    print_bar_chart("📄 Books by Length", &page_points(stats));
+
+    let pages = &stats.pages;
+    let Some(average) = pages.average_pages else {
+        return;
+    };
+    println!();
+    println!(
+        "Average: {:.0} pages over {} book(s)",
+        average, pages.books_with_pages
+    );
+    if let (Some(longest), Some(shortest)) = (&pages.longest, &pages.shortest) {
+        println!("Longest: {} ({} pages)", longest.title, longest.pages);
+        println!("Shortest: {} ({} pages)", shortest.title, shortest.pages);
+    }
+}
+
+/// Show finishes per month and week, streaks and the days between finishes
+fn show_pace_summary(books: &[ExtendedBook], limit: u32) {
+    let pace = reading_pace(books, Local::now().date_naive()).recent(limit as usize);
+
+    println!("{}", style("🏃 Reading Pace").bold().green());
+    println!("{}", "═".repeat(50));
+    let (Some(first), Some(last)) = (&pace.first_finished, &pace.last_finished) else {
+        println!("No reading dates available for pace.");
+        return;
+    };
+    println!(
+        "Finished: {} book(s), {} pages ({} to {})",
+        pace.finished, pace.pages, first, last
+    );
+    if let Some(days) = pace.average_days_between {
+        println!("Average: {:.1} days between finishes", days);
+    }
+    println!(
+        "Longest streak: {} week{}",
+        pace.longest_streak_weeks,
+        if pace.longest_streak_weeks == 1 {
+            ""
+        } else {
+            "s"
+        }
+    );
+    println!(
+        "Current streak: {} week{}",
+        pace.current_streak_weeks,
+        if pace.current_streak_weeks == 1 {
+            ""
+        } else {
+            "s"
+        }
+    );
+
+    for (title, periods) in [("Monthly", &pace.monthly), ("Weekly", &pace.weekly)] {
+        println!();
+        println!(
+            "{}",
+            style(&format!("📅 {} (last {})", title, periods.len()))
+                .bold()
+                .green()
+        );
+        for period in periods {
+            println!(
+                "{:<8}  {} {} book{} · {} pages",
+                period.period,
+                "█".repeat(period.books),
+                period.books,
+                if period.books == 1 { "" } else { "s" },
+                period.pages
+            );
+        }
+    }
+}
+
+/// Show a year in review: totals, standout books, top authors and genres and
+/// the change from the year before
+fn show_wrapped_summary(wrapped: &YearInReview) {
+    let totals = &wrapped.totals;
+    println!(
+        "{}",
+        style(&format!("🎁 {} in Books", wrapped.year))
+            .bold()
+            .green()
+    );
+    println!("{}", "═".repeat(50));
+    if totals.total_books == 0 {
+        println!("No books read in {}.", wrapped.year);
+        return;
+    }
+
+    println!(
+        "📚 {} book{} • 📄 {} pages • ✍️  {} author{}",
+        totals.total_books,
+        if totals.total_books == 1 { "" } else { "s" },
+        totals.total_pages,
+        totals.total_authors,
+        if totals.total_authors == 1 { "" } else { "s" }
+    );
+    if let Some(average) = totals.average_rating {
+        println!(
+            "⭐ Average: {:.1}/{} over {} review(s)",
+            average, totals.rating_scale, totals.total_reviews
+        );
+    }
+    if let Some(share) = wrapped.translated_share {
+        println!(
+            "🌐 Translated: {} of {} ({:.0}%)",
+            totals.translated_books,
+            totals.total_books,
+            share * 100.0
+        );
+    }
+    if let (Some(first), Some(last)) = (&wrapped.first_book, &wrapped.last_book) {
+        println!(
+            "🌱 First: {} ({})",
+            style(&first.title).bold(),
+            first.date_read
+        );
+        println!(
+            "🏁 Last:  {} ({})",
+            style(&last.title).bold(),
+            last.date_read
+        );
+    }
+    if let (Some(longest), Some(shortest)) = (&wrapped.longest, &wrapped.shortest) {
+        println!("📏 Longest: {} ({} pages)", longest.title, longest.pages);
+        println!("📐 Shortest: {} ({} pages)", shortest.title, shortest.pages);
+    }
+
+    println!();
+    println!("{}", style("🏆 Top Rated").bold().green());
+    for (rank, book) in wrapped.top_rated.iter().enumerate() {
+        println!(
+            "{}. {} by {} ⭐ {}/{}",
+            rank + 1,
+            style(&book.title).bold(),
+            book.authors,
+            book.rating,
+            totals.rating_scale
+        );
+    }
+
+    println!();
+    println!("{}", style("✍️  Most-Read Authors").bold().green());
+    for (rank, author) in wrapped.authors.iter().enumerate() {
+        println!(
+            "{}. {} ({} book{})",
+            rank + 1,
+            author.author,
+            author.books,
+            if author.books == 1 { "" } else { "s" }
+        );
+    }
+
+    println!();
+    println!("{}", style("🏷️  Top Genres").bold().green());
+    for (rank, genre) in wrapped.genres.iter().enumerate() {
+        println!(
+            "{}. {} ({} book{})",
+            rank + 1,
+            genre.genre,
+            genre.books,
+            if genre.books == 1 { "" } else { "s" }
+        );
+    }
+
+    let changes = wrapped.changes();
+    if !changes.is_empty() {
+        println!();
+        println!(
+            "{}",
+            style(&format!("📈 Compared with {}", wrapped.year - 1))
+                .bold()
+                .green()
+        );
+        for change in changes {
+            println!(
+                "{:<15} {:>8} → {:>8}  ({})",
+                change.label,
+                format_rating(change.previous),
+                format_rating(change.current),
+                format_change(change.delta())
+            );
+        }
+    }
+}
+
+/// Print chart points as a terminal bar chart
+fn print_bar_chart(title: &str, points: &[(String, f64)]) {
+    println!("{}", style(title).bold().green());
+    println!("{}", "═".repeat(50));
+
+    if points.is_empty() {
+        println!("No data available for chart generation.");
+        return;
+    }
+
+    let width = points
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (label, value) in points {
+        let count = *value as usize;
+        println!(
+            "{}{}: {} ({})",
+            label,
+            " ".repeat(width - label.chars().count()),
+            "█".repeat(count),
+            count
+        );
+    }
+}
+
+fn year_points(stats: &LibraryStats) -> Vec<(String, f64)> {
+    stats
+        .years
+        .iter()
+        .map(|c| (c.year.to_string(), c.books as f64))
+        .collect()
+}
+
+fn author_points(stats: &LibraryStats, limit: u32) -> Vec<(String, f64)> {
+    stats
+        .authors
+        .iter()
+        .take(limit as usize)
+        .map(|c| (c.author.clone(), c.books as f64))
+        .collect()
+}
+
+fn genre_points(stats: &LibraryStats) -> Vec<(String, f64)> {
+    stats
+        .genres
+        .iter()
+        .map(|c| (c.genre.clone(), c.books as f64))
+        .collect()
+}
+
+fn rating_points(stats: &LibraryStats) -> Vec<(String, f64)> {
+    stats
+        .ratings
+        .iter()
+        .map(|c| (format!("{}★", c.rating), c.reviews as f64))
+        .collect()
+}
+
+fn page_points(stats: &LibraryStats) -> Vec<(String, f64)> {
+    stats
+        .page_lengths
+        .iter()
+        .map(|b| (format!("{} ({})", b.length, b.pages), b.books as f64))
+        .collect()
+}
+
+/// Reports with nested lists have no single row shape, so csv and tsv are
+/// refused before anything is printed
+fn not_tabular(report: &str) -> LibroResult<()> {
+    match output_format() {
+        format @ (OutputFormat::Csv | OutputFormat::Tsv) => {
+            Err(LibroError::invalid_input(format!(
+                "--format {} is not supported by {}; use json, ndjson or yaml",
+                format.name(),
+                report
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Print the requested report as records for `--format`
+fn print_report_records(
+    books: &[ExtendedBook],
+    stats: &LibraryStats,
+    options: &ReportOptions,
+) -> LibroResult<()> {
+    if options.authors {
+        let limit = stats.authors.len().min(options.limit as usize);
+        print_records(&stats.authors[..limit])
+    } else if options.books {
+        let by = options.dates.sort.unwrap_or_default().into();
+        let rows: Vec<BookRecord> = latest_books(books, by, options.limit)
+            .iter()
+            .map(BookRecord::from)
+            .collect();
+        print_records(&rows)
+    } else if options.reviews {
+        print_records(&latest_reviews(books, options.limit))
+    } else if options.genres {
+        print_records(&stats.genres)
+    } else if options.ratings {
+        print_records(&stats.ratings)
+    } else if options.pages {
+        print_records(&stats.page_lengths)
+    } else if options.pace {
+        not_tabular("report --pace")?;
+        print_record(&reading_pace(books, Local::now().date_naive()).recent(options.limit as usize))
+    } else if options.years {
+        print_records(&stats.years)
+    } else {
+        print_record(&stats.totals)
+    }
+}
@@ -0,0 +1,52 @@
+use crate::utils::database::{get_db_path, require_sqlite};
+use crate::utils::error_handler::{print_info, print_success};
+use crate::utils::input::confirm;
+use crate::utils::output::{is_machine_readable, print_record};
+use libro_core::backup::{restore_database, validate_backup};
+use libro_core::errors::{LibroError, LibroResult};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// What `restore` did, printed with a machine-readable `--format`
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreResult {
+    pub database: String,
+    pub backup: String,
+    pub schema_version: i32,
+    pub previous: Option<String>,
+}
+
+/// Replace the current database with a backup after validating it
+pub fn run(file: PathBuf, yes: bool) -> LibroResult<()> {
+    require_sqlite("restore")?;
+    let db_path = get_db_path();
+
+    // Validate before asking so a bad file never gets as far as the prompt
+    validate_backup(&file)?;
+
+    if !yes && !confirm(&format!("Replace {} with {}?", db_path, file.display()))? {
+        return Err(LibroError::UserCancelled);
+    }
+
+    let summary = restore_database(&file, Path::new(&db_path))?;
+
+    print_success(&format!(
+        "Restored {} from {} (schema version {})",
+        db_path,
+        file.display(),
+        summary.schema_version
+    ));
+    if let Some(previous) = &summary.previous {
+        print_info(&format!("Previous database kept at {}", previous.display()));
+    }
+    if is_machine_readable() {
+        print_record(&RestoreResult {
+            database: db_path,
+            backup: file.display().to_string(),
+            schema_version: summary.schema_version,
+            previous: summary.previous.map(|p| p.display().to_string()),
+        })?;
+    }
+
+    Ok(())
+}
@@ -1,114 +1,309 @@
-use crate::lib::db_operations::Database;
-use crate::lib::errors::LibroResult;
-use crate::lib::models::BookFilter;
-use crate::utils::database::get_db_path;
+use crate::utils::database::open_store;
+use crate::utils::date::{current_date, format_date, parse_and_validate_date};
+use crate::utils::editor::{edit_review, editor_command, ReviewDraft};
 use crate::utils::error_handler::{print_info, print_success, validation::validate_book_id};
 use crate::utils::input::{prompt_edit_review, prompt_for_review, select_from_list};
+use crate::utils::output::{is_machine_readable, print_record};
+use libro_core::errors::{validation, LibroError, LibroResult};
+use libro_core::models::{BookFilter, ExtendedBook, NewReview, Review};
+use libro_core::store::LibraryStore;
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Read};
 
-/// Add or edit a review for a book
-pub fn run(id: u32) -> LibroResult<()> {
+/// Review fields given on the command line
+#[derive(Debug, Clone, Default)]
+pub struct ReviewOptions {
+    pub rating: Option<i32>,
+    pub date: Option<String>,
+    pub text: Option<String>,
+    /// Read the review text from this file ('-' for stdin)
+    pub file: Option<String>,
+    /// Edit this review instead of adding a new one
+    pub review_id: Option<i64>,
+}
+
+/// What `review` did, printed with a machine-readable `--format`
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewResult {
+    pub action: &'static str,
+    pub review_id: i64,
+    pub book_id: i64,
+}
+
+impl ReviewResult {
+    fn added(review_id: i64, book_id: i64) -> LibroResult<()> {
+        Self::print("added", review_id, book_id)
+    }
+
+    fn updated(review_id: i64, book_id: i64) -> LibroResult<()> {
+        Self::print("updated", review_id, book_id)
+    }
+
+    fn print(action: &'static str, review_id: i64, book_id: i64) -> LibroResult<()> {
+        if is_machine_readable() {
+            print_record(&ReviewResult {
+                action,
+                review_id,
+                book_id,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl ReviewOptions {
+    fn has_fields(&self) -> bool {
+        self.rating.is_some() || self.date.is_some() || self.text.is_some() || self.file.is_some()
+    }
+}
+
+/// Add or edit a review for a book.
+///
+/// With `--rating`/`--date`/`--text`/`--file` the review is written without
+/// prompting. Otherwise the review is written in `$VISUAL`/`$EDITOR` when one
+/// is set, falling back to interactive prompts.
+pub fn run(id: u32, options: ReviewOptions) -> LibroResult<()> {
     // Validate book ID
     let book_id = validate_book_id(Some(id))?;
 
     // Initialize database connection
-    let mut db = Database::new(&get_db_path())?;
+    let mut db = open_store()?;
 
     // Check if book exists
     let filter = BookFilter {
         id: Some(book_id),
         year: None,
     };
-    let books = db.get_books(&filter)?;
+    let book = db
+        .get_books(&filter)?
+        .into_iter()
+        .next()
+        .ok_or(LibroError::BookNotFound { id: book_id })?;
 
-    if books.is_empty() {
-        return Err(crate::lib::errors::LibroError::BookNotFound { id: book_id });
+    let target = match options.review_id {
+        Some(review_id) => Some(review_of_book(db.as_ref(), &book, review_id)?),
+        None => None,
+    };
+
+    if options.has_fields() {
+        return run_with_flags(db.as_mut(), &book, target, options);
     }
 
-    let book = &books[0];
-    let book_title = &book.book.title;
-
-    print_info(&format!("Managing review for: '{}'", book_title));
-
-    // Check if review already exists
-    if !book.reviews.is_empty() {
-        println!("This book already has {} review(s):", book.reviews.len());
-        for (i, review) in book.reviews.iter().enumerate() {
-            println!(
-                "  {}. Rating: {}/5 - {}",
-                i + 1,
-                review.rating,
-                if review.review.len() > 50 {
-                    format!("{}...", &review.review[..50])
-                } else {
-                    review.review.clone()
-                }
-            );
-        }
+    print_info(&format!("Managing review for: '{}'", book.book.title));
 
-        // Ask user what they want to do
-        let options = vec!["Add a new review", "Edit an existing review", "Cancel"];
+    let target = match target {
+        Some(review) => Some(review),
+        None if book.reviews.is_empty() => None,
+        None => match choose_review(&book)? {
+            Choice::Add => None,
+            Choice::Edit(review) => Some(review),
+            Choice::Cancel => {
+                print_info("Operation cancelled");
+                return Ok(());
+            }
+        },
+    };
 
-        let choice = select_from_list("What would you like to do?", &options)?;
+    match target {
+        Some(existing) => edit_existing(db.as_mut(), &book, &existing),
+        None => add_new(db.as_mut(), &book),
+    }
+}
 
-        match choice {
-            0 => {
-                // Add new review
-                let new_review = prompt_for_review(book_id, book_title)?;
-                let review_id = db.add_review(&new_review)?;
-                print_success(&format!(
-                    "Review added successfully! Review ID: {}",
-                    review_id
-                ));
-            }
-            1 => {
-                // Edit existing review
-                let review_to_edit = if book.reviews.len() == 1 {
-                    &book.reviews[0]
-                } else {
-                    let review_options: Vec<String> = book
-                        .reviews
-                        .iter()
-                        .enumerate()
-                        .map(|(i, review)| {
-                            format!(
-                                "{}. Rating: {}/5 - {}",
-                                i + 1,
-                                review.rating,
-                                if review.review.len() > 50 {
-                                    format!("{}...", &review.review[..50])
-                                } else {
-                                    review.review.clone()
-                                }
-                            )
-                        })
-                        .collect();
-
-                    let selection = select_from_list("Select review to edit:", &review_options)?;
-                    &book.reviews[selection]
-                };
-
-                let review_id = review_to_edit
-                    .id
-                    .ok_or_else(|| crate::lib::errors::LibroError::internal("Review missing ID"))?;
-
-                let updated_review = prompt_edit_review(review_to_edit, book_title)?;
-                db.update_review(review_id, &updated_review)?;
-                print_success("Review updated successfully!");
+enum Choice {
+    Add,
+    Edit(Review),
+    Cancel,
+}
+
+/// Let the user add a review or pick which existing review to edit
+fn choose_review(book: &ExtendedBook) -> LibroResult<Choice> {
+    println!("This book already has {} review(s):", book.reviews.len());
+
+    let mut options = vec!["Add a new review".to_string()];
+    options.extend(
+        book.reviews
+            .iter()
+            .map(|review| format!("Edit: {}", describe(review))),
+    );
+    options.push("Cancel".to_string());
+
+    let choice = select_from_list("What would you like to do?", &options)?;
+    Ok(match choice {
+        0 => Choice::Add,
+        i if i <= book.reviews.len() => Choice::Edit(book.reviews[i - 1].clone()),
+        _ => Choice::Cancel,
+    })
+}
+
+fn add_new(db: &mut dyn LibraryStore, book: &ExtendedBook) -> LibroResult<()> {
+    let book_id = book.book.id.unwrap_or_default();
+
+    let new_review = match editor_command() {
+        Some(editor) => {
+            let draft = ReviewDraft {
+                rating: None,
+                date_read: Some(current_date()),
+                text: String::new(),
+            };
+            let parsed = edit_review(&editor, &book.book.title, &draft)?;
+            NewReview {
+                book_id,
+                date_read: parsed.date_read,
+                rating: parsed.rating,
+                review: parsed.text,
             }
-            _ => {
-                print_info("Operation cancelled");
-                return Ok(());
+        }
+        None => prompt_for_review(book_id, &book.book.title)?,
+    };
+
+    let review_id = db.add_review(&new_review)?;
+    print_success(&format!(
+        "Review added successfully! Review ID: {}",
+        review_id
+    ));
+    ReviewResult::added(review_id, book_id)
+}
+
+fn edit_existing(
+    db: &mut dyn LibraryStore,
+    book: &ExtendedBook,
+    existing: &Review,
+) -> LibroResult<()> {
+    let review_id = existing
+        .id
+        .ok_or_else(|| LibroError::internal("Review missing ID"))?;
+
+    let updated = match editor_command() {
+        Some(editor) => {
+            let draft = ReviewDraft {
+                rating: Some(existing.rating),
+                date_read: existing.date_read,
+                text: existing.review.clone(),
+            };
+            let parsed = edit_review(&editor, &book.book.title, &draft)?;
+            Review {
+                id: existing.id,
+                book_id: existing.book_id,
+                date_read: parsed.date_read,
+                rating: parsed.rating,
+                review: parsed.text,
+                created_at: None,
+                updated_at: None,
             }
         }
-    } else {
-        // No existing reviews, add new one
-        let new_review = prompt_for_review(book_id, book_title)?;
-        let review_id = db.add_review(&new_review)?;
-        print_success(&format!(
-            "Review added successfully! Review ID: {}",
+        None => prompt_edit_review(existing, &book.book.title)?,
+    };
+
+    db.update_review(review_id, &updated)?;
+    print_success("Review updated successfully!");
+    ReviewResult::updated(review_id, existing.book_id)
+}
+
+fn run_with_flags(
+    db: &mut dyn LibraryStore,
+    book: &ExtendedBook,
+    target: Option<Review>,
+    options: ReviewOptions,
+) -> LibroResult<()> {
+    let text = match (&options.text, &options.file) {
+        (Some(text), _) => Some(text.trim().to_string()),
+        (None, Some(file)) => Some(read_text(file)?),
+        (None, None) => None,
+    };
+    let date_read = options
+        .date
+        .as_deref()
+        .map(parse_and_validate_date)
+        .transpose()?;
+
+    match target {
+        Some(existing) => {
+            let updated = Review {
+                id: existing.id,
+                book_id: existing.book_id,
+                date_read: date_read.or(existing.date_read),
+                rating: options.rating.unwrap_or(existing.rating),
+                review: text.unwrap_or(existing.review),
+                created_at: None,
+                updated_at: None,
+            };
+            let review_id = existing.id.unwrap_or_default();
+            db.update_review(review_id, &updated)?;
+            print_success("Review updated successfully!");
+            ReviewResult::updated(review_id, existing.book_id)
+        }
+        None => {
+            let rating = options.rating.ok_or_else(|| {
+                LibroError::invalid_input("--rating is required for a new review")
+            })?;
+            let review = text.unwrap_or_default();
+            validation::validate_non_empty(&review, "Review text")?;
+
+            let book_id = book.book.id.unwrap_or_default();
+            let review_id = db.add_review(&NewReview {
+                book_id,
+                date_read,
+                rating,
+                review,
+            })?;
+            print_success(&format!(
+                "Review added successfully! Review ID: {}",
+                review_id
+            ));
+            ReviewResult::added(review_id, book_id)
+        }
+    }
+}
+
+/// Look up a review and make sure it belongs to the book
+fn review_of_book(
+    db: &dyn LibraryStore,
+    book: &ExtendedBook,
+    review_id: i64,
+) -> LibroResult<Review> {
+    let review = db.get_review(review_id)?;
+    if Some(review.book_id) != book.book.id {
+        return Err(LibroError::invalid_input(format!(
+            "Review #{} belongs to another book",
             review_id
-        ));
+        )));
     }
+    Ok(review)
+}
 
-    Ok(())
+fn read_text(file: &str) -> LibroResult<String> {
+    let text = if file == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+    } else {
+        fs::read_to_string(file)?
+    };
+    Ok(text.trim().to_string())
+}
+
+/// One-line summary of a review for selection lists
+fn describe(review: &Review) -> String {
+    let date = review
+        .date_read
+        .map(|d| format_date(&d))
+        .unwrap_or_else(|| "undated".to_string());
+    let first_line = review.review.lines().next().unwrap_or_default();
+    let snippet: String = first_line.chars().take(50).collect();
+    let ellipsis = if snippet.chars().count() < review.review.chars().count() {
+        "..."
+    } else {
+        ""
+    };
+    format!(
+        "#{} {} {}/{} - {}{}",
+        review.id.unwrap_or_default(),
+        date,
+        review.rating,
+        validation::max_rating(),
+        snippet,
+        ellipsis
+    )
 }
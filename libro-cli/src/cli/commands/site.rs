@@ -0,0 +1,27 @@
+use crate::utils::database::open_store;
+use crate::utils::error_handler::print_success;
+use crate::utils::output::{is_machine_readable, print_record};
+use crate::utils::site::generate_site;
+use libro_core::errors::LibroResult;
+use libro_core::models::BookFilter;
+use std::path::PathBuf;
+
+/// Generate a static HTML site for the reading log
+pub fn run(out: PathBuf) -> LibroResult<()> {
+    let db = open_store()?;
+    let books = db.get_books(&BookFilter::default())?;
+
+    let summary = generate_site(&books, &out)?;
+
+    print_success(&format!(
+        "Site written to {}: {} page(s), {} stale page(s) removed",
+        out.display(),
+        summary.pages,
+        summary.removed
+    ));
+    if is_machine_readable() {
+        print_record(&summary)?;
+    }
+
+    Ok(())
+}
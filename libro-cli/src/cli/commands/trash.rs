@@ -0,0 +1,108 @@
+use crate::utils::database::open_store;
+use crate::utils::error_handler::{print_info, print_success};
+use crate::utils::input::confirm;
+use crate::utils::output::{is_machine_readable, print_records};
+use chrono::{Duration, Local};
+use console::style;
+use libro_core::errors::{LibroError, LibroResult};
+use libro_core::trash::{TrashItem, TrashKind};
+
+/// List trashed books and reviews, most recently deleted first
+pub fn list() -> LibroResult<()> {
+    let db = open_store()?;
+    let items = db.trash()?;
+    if is_machine_readable() {
+        return print_records(&items);
+    }
+    if items.is_empty() {
+        print_info("The trash is empty");
+        return Ok(());
+    }
+
+    for item in &items {
+        println!("{}  {}", style(&item.deleted_at).dim(), describe(item));
+    }
+    Ok(())
+}
+
+/// Bring a book back from the trash with its reviews and writers
+pub fn restore_book(id: i64) -> LibroResult<()> {
+    let mut db = open_store()?;
+    db.restore_book(id)?;
+    print_success(&format!("Restored book #{}", id));
+    Ok(())
+}
+
+/// Bring a review back from the trash
+pub fn restore_review(id: i64) -> LibroResult<()> {
+    let mut db = open_store()?;
+    db.restore_review(id)?;
+    print_success(&format!("Restored review #{}", id));
+    Ok(())
+}
+
+/// Permanently delete trashed items, optionally only those older than a duration
+pub fn purge(older_than: Option<String>, yes: bool) -> LibroResult<()> {
+    let cutoff = older_than
+        .as_deref()
+        .map(|age| parse_age(age).map(|age| Local::now().naive_local() - age))
+        .transpose()?;
+
+    let mut db = open_store()?;
+    let count = db
+        .trash()?
+        .iter()
+        .filter(|item| item.deleted_before(cutoff))
+        .count();
+    if count == 0 {
+        print_info("Nothing in the trash to purge");
+        return Ok(());
+    }
+    if !yes
+        && !confirm(&format!(
+            "Permanently delete {} item(s) from the trash?",
+            count
+        ))?
+    {
+        return Err(LibroError::UserCancelled);
+    }
+
+    let purged = db.purge_trash(cutoff)?;
+    print_success(&format!("Purged {} item(s) from the trash", purged.len()));
+    if is_machine_readable() {
+        print_records(&purged)?;
+    }
+    Ok(())
+}
+
+/// One line naming a trashed item and what comes back with it
+fn describe(item: &TrashItem) -> String {
+    match item.kind {
+        TrashKind::Book => format!(
+            "book #{} {} ({} review(s))",
+            item.id, item.title, item.reviews
+        ),
+        TrashKind::Review => format!("review #{} of #{} {}", item.id, item.book_id, item.title),
+    }
+}
+
+/// Parse an age like `30d` or `2w`
+fn parse_age(age: &str) -> LibroResult<Duration> {
+    let invalid = || {
+        LibroError::invalid_field(
+            "older-than",
+            "format:<n>d|<n>w",
+            format!("'{}' is not an age like 30d or 2w", age),
+        )
+    };
+    let age = age.trim();
+    let (count, days) = if let Some(count) = age.strip_suffix('d') {
+        (count, 1)
+    } else if let Some(count) = age.strip_suffix('w') {
+        (count, 7)
+    } else {
+        return Err(invalid());
+    };
+    let count: u32 = count.parse().map_err(|_| invalid())?;
+    Ok(Duration::days(i64::from(count) * days))
+}
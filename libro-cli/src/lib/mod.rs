@@ -1,4 +0,0 @@
-pub mod db;
-pub mod models;
-pub mod errors;
-pub mod db_operations;
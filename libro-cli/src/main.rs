@@ -1,29 +1,94 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use std::path::PathBuf;
 
 mod cli;
-mod lib;
 mod tui;
 mod utils;
 
+/// Exit statuses listed under `--help`, matching `utils::error_handler::exit_status`
+const EXIT_STATUS_HELP: &str = "\
+Exit statuses:
+  0    success
+  1    unexpected failure (internal)
+  2    bad arguments or input (invalid_input)
+  3    a value broke a rule (validation, date_parse)
+  4    the requested item does not exist (book/review/writer_not_found)
+  5    database error (database)
+  6    another process holds the database (database_locked)
+  7    file system error (io)
+  8    malformed JSON input (json)
+  130  declined a prompt or left the editor empty (cancelled)";
+
 #[derive(Parser)]
 #[command(
     name = "libro-cli",
     version,
-    about = "A command-line book tracking tool with data stored in SQLite"
+    about = "A command-line book tracking tool with data stored in SQLite",
+    after_long_help = EXIT_STATUS_HELP
 )]
 struct Args {
     /// Use CLI mode instead of TUI
     #[arg(long)]
     cli: bool,
 
+    /// Database file (overrides LIBRO_DB_PATH and the config file)
+    #[arg(long, global = true, value_name = "PATH")]
+    db: Option<PathBuf>,
+
+    /// Named library to use for this run instead of the active one
+    #[arg(long, global = true, value_name = "NAME", conflicts_with = "db")]
+    library: Option<String>,
+
+    /// Output format (markdown is only for `export` and `report --wrapped`)
+    #[arg(long, global = true, value_enum)]
+    format: Option<utils::output::OutputFormat>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Add a new book
-    Add,
+    /// Add a new book (interactive unless book fields or --from-json are given)
+    Add {
+        /// Book title
+        #[arg(long)]
+        title: Option<String>,
+        /// Author (repeat for several)
+        #[arg(long = "author", value_name = "NAME")]
+        authors: Vec<String>,
+        /// Translator (repeat for several)
+        #[arg(long = "translator", value_name = "NAME")]
+        translators: Vec<String>,
+        /// Genre
+        #[arg(long)]
+        genre: Option<String>,
+        /// Number of pages
+        #[arg(long)]
+        pages: Option<i32>,
+        /// Publication year
+        #[arg(long)]
+        year: Option<i32>,
+        /// Review rating (1-5 unless `defaults.rating_scale` is set)
+        #[arg(long, requires = "review")]
+        rating: Option<i32>,
+        /// Date read (YYYY-MM-DD, defaults to today)
+        #[arg(long, requires = "rating")]
+        date: Option<String>,
+        /// Review text
+        #[arg(long, requires = "rating")]
+        review: Option<String>,
+        /// Read the book and review as JSON from a file ('-' for stdin)
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = [
+                "title", "authors", "translators", "genre", "pages",
+                "year", "rating", "date", "review",
+            ]
+        )]
+        from_json: Option<String>,
+    },
     /// Browse and search books
     Browse {
         /// Search query (title, author, or genre)
@@ -31,7 +96,16 @@ enum Commands {
         /// Show only books from specific year
         #[arg(long)]
         year: Option<u32>,
-        /// Show in JSON format
+        /// List newest first by when books were added or modified
+        #[arg(long, value_enum)]
+        sort: Option<cli::commands::browse::SortBy>,
+        /// Only books added on or after this day (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        added_since: Option<String>,
+        /// Only books modified on or after this day (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        modified_since: Option<String>,
+        /// Show in JSON format (same as --format json)
         #[arg(long)]
         json: bool,
     },
@@ -52,20 +126,294 @@ enum Commands {
         /// 연도별 차트
         #[arg(long)]
         years: bool,
-        /// 표시할 항목 수 (books, reviews, authors용)
+        /// 장르별 도서 수
+        #[arg(long)]
+        genres: bool,
+        /// 평점 분포
+        #[arg(long)]
+        ratings: bool,
+        /// 분량별 도서 수 (short/medium/long)
+        #[arg(long)]
+        pages: bool,
+        /// 월별/주별 완독 수, 연속 기록, 완독 간격
+        #[arg(long)]
+        pace: bool,
+        /// 한 해 독서 결산 (총계, 최고 평점, 작가/장르, 전년 대비)
+        #[arg(long, value_name = "YEAR", conflicts_with_all = ["year", "svg"])]
+        wrapped: Option<u32>,
+        /// 표시할 항목 수 (books, reviews, authors, pace용)
         #[arg(short, long, default_value = "10")]
         limit: u32,
+        /// 차트를 SVG 파일로 저장 (--years, --authors, --genres, --ratings, --pages)
+        #[arg(long, value_name = "FILE")]
+        svg: Option<PathBuf>,
+        /// --books 정렬 기준 (기본: added)
+        #[arg(long, value_enum)]
+        sort: Option<cli::commands::browse::SortBy>,
+        /// 이 날짜 이후에 추가된 책만 (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        added_since: Option<String>,
+        /// 이 날짜 이후에 수정된 책만 (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        modified_since: Option<String>,
     },
     /// Add or edit a review for a book
     Review {
         /// 리뷰할 책 ID
         id: u32,
+        /// Rating (1-5 unless `defaults.rating_scale` is set)
+        #[arg(long)]
+        rating: Option<i32>,
+        /// Date read (YYYY-MM-DD)
+        #[arg(long)]
+        date: Option<String>,
+        /// Review text
+        #[arg(long, conflicts_with = "file")]
+        text: Option<String>,
+        /// Read the review text from a file ('-' for stdin)
+        #[arg(long, value_name = "FILE")]
+        file: Option<String>,
+        /// Edit this review instead of adding a new one
+        #[arg(long, value_name = "REVIEW_ID")]
+        edit: Option<i64>,
+    },
+    /// Export the whole library as a versioned archive
+    Export {
+        /// Output file (defaults to stdout), or directory for markdown
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Import an archive produced by `export`
+    Import {
+        /// Archive file to read ('-' for stdin)
+        file: String,
+    },
+    /// Generate a static HTML site for the reading log
+    Site {
+        /// Output directory
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Back up the database while it is in use
+    Backup {
+        /// Backup file, or directory for timestamped backups (defaults to backups/ next to the database)
+        dest: Option<PathBuf>,
+        /// Number of timestamped backups to keep (0 keeps all)
+        #[arg(long, default_value_t = utils::database::DEFAULT_BACKUP_KEEP)]
+        keep: usize,
+    },
+    /// Move a book or a review to the trash
+    Delete {
+        #[command(subcommand)]
+        target: DeleteTarget,
+        /// Skip the confirmation prompt
+        #[arg(long, global = true)]
+        yes: bool,
+        /// Show what would be deleted without deleting anything
+        #[arg(long, global = true)]
+        dry_run: bool,
+    },
+    /// Compare or merge libro databases
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+    /// Restore the database from a backup
+    Restore {
+        /// Backup file to restore
+        file: PathBuf,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// List, restore or purge deleted books and reviews
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommands,
+    },
+    /// Show recorded changes, newest first
+    History {
+        /// Only changes to this book, its reviews or its writers
+        #[arg(long, value_name = "ID")]
+        book: Option<i64>,
+    },
+    /// Revert the most recent changes
+    Undo {
+        /// Number of changes to revert
+        #[arg(default_value_t = 1)]
+        count: usize,
+    },
+    /// Copy the library into another storage backend
+    Convert {
+        /// Backend to convert to
+        #[arg(long, value_enum)]
+        to: utils::config::Backend,
+        /// Destination (defaults to where that backend keeps the current library)
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+    },
+    /// Check the database for corruption and inconsistent data
+    Doctor {
+        /// Apply the safe repairs in a single transaction
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Show or change settings in the config file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Manage separate named libraries
+    Library {
+        #[command(subcommand)]
+        command: LibraryCommands,
+    },
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Print the man page, or write one page per command with --out
+    Man {
+        /// Directory for the man pages
+        #[arg(long, value_name = "DIR")]
+        out: Option<PathBuf>,
+    },
+    /// List completion candidates for the shell scripts
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        #[arg(value_enum)]
+        kind: cli::commands::completions::CompletionKind,
+    },
+}
+
+#[derive(Subcommand)]
+enum DeleteTarget {
+    /// Move a book with its reviews to the trash
+    Book {
+        /// 삭제할 책 ID
+        id: i64,
+    },
+    /// Move a single review to the trash
+    Review {
+        /// 삭제할 리뷰 ID
+        id: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum TrashCommands {
+    /// List deleted books and reviews, most recent first
+    List,
+    /// Bring a book (with its reviews) or a review back
+    Restore {
+        #[command(subcommand)]
+        target: RestoreTarget,
+    },
+    /// Permanently delete what is in the trash
+    Purge {
+        /// Only items deleted longer ago than this, like 30d or 2w
+        #[arg(long, value_name = "AGE")]
+        older_than: Option<String>,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum RestoreTarget {
+    /// Restore a book with its reviews and writers
+    Book {
+        /// 복원할 책 ID
+        id: i64,
+    },
+    /// Restore a single review
+    Review {
+        /// 복원할 리뷰 ID
+        id: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum DbCommands {
+    /// Show books, writers and reviews that differ between two databases
+    Diff {
+        /// Base database
+        a: PathBuf,
+        /// Database to compare against the base
+        b: PathBuf,
+    },
+    /// Merge another database into the current one
+    Merge {
+        /// Database to merge from
+        #[arg(long)]
+        from: PathBuf,
+        /// How to settle books that differ on both sides
+        #[arg(long, value_enum, default_value = "prefer-newer")]
+        strategy: cli::commands::db::MergeStrategy,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print a setting, or the whole config file without a key
+    Get {
+        /// Dotted key, e.g. defaults.rating_scale or ui.keys.quit
+        key: Option<String>,
+    },
+    /// Change a setting (an empty value resets it)
+    Set {
+        /// Dotted key, e.g. defaults.rating_scale or ui.keys.quit
+        key: String,
+        value: String,
+    },
+    /// Print the location of the config file
+    Path,
+}
+
+#[derive(Subcommand)]
+enum LibraryCommands {
+    /// List libraries; the active one is marked with *
+    List,
+    /// Create an empty library
+    Create {
+        name: String,
+        /// Also make it the active library
+        #[arg(long)]
+        switch: bool,
+    },
+    /// Make a library the active one
+    Switch { name: String },
+    /// Delete a library and its database
+    Remove {
+        name: String,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
     },
 }
 
 fn main() {
     let args = Args::parse();
 
+    // 설정 우선순위: CLI 플래그 > 환경 변수 > 설정 파일 > 기본값
+    if let Err(e) = utils::config::init() {
+        eprintln!("Warning: ignoring config file: {}", e);
+    }
+    utils::output::set_output_format(args.format.unwrap_or_default());
+    libro_core::errors::validation::set_max_rating(utils::config::current().defaults.rating_scale);
+    if let Some(db) = args.db {
+        utils::database::set_db_path_override(db.to_string_lossy().into_owned());
+    }
+    if let Some(name) = args.library {
+        if let Err(e) = utils::library::ensure_exists(&name) {
+            utils::error_handler::handle_cli_error(e);
+        }
+        utils::database::set_library_override(name);
+    }
+
     let result = if args.cli || args.command.is_some() {
         // CLI 모드: --cli 플래그가 있거나 서브커맨드가 제공된 경우
         run_cli_mode(args.command)
@@ -82,25 +430,176 @@ fn main() {
 
 fn run_cli_mode(command: Option<Commands>) -> Result<(), Box<dyn std::error::Error>> {
     use utils::error_handler::handle_result;
+    use utils::output::{output_format, OutputFormat};
 
     let command = command.unwrap_or(Commands::Browse {
         query: None,
         year: None,
+        sort: None,
+        added_since: None,
+        modified_since: None,
         json: false,
     });
 
+    if output_format() == OutputFormat::Markdown
+        && !matches!(
+            command,
+            Commands::Export { .. }
+                | Commands::Report {
+                    wrapped: Some(_),
+                    ..
+                }
+        )
+    {
+        utils::error_handler::handle_cli_error(libro_core::errors::LibroError::invalid_input(
+            "--format markdown is only supported by export and report --wrapped",
+        ));
+    }
+
     let result = match command {
-        Commands::Add => cli::commands::add::run(),
-        Commands::Browse { query, year, json } => cli::commands::browse::run(query, year, json),
+        Commands::Add {
+            title,
+            authors,
+            translators,
+            genre,
+            pages,
+            year,
+            rating,
+            date,
+            review,
+            from_json,
+        } => cli::commands::add::run(cli::commands::add::AddOptions {
+            title,
+            authors,
+            translators,
+            genre,
+            pages,
+            year,
+            rating,
+            date,
+            review,
+            from_json,
+        }),
+        Commands::Browse {
+            query,
+            year,
+            sort,
+            added_since,
+            modified_since,
+            json,
+        } => cli::commands::browse::run(
+            query,
+            year,
+            cli::commands::browse::DateOptions {
+                sort,
+                added_since,
+                modified_since,
+            },
+            json,
+        ),
         Commands::Report {
             authors,
             books,
             reviews,
             year,
             years,
+            genres,
+            ratings,
+            pages,
+            pace,
+            wrapped,
+            limit,
+            svg,
+            sort,
+            added_since,
+            modified_since,
+        } => cli::commands::report::run(cli::commands::report::ReportOptions {
+            authors,
+            books,
+            reviews,
+            years,
+            genres,
+            ratings,
+            pages,
+            pace,
+            wrapped,
+            year,
             limit,
-        } => cli::commands::report::run(authors, books, reviews, year, years, limit),
-        Commands::Review { id } => cli::commands::review::run(id),
+            svg,
+            dates: cli::commands::browse::DateOptions {
+                sort,
+                added_since,
+                modified_since,
+            },
+        }),
+        Commands::Review {
+            id,
+            rating,
+            date,
+            text,
+            file,
+            edit,
+        } => cli::commands::review::run(
+            id,
+            cli::commands::review::ReviewOptions {
+                rating,
+                date,
+                text,
+                file,
+                review_id: edit,
+            },
+        ),
+        Commands::Export { out } => cli::commands::export::ExportFormat::try_from(output_format())
+            .and_then(|format| cli::commands::export::run(format, out)),
+        Commands::Import { file } => cli::commands::import::ImportFormat::try_from(output_format())
+            .and_then(|format| cli::commands::import::run(file, format)),
+        Commands::Site { out } => cli::commands::site::run(out),
+        Commands::Backup { dest, keep } => cli::commands::backup::run(dest, keep),
+        Commands::Delete {
+            target,
+            yes,
+            dry_run,
+        } => match target {
+            DeleteTarget::Book { id } => cli::commands::delete::book(id, yes, dry_run),
+            DeleteTarget::Review { id } => cli::commands::delete::review(id, yes, dry_run),
+        },
+        Commands::Db { command } => match command {
+            DbCommands::Diff { a, b } => cli::commands::db::diff(&a, &b),
+            DbCommands::Merge { from, strategy } => cli::commands::db::merge(&from, strategy),
+        },
+        Commands::Restore { file, yes } => cli::commands::restore::run(file, yes),
+        Commands::Trash { command } => match command {
+            TrashCommands::List => cli::commands::trash::list(),
+            TrashCommands::Restore { target } => match target {
+                RestoreTarget::Book { id } => cli::commands::trash::restore_book(id),
+                RestoreTarget::Review { id } => cli::commands::trash::restore_review(id),
+            },
+            TrashCommands::Purge { older_than, yes } => {
+                cli::commands::trash::purge(older_than, yes)
+            }
+        },
+        Commands::History { book } => cli::commands::history::run(book),
+        Commands::Undo { count } => cli::commands::history::undo(count),
+        Commands::Convert { to, out } => cli::commands::convert::run(to, out),
+        Commands::Doctor { fix } => cli::commands::doctor::run(fix),
+        Commands::Config { command } => match command {
+            ConfigCommands::Get { key } => cli::commands::config::get(key),
+            ConfigCommands::Set { key, value } => cli::commands::config::set(key, value),
+            ConfigCommands::Path => cli::commands::config::path(),
+        },
+        Commands::Library { command } => match command {
+            LibraryCommands::List => cli::commands::library::list(),
+            LibraryCommands::Create { name, switch } => {
+                cli::commands::library::create(name, switch)
+            }
+            LibraryCommands::Switch { name } => cli::commands::library::switch(name),
+            LibraryCommands::Remove { name, yes } => cli::commands::library::remove(name, yes),
+        },
+        Commands::Completions { shell } => {
+            cli::commands::completions::completions(shell, Args::command())
+        }
+        Commands::Man { out } => cli::commands::completions::man(Args::command(), out),
+        Commands::Complete { kind } => cli::commands::completions::candidates(kind),
     };
 
     handle_result(result);
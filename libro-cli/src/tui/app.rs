@@ -10,30 +10,56 @@ use ratatui::{
 use std::time::Duration;
 
 use crate::{
-    lib::{db_operations::Database, models::BookFilter},
     tui::{
         events::{key_to_action, AppEvent, EventHandler, KeyAction},
         input::TextInput,
-        state::{AppMode, AppState, Screen},
-        ui::{add_book, book_list, edit_book, help, report, review, search},
+        state::{next_book_date, AppMode, AppState, Screen, RECENT_DAYS},
+        theme::Theme,
+        ui::{
+            add_book, book_list, edit_book, help, library, report, review, search, trash, wrapped,
+        },
     },
+    utils::database::{backend, db_source, get_db_path, open_backend, store_path, DbSource},
+    utils::library::{library_path, list_libraries, set_active_library},
+};
+use libro_core::{
+    models::BookFilter,
+    stats::{retain_since, sort_by_date},
+    store::LibraryStore,
+    trash::TrashKind,
 };
 
 pub struct App {
     state: AppState,
     event_handler: EventHandler,
     text_input: TextInput,
-    database: Database,
+    database: Box<dyn LibraryStore>,
+    db_path: String,
 }
 
 impl App {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        let database = Database::new("libro.db")?;
+        let db_path = get_db_path();
+        let database = open_backend(&store_path(&db_path, backend()), backend())?;
+        let mut app = Self::with_store(database, db_path)?;
+        app.state.library_label = match db_source() {
+            DbSource::Library(name) => name,
+            DbSource::Path(path) => path,
+        };
+        Ok(app)
+    }
+
+    /// 주어진 저장소로 앱을 만듭니다 (테스트에서는 `MemoryStore`)
+    pub fn with_store(
+        database: Box<dyn LibraryStore>,
+        db_path: String,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
         let mut app = Self {
             state: AppState::new(),
             event_handler: EventHandler::new(Duration::from_millis(100)),
             text_input: TextInput::new(),
             database,
+            db_path,
         };
 
         // 초기 도서 목록 로드
@@ -42,11 +68,24 @@ impl App {
         Ok(app)
     }
 
+    /// 현재 열려 있는 데이터베이스 파일 경로
+    pub fn db_path(&self) -> &str {
+        &self.db_path
+    }
+
     /// 데이터베이스에서 도서 목록을 로드합니다
     fn load_books(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         let filter = BookFilter::default();
         match self.database.get_books(&filter) {
-            Ok(books) => {
+            Ok(mut books) => {
+                if let Some(date) = self.state.recent_filter {
+                    let since =
+                        chrono::Local::now().date_naive() - chrono::Duration::days(RECENT_DAYS);
+                    retain_since(&mut books, date, since);
+                }
+                if let Some(date) = self.state.book_order {
+                    sort_by_date(&mut books, date);
+                }
                 self.state.books = books;
                 self.state.error_message = None;
 
@@ -98,6 +137,12 @@ impl App {
     }
 
     fn render(&mut self, f: &mut Frame) {
+        // 결산 화면은 헤더/푸터 없이 전체 화면으로
+        if self.state.current_screen == Screen::Wrapped {
+            wrapped::render_wrapped(f, f.size(), &self.state);
+            return;
+        }
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -129,6 +174,8 @@ impl App {
             Screen::Report => {
                 report::render_report(f, chunks[1], &self.state, &self.state.current_report_view)
             }
+            Screen::Libraries => library::render_libraries(f, chunks[1], &self.state),
+            Screen::Trash => trash::render_trash(f, chunks[1], &self.state),
             _ => self.render_placeholder(f, chunks[1], "Coming Soon"),
         }
 
@@ -155,14 +202,20 @@ impl App {
             Screen::AddBook => "도서 추가",
             Screen::EditBook => "도서 편집",
             Screen::Report => "리포트",
+            Screen::Libraries => "서재 전환",
+            Screen::Trash => "휴지통",
+            Screen::Wrapped => "결산",
             _ => "기타",
         };
 
-        let header_text = format!("📚 Libro TUI | 모드: {} | 화면: {}", mode_text, screen_text);
+        let header_text = format!(
+            "📚 Libro TUI | 서재: {} | 모드: {} | 화면: {}",
+            self.state.library_label, mode_text, screen_text
+        );
 
         let header = Paragraph::new(header_text)
             .block(Block::default().borders(Borders::ALL))
-            .style(Style::default().fg(Color::Cyan));
+            .style(Style::default().fg(Theme::current().accent));
 
         f.render_widget(header, area);
     }
@@ -180,7 +233,7 @@ impl App {
 
         let footer = Paragraph::new(footer_text)
             .block(Block::default().borders(Borders::ALL))
-            .style(Style::default().fg(Color::Gray));
+            .style(Style::default().fg(Theme::current().muted));
 
         f.render_widget(footer, area);
     }
@@ -282,23 +335,87 @@ impl App {
                     self.state.set_screen(Screen::Report);
                 }
             }
-            KeyAction::AuthorReport => {
-                if self.state.mode == AppMode::Normal && self.state.current_screen == Screen::Report
-                {
-                    self.state.set_report_view(report::ReportView::Authors);
-                }
+            KeyAction::Libraries if self.state.mode == AppMode::Normal => {
+                self.open_library_switcher();
             }
-            KeyAction::YearReport => {
-                if self.state.mode == AppMode::Normal && self.state.current_screen == Screen::Report
-                {
-                    self.state.set_report_view(report::ReportView::Years);
-                }
+            KeyAction::Trash if self.state.mode == AppMode::Normal => {
+                self.open_trash();
             }
-            KeyAction::RecentReport => {
-                if self.state.mode == AppMode::Normal && self.state.current_screen == Screen::Report
-                {
-                    self.state.set_report_view(report::ReportView::Recent);
-                }
+            KeyAction::Wrapped
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen != Screen::Wrapped =>
+            {
+                self.state.open_wrapped();
+            }
+            KeyAction::MoveLeft
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen == Screen::Wrapped =>
+            {
+                self.state.wrapped_year -= 1;
+            }
+            KeyAction::MoveRight
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen == Screen::Wrapped =>
+            {
+                self.state.wrapped_year += 1;
+            }
+            KeyAction::SortBooks
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen == Screen::BookList =>
+            {
+                self.state.book_order = next_book_date(self.state.book_order);
+                self.state.selected_book_index = 0;
+                self.load_books()?;
+            }
+            KeyAction::Recent
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen == Screen::BookList =>
+            {
+                self.state.recent_filter = next_book_date(self.state.recent_filter);
+                self.state.selected_book_index = 0;
+                self.load_books()?;
+            }
+            KeyAction::AuthorReport
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen == Screen::Report =>
+            {
+                self.state.set_report_view(report::ReportView::Authors);
+            }
+            KeyAction::YearReport
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen == Screen::Report =>
+            {
+                self.state.set_report_view(report::ReportView::Years);
+            }
+            KeyAction::RecentReport
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen == Screen::Report =>
+            {
+                self.state.set_report_view(report::ReportView::Recent);
+            }
+            KeyAction::GenreReport
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen == Screen::Report =>
+            {
+                self.state.set_report_view(report::ReportView::Genres);
+            }
+            KeyAction::RatingReport
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen == Screen::Report =>
+            {
+                self.state.set_report_view(report::ReportView::Ratings);
+            }
+            KeyAction::PageReport
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen == Screen::Report =>
+            {
+                self.state.set_report_view(report::ReportView::Pages);
+            }
+            KeyAction::PaceReport
+                if self.state.mode == AppMode::Normal
+                    && self.state.current_screen == Screen::Report =>
+            {
+                self.state.set_report_view(report::ReportView::Pace);
             }
             KeyAction::AddReview => {
                 if self.state.mode == AppMode::Normal {
@@ -364,6 +481,9 @@ impl App {
                     }
                 }
             }
+            KeyAction::Undo if self.state.mode == AppMode::Normal => {
+                self.handle_undo();
+            }
             // 편집 모드 액션들
             KeyAction::SaveEdit => {
                 match self.state.mode {
@@ -680,6 +800,14 @@ impl App {
                                 self.state.search_selected_index -= 1;
                             }
                         }
+                        // 서재 전환 화면에서 위로 이동 (k 키)
+                        Screen::Libraries if self.state.library_selected_index > 0 => {
+                            self.state.library_selected_index -= 1;
+                        }
+                        // 휴지통 화면에서 위로 이동 (k 키)
+                        Screen::Trash if self.state.trash_selected_index > 0 => {
+                            self.state.trash_selected_index -= 1;
+                        }
                         _ => {}
                     }
                 }
@@ -735,6 +863,20 @@ impl App {
                                 self.state.search_selected_index += 1;
                             }
                         }
+                        // 서재 전환 화면에서 아래로 이동 (j 키)
+                        Screen::Libraries
+                            if self.state.library_selected_index + 1
+                                < self.state.libraries.len() =>
+                        {
+                            self.state.library_selected_index += 1;
+                        }
+                        // 휴지통 화면에서 아래로 이동 (j 키)
+                        Screen::Trash
+                            if self.state.trash_selected_index + 1
+                                < self.state.trash_items.len() =>
+                        {
+                            self.state.trash_selected_index += 1;
+                        }
                         _ => {}
                     }
                 }
@@ -845,7 +987,7 @@ impl App {
                                     // 검색 결과에서 선택된 도서의 실제 인덱스 찾기
                                     let search_results: Vec<(
                                         usize,
-                                        &crate::lib::models::ExtendedBook,
+                                        &libro_core::models::ExtendedBook,
                                     )> = self
                                         .state
                                         .books
@@ -873,6 +1015,14 @@ impl App {
                                     }
                                 }
                             }
+                            Screen::Libraries => {
+                                // 서재 전환 화면에서 Enter: 선택된 서재로 전환
+                                self.switch_to_selected_library();
+                            }
+                            Screen::Trash => {
+                                // 휴지통 화면에서 Enter: 선택된 항목 복원
+                                self.restore_selected_trash_item();
+                            }
                             _ => {
                                 // 다른 화면에서는 아직 구현하지 않음
                             }
@@ -891,6 +1041,126 @@ impl App {
         Ok(())
     }
 
+    /// 서재 전환 화면을 엽니다
+    fn open_library_switcher(&mut self) {
+        match list_libraries() {
+            Ok(libraries) => {
+                self.state.library_selected_index = libraries
+                    .iter()
+                    .position(|l| l.name == self.state.library_label)
+                    .unwrap_or(0);
+                self.state.libraries = libraries;
+                self.state.set_screen(Screen::Libraries);
+            }
+            Err(e) => {
+                self.state
+                    .set_message(format!("❌ 서재 목록을 읽을 수 없습니다: {}", e));
+            }
+        }
+    }
+
+    /// 선택된 서재로 전환하고 활성 서재로 기억합니다
+    fn switch_to_selected_library(&mut self) {
+        let Some(name) = self
+            .state
+            .libraries
+            .get(self.state.library_selected_index)
+            .map(|l| l.name.clone())
+        else {
+            return;
+        };
+
+        let db_path = library_path(&name).to_string_lossy().into_owned();
+        let database = match open_backend(&store_path(&db_path, backend()), backend()) {
+            Ok(database) => database,
+            Err(e) => {
+                self.state.set_message(format!("❌ 서재 열기 실패: {}", e));
+                return;
+            }
+        };
+        self.database = database;
+        self.db_path = db_path;
+        self.state.library_label = name.clone();
+        self.state.selected_book_index = 0;
+        self.state.current_screen = Screen::BookList;
+        self.state.previous_screen = None;
+
+        if let Err(e) = self.load_books() {
+            self.state
+                .set_message(format!("도서 목록 로드 실패: {}", e));
+            return;
+        }
+        match set_active_library(&name) {
+            Ok(()) => self
+                .state
+                .set_message(format!("✅ \"{}\" 서재로 전환했습니다", name)),
+            Err(e) => self
+                .state
+                .set_message(format!("⚠️ 서재는 전환했지만 설정 저장 실패: {}", e)),
+        }
+    }
+
+    /// 휴지통 화면을 엽니다
+    fn open_trash(&mut self) {
+        match self.database.trash() {
+            Ok(items) => {
+                self.state.trash_items = items;
+                self.state.trash_selected_index = 0;
+                if self.state.current_screen != Screen::Trash {
+                    self.state.set_screen(Screen::Trash);
+                }
+            }
+            Err(e) => {
+                self.state
+                    .set_message(format!("❌ 휴지통을 읽을 수 없습니다: {}", e));
+            }
+        }
+    }
+
+    /// 선택된 휴지통 항목을 복원합니다 (도서는 리뷰와 작가 연결까지 함께)
+    fn restore_selected_trash_item(&mut self) {
+        let Some(item) = self
+            .state
+            .trash_items
+            .get(self.state.trash_selected_index)
+            .cloned()
+        else {
+            return;
+        };
+
+        let result = match item.kind {
+            TrashKind::Book => self.database.restore_book(item.id),
+            TrashKind::Review => self.database.restore_review(item.id),
+        };
+        if let Err(e) = result {
+            self.state.set_message(format!("❌ 복원 실패: {}", e));
+            return;
+        }
+        if let Err(e) = self.load_books() {
+            self.state
+                .set_message(format!("도서 목록 로드 실패: {}", e));
+            return;
+        }
+
+        match self.database.trash() {
+            Ok(items) => self.state.trash_items = items,
+            Err(e) => {
+                self.state
+                    .set_message(format!("❌ 휴지통을 읽을 수 없습니다: {}", e));
+                return;
+            }
+        }
+        if self.state.trash_selected_index >= self.state.trash_items.len() {
+            self.state.trash_selected_index = self.state.trash_items.len().saturating_sub(1);
+        }
+        let what = match item.kind {
+            TrashKind::Book => "도서",
+            TrashKind::Review => "리뷰",
+        };
+        self.state
+            .set_message(format!("♻️  {} \"{}\" 복원 완료", what, item.title));
+    }
+
     /// 리뷰 저장을 처리합니다
     fn handle_save_review(&mut self, text: String) {
         if let Some(book) = self.state.books.get(self.state.selected_book_index) {
@@ -898,12 +1168,14 @@ impl App {
                 if let Some(review_index) = self.state.editing_review_index {
                     // 기존 리뷰 수정
                     if let Some(existing_review) = book.reviews.get(review_index) {
-                        let updated_review = crate::lib::models::Review {
+                        let updated_review = libro_core::models::Review {
                             id: existing_review.id,
                             book_id: existing_review.book_id,
                             date_read: existing_review.date_read,
                             rating: existing_review.rating,
                             review: text.trim().to_string(),
+                            created_at: None,
+                            updated_at: None,
                         };
 
                         match self
@@ -929,7 +1201,7 @@ impl App {
                     }
                 } else {
                     // 새 리뷰 생성
-                    let new_review = crate::lib::models::NewReview {
+                    let new_review = libro_core::models::NewReview {
                         book_id,
                         date_read: Some(chrono::Utc::now().date_naive()),
                         rating: 5, // 기본값, 나중에 UI에서 입력받도록 개선
@@ -996,7 +1268,7 @@ impl App {
             };
 
             // 업데이트할 도서 정보 생성
-            let updated_book = crate::lib::models::NewBook {
+            let updated_book = libro_core::models::NewBook {
                 title: self.state.form_title.trim().to_string(),
                 authors,
                 translators,
@@ -1088,7 +1360,7 @@ impl App {
         };
 
         // 새 도서 생성
-        let new_book = crate::lib::models::NewBook {
+        let new_book = libro_core::models::NewBook {
             title: self.state.form_title.trim().to_string(),
             authors,
             translators,
@@ -1166,7 +1438,7 @@ impl App {
         };
 
         // 새 도서 생성
-        let new_book = crate::lib::models::NewBook {
+        let new_book = libro_core::models::NewBook {
             title: self.state.form_title.trim().to_string(),
             authors,
             translators,
@@ -1225,7 +1497,7 @@ impl App {
             if let Some(book_id) = book.book.id {
                 let book_title = book.book.title.clone();
 
-                match self.database.delete_book(book_id as i64) {
+                match self.database.delete_book(book_id) {
                     Ok(_) => {
                         // 삭제 성공 시 도서 목록 다시 로드
                         if let Err(e) = self.load_books() {
@@ -1260,6 +1532,35 @@ impl App {
         }
     }
 
+    /// 마지막 변경을 한 트랜잭션으로 되돌립니다
+    fn handle_undo(&mut self) {
+        match self.database.undo(1) {
+            Ok(entries) => {
+                if let Err(e) = self.load_books() {
+                    self.state
+                        .set_message(format!("도서 목록 로드 실패: {}", e));
+                    return;
+                }
+                if let Some(entry) = entries.first() {
+                    self.state.set_message(format!(
+                        "↩️  #{} {} 되돌림 ({})",
+                        entry.id,
+                        entry.operation,
+                        entry.describe_changes()
+                    ));
+                }
+
+                // 되돌린 뒤 목록이 줄었으면 선택 위치 조정
+                if self.state.selected_book_index >= self.state.books.len() {
+                    self.state.selected_book_index = self.state.books.len().saturating_sub(1);
+                }
+            }
+            Err(e) => {
+                self.state.set_message(format!("❌ 되돌리기 실패: {}", e));
+            }
+        }
+    }
+
     /// 선택된 리뷰를 삭제합니다
     fn handle_delete_review(&mut self) {
         if let Some(book) = self.state.books.get(self.state.selected_book_index) {
@@ -1309,3 +1610,147 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Datelike;
+    use libro_core::memory::MemoryStore;
+    use libro_core::models::NewBook;
+    use libro_core::stats::BookDate;
+
+    fn app_with_books(titles: &[&str]) -> App {
+        let mut store = MemoryStore::new();
+        for title in titles {
+            store
+                .add_book(&NewBook {
+                    title: title.to_string(),
+                    authors: vec!["Author".to_string()],
+                    translators: vec![],
+                    pages: None,
+                    pub_year: None,
+                    genre: "Fiction".to_string(),
+                })
+                .unwrap();
+        }
+        App::with_store(Box::new(store), ":memory:".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_loads_books_from_store() {
+        let app = app_with_books(&["Dune", "Solaris"]);
+        assert_eq!(app.state.books.len(), 2);
+        assert_eq!(app.state.current_screen, Screen::BookList);
+    }
+
+    #[test]
+    fn test_delete_selected_book() {
+        let mut app = app_with_books(&["Dune", "Solaris"]);
+        app.handle_action(KeyAction::MoveDown).unwrap();
+        app.handle_action(KeyAction::DeleteBook).unwrap();
+
+        let titles: Vec<&str> = app
+            .state
+            .books
+            .iter()
+            .map(|b| b.book.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Dune"]);
+        assert_eq!(app.state.selected_book_index, 0);
+        assert_eq!(app.database.get_writers().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_undo_restores_deleted_book() {
+        let mut app = app_with_books(&["Dune", "Solaris"]);
+        app.handle_action(KeyAction::DeleteBook).unwrap();
+        assert_eq!(app.state.books.len(), 1);
+
+        app.handle_action(KeyAction::Undo).unwrap();
+        assert_eq!(app.state.books.len(), 2);
+        assert!(app.database.history(None).unwrap()[0].undone);
+    }
+
+    #[test]
+    fn test_trash_screen_restores_book() {
+        let mut app = app_with_books(&["Dune", "Solaris"]);
+        app.handle_action(KeyAction::DeleteBook).unwrap();
+        assert_eq!(app.state.books.len(), 1);
+
+        app.handle_action(KeyAction::Trash).unwrap();
+        assert_eq!(app.state.current_screen, Screen::Trash);
+        assert_eq!(app.state.trash_items.len(), 1);
+
+        app.handle_action(KeyAction::Select).unwrap();
+        assert_eq!(app.state.books.len(), 2);
+        assert!(app.state.trash_items.is_empty());
+        assert_eq!(app.database.get_writers().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_sort_and_recent_filter_cycle() {
+        let mut app = app_with_books(&["Dune", "Solaris"]);
+        let titles = |app: &App| {
+            app.state
+                .books
+                .iter()
+                .map(|b| b.book.title.clone())
+                .collect::<Vec<_>>()
+        };
+
+        // 같은 시각에 추가되면 나중에 추가된 도서가 먼저
+        app.handle_action(KeyAction::SortBooks).unwrap();
+        assert_eq!(app.state.book_order, Some(BookDate::Added));
+        assert_eq!(titles(&app), ["Solaris", "Dune"]);
+        app.handle_action(KeyAction::SortBooks).unwrap();
+        app.handle_action(KeyAction::SortBooks).unwrap();
+        assert_eq!(app.state.book_order, None);
+        assert_eq!(titles(&app), ["Dune", "Solaris"]);
+
+        // 방금 추가한 도서는 최근 필터에 남음
+        app.handle_action(KeyAction::Recent).unwrap();
+        assert_eq!(app.state.recent_filter, Some(BookDate::Added));
+        assert_eq!(app.state.books.len(), 2);
+    }
+
+    #[test]
+    fn test_report_tabs_switch_with_number_keys() {
+        let mut app = app_with_books(&["Dune"]);
+        app.handle_action(KeyAction::GenreReport).unwrap();
+        assert_eq!(app.state.current_report_view, report::ReportView::default());
+
+        app.handle_action(KeyAction::Report).unwrap();
+        for (action, view) in [
+            (KeyAction::GenreReport, report::ReportView::Genres),
+            (KeyAction::RatingReport, report::ReportView::Ratings),
+            (KeyAction::PageReport, report::ReportView::Pages),
+            (KeyAction::PaceReport, report::ReportView::Pace),
+        ] {
+            app.handle_action(action).unwrap();
+            assert_eq!(app.state.current_report_view, view);
+        }
+    }
+
+    #[test]
+    fn test_wrapped_screen_steps_through_years() {
+        let mut app = app_with_books(&["Dune"]);
+        let this_year = chrono::Local::now().year();
+
+        app.handle_action(KeyAction::Wrapped).unwrap();
+        assert_eq!(app.state.current_screen, Screen::Wrapped);
+        assert_eq!(app.state.wrapped_year, this_year);
+
+        app.handle_action(KeyAction::MoveLeft).unwrap();
+        app.handle_action(KeyAction::MoveLeft).unwrap();
+        app.handle_action(KeyAction::MoveRight).unwrap();
+        assert_eq!(app.state.wrapped_year, this_year - 1);
+
+        app.handle_action(KeyAction::Back).unwrap();
+        assert_eq!(app.state.current_screen, Screen::BookList);
+
+        // 결산 화면 밖에서 h/l은 연도를 바꾸지 않음
+        app.handle_action(KeyAction::MoveLeft).unwrap();
+        app.handle_action(KeyAction::Wrapped).unwrap();
+        assert_eq!(app.state.wrapped_year, this_year);
+    }
+}
@@ -62,11 +62,17 @@ pub enum KeyAction {
     AddReview,    // v (view/review)
     NewReview,    // n (new review)
     DeleteReview, // d (delete review - context dependent)
+    Undo,         // u (마지막 변경 되돌리기)
 
     // 기능
-    Search, // /
-    Report, // r
-    Help,   // ?
+    Search,    // /
+    Report,    // r
+    Help,      // ?
+    Libraries, // L
+    Trash,     // t
+    SortBooks, // o (ID순 → 추가일순 → 수정일순)
+    Recent,    // f (전체 → 최근 추가 → 최근 수정)
+    Wrapped,   // w (올해 독서 결산)
 
     // 폼 네비게이션
     NextField, // Tab
@@ -76,6 +82,10 @@ pub enum KeyAction {
     AuthorReport, // 1
     YearReport,   // 2
     RecentReport, // 3
+    GenreReport,  // 4
+    RatingReport, // 5
+    PageReport,   // 6
+    PaceReport,   // 7
 
     // 편집 모드 전용
     SaveEdit,   // Ctrl+S
@@ -214,6 +224,11 @@ pub fn key_to_action(key: KeyEvent, mode: &crate::tui::state::AppMode) -> KeyAct
             }
         }
         AppMode::Normal => {
+            // 설정 파일의 [ui.keys] 바인딩이 기본 키보다 우선
+            if let Some(action) = configured_action(key) {
+                return action;
+            }
+
             // 일반 모드 - 모든 전역 키매핑 활성
             match (key.modifiers, key.code) {
                 (KeyModifiers::NONE, KeyCode::Char('q')) => KeyAction::Quit,
@@ -227,16 +242,26 @@ pub fn key_to_action(key: KeyEvent, mode: &crate::tui::state::AppMode) -> KeyAct
                 (KeyModifiers::NONE, KeyCode::Char('a')) => KeyAction::AddBook,
                 (KeyModifiers::NONE, KeyCode::Char('e')) => KeyAction::EditBook,
                 (KeyModifiers::NONE, KeyCode::Char('d')) => KeyAction::DeleteBook, // 컨텍스트에 따라 DeleteReview로 처리됨
+                (KeyModifiers::NONE, KeyCode::Char('u')) => KeyAction::Undo,
                 (KeyModifiers::NONE, KeyCode::Char('v')) => KeyAction::AddReview,
                 (KeyModifiers::NONE, KeyCode::Char('n')) => KeyAction::NewReview,
                 (KeyModifiers::NONE, KeyCode::Char('/')) => KeyAction::Search,
                 (KeyModifiers::NONE, KeyCode::Char('r')) => KeyAction::Report,
+                (KeyModifiers::SHIFT, KeyCode::Char('L')) => KeyAction::Libraries,
+                (KeyModifiers::NONE, KeyCode::Char('t')) => KeyAction::Trash,
+                (KeyModifiers::NONE, KeyCode::Char('o')) => KeyAction::SortBooks,
+                (KeyModifiers::NONE, KeyCode::Char('f')) => KeyAction::Recent,
+                (KeyModifiers::NONE, KeyCode::Char('w')) => KeyAction::Wrapped,
                 (KeyModifiers::NONE, KeyCode::Tab) => KeyAction::NextField,
                 (KeyModifiers::SHIFT, KeyCode::BackTab) => KeyAction::PrevField,
                 (KeyModifiers::NONE, KeyCode::Char(' ')) => KeyAction::ToggleMode,
                 (KeyModifiers::NONE, KeyCode::Char('1')) => KeyAction::AuthorReport,
                 (KeyModifiers::NONE, KeyCode::Char('2')) => KeyAction::YearReport,
                 (KeyModifiers::NONE, KeyCode::Char('3')) => KeyAction::RecentReport,
+                (KeyModifiers::NONE, KeyCode::Char('4')) => KeyAction::GenreReport,
+                (KeyModifiers::NONE, KeyCode::Char('5')) => KeyAction::RatingReport,
+                (KeyModifiers::NONE, KeyCode::Char('6')) => KeyAction::PageReport,
+                (KeyModifiers::NONE, KeyCode::Char('7')) => KeyAction::PaceReport,
                 (KeyModifiers::NONE, KeyCode::Up) => KeyAction::MoveUp,
                 (KeyModifiers::NONE, KeyCode::Down) => KeyAction::MoveDown,
                 (KeyModifiers::NONE, KeyCode::Left) => KeyAction::MoveLeft,
@@ -246,3 +271,44 @@ pub fn key_to_action(key: KeyEvent, mode: &crate::tui::state::AppMode) -> KeyAct
         }
     }
 }
+
+/// 설정 파일([ui.keys])에서 지정한 키를 액션으로 변환합니다
+fn configured_action(key: KeyEvent) -> Option<KeyAction> {
+    let KeyCode::Char(c) = key.code else {
+        return None;
+    };
+    if !matches!(key.modifiers, KeyModifiers::NONE | KeyModifiers::SHIFT) {
+        return None;
+    }
+
+    let keys = &crate::utils::config::current().ui.keys;
+    let (name, _) = keys.iter().find(|(_, bound)| **bound == c)?;
+    action_by_name(name)
+}
+
+/// 설정 파일에서 쓰는 액션 이름을 KeyAction으로 변환합니다
+fn action_by_name(name: &str) -> Option<KeyAction> {
+    let action = match name {
+        "quit" => KeyAction::Quit,
+        "help" => KeyAction::Help,
+        "move_down" => KeyAction::MoveDown,
+        "move_up" => KeyAction::MoveUp,
+        "move_left" => KeyAction::MoveLeft,
+        "move_right" => KeyAction::MoveRight,
+        "add_book" => KeyAction::AddBook,
+        "edit_book" => KeyAction::EditBook,
+        "delete" => KeyAction::DeleteBook,
+        "undo" => KeyAction::Undo,
+        "add_review" => KeyAction::AddReview,
+        "new_review" => KeyAction::NewReview,
+        "search" => KeyAction::Search,
+        "report" => KeyAction::Report,
+        "libraries" => KeyAction::Libraries,
+        "trash" => KeyAction::Trash,
+        "sort_books" => KeyAction::SortBooks,
+        "recent" => KeyAction::Recent,
+        "wrapped" => KeyAction::Wrapped,
+        _ => return None,
+    };
+    Some(action)
+}
@@ -6,6 +6,7 @@ pub mod events;
 pub mod handlers;
 pub mod input;
 pub mod state;
+pub mod theme;
 pub mod ui;
 
 use crossterm::{
@@ -15,8 +16,14 @@ use crossterm::{
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
+use std::path::Path;
 
+use crate::utils::config::Backend;
+use crate::utils::database::{
+    self, backup_on_exit_enabled, default_backup_dir, DEFAULT_BACKUP_KEEP,
+};
 use app::App;
+use libro_core::backup::create_rotated_backup;
 
 pub fn run_tui() -> Result<(), Box<dyn std::error::Error>> {
     // 터미널 설정
@@ -39,5 +46,23 @@ pub fn run_tui() -> Result<(), Box<dyn std::error::Error>> {
     )?;
     terminal.show_cursor()?;
 
+    // 종료 시 자동 백업 (LIBRO_BACKUP_ON_EXIT, SQLite 저장소만)
+    if result.is_ok() && backup_on_exit_enabled() && database::backend() == Backend::Sqlite {
+        backup_on_exit(app.db_path());
+    }
+
     result
 }
+
+/// 종료 시 백업. 실패해도 종료 자체는 막지 않음
+fn backup_on_exit(db_path: &str) {
+    match create_rotated_backup(
+        Path::new(db_path),
+        &default_backup_dir(db_path),
+        DEFAULT_BACKUP_KEEP,
+        chrono::Local::now().naive_local(),
+    ) {
+        Ok(summary) => println!("백업 완료: {}", summary.path.display()),
+        Err(e) => eprintln!("백업 실패: {}", e),
+    }
+}
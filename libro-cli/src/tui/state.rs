@@ -1,8 +1,12 @@
 //! 애플리케이션 상태 관리
 
-use crate::lib::models::ExtendedBook;
 use crate::tui::ui::report::ReportView;
 use chrono::Datelike;
+use libro_core::models::ExtendedBook;
+use libro_core::stats::BookDate;
+
+/// 최근 필터가 남기는 기간 (일)
+pub const RECENT_DAYS: i64 = 30;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppMode {
@@ -26,6 +30,9 @@ pub enum Screen {
     Report,
     Help,
     ConfirmDelete,
+    Libraries,
+    Trash,
+    Wrapped,
 }
 
 pub struct AppState {
@@ -39,7 +46,9 @@ pub struct AppState {
     pub search_selected_index: usize, // 검색 결과에서 선택된 인덱스
     pub should_quit: bool,
     pub previous_screen: Option<Screen>, // 뒤로가기를 위한 이전 화면
-    pub books: Vec<crate::lib::models::ExtendedBook>, // 도서 목록
+    pub books: Vec<libro_core::models::ExtendedBook>, // 도서 목록
+    pub book_order: Option<BookDate>,    // 도서 목록 정렬 (None이면 ID순)
+    pub recent_filter: Option<BookDate>, // 최근 추가/수정된 도서만 표시 (None이면 전체)
     pub error_message: Option<String>,   // 에러/성공 메시지
     pub message_timer: Option<std::time::Instant>, // 메시지 표시 시간
     pub editing_review_index: Option<usize>, // 편집 중인 리뷰의 인덱스 (None이면 새 리뷰)
@@ -62,6 +71,18 @@ pub struct AppState {
 
     // 리포트 관련
     pub current_report_view: ReportView, // 현재 리포트 뷰
+
+    // 서재 관련
+    pub library_label: String, // 헤더에 표시할 현재 서재
+    pub libraries: Vec<crate::utils::library::LibraryInfo>, // 서재 전환 화면 목록
+    pub library_selected_index: usize, // 서재 전환 화면에서 선택된 인덱스
+
+    // 휴지통 관련
+    pub trash_items: Vec<libro_core::trash::TrashItem>, // 휴지통 화면 목록
+    pub trash_selected_index: usize,                    // 휴지통 화면에서 선택된 인덱스
+
+    // 결산 관련
+    pub wrapped_year: i32, // 결산 화면에서 보는 연도
 }
 
 impl Default for AppState {
@@ -78,6 +99,8 @@ impl Default for AppState {
             should_quit: false,
             previous_screen: None,
             books: Vec::new(),
+            book_order: None,
+            recent_filter: None,
             error_message: None,
             message_timer: None,
             editing_review_index: None,
@@ -100,6 +123,14 @@ impl Default for AppState {
 
             // 리포트 관련
             current_report_view: ReportView::default(),
+
+            // 서재 관련
+            library_label: String::new(),
+            libraries: Vec::new(),
+            library_selected_index: 0,
+            trash_items: Vec::new(),
+            trash_selected_index: 0,
+            wrapped_year: chrono::Local::now().year(),
         }
     }
 }
@@ -109,9 +140,12 @@ impl AppState {
         Self::default()
     }
 
-    /// 사용 가능한 장르 목록을 반환합니다
+    /// 사용 가능한 장르 목록을 반환합니다 (설정 파일의 defaults.genres 우선)
     pub fn get_genres() -> Vec<&'static str> {
-        vec!["소설", "에세이", "자기계발", "기술/IT", "기타"]
+        match &crate::utils::config::current().defaults.genres {
+            Some(genres) => genres.iter().map(String::as_str).collect(),
+            None => vec!["소설", "에세이", "자기계발", "기술/IT", "기타"],
+        }
     }
 
     pub fn set_screen(&mut self, screen: Screen) {
@@ -119,6 +153,12 @@ impl AppState {
         self.current_screen = screen;
     }
 
+    /// 올해 결산 화면을 엽니다
+    pub fn open_wrapped(&mut self) {
+        self.wrapped_year = chrono::Local::now().year();
+        self.set_screen(Screen::Wrapped);
+    }
+
     pub fn go_back(&mut self) {
         if let Some(prev_screen) = self.previous_screen.take() {
             self.current_screen = prev_screen;
@@ -380,3 +420,12 @@ impl AppState {
         }
     }
 }
+
+/// 정렬/필터 기준을 없음 → 추가일 → 수정일 → 없음 순서로 바꿉니다
+pub fn next_book_date(date: Option<BookDate>) -> Option<BookDate> {
+    match date {
+        None => Some(BookDate::Added),
+        Some(BookDate::Added) => Some(BookDate::Modified),
+        Some(BookDate::Modified) => None,
+    }
+}
@@ -0,0 +1,47 @@
+//! 색상 테마 (설정 파일의 ui.theme)
+
+use ratatui::style::Color;
+
+/// TUI에서 쓰는 색상 묶음
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    /// 헤더 등 강조 색
+    pub accent: Color,
+    /// 선택된 항목 글자색
+    pub highlight_fg: Color,
+    /// 선택된 항목 배경색
+    pub highlight_bg: Color,
+    /// 상태바 등 보조 텍스트 색
+    pub muted: Color,
+}
+
+impl Theme {
+    /// 이름으로 테마를 찾습니다. 알 수 없는 이름은 기본(dark) 테마
+    pub fn by_name(name: &str) -> Self {
+        match name {
+            "light" => Self {
+                accent: Color::Blue,
+                highlight_fg: Color::Black,
+                highlight_bg: Color::LightCyan,
+                muted: Color::DarkGray,
+            },
+            "mono" => Self {
+                accent: Color::White,
+                highlight_fg: Color::Black,
+                highlight_bg: Color::White,
+                muted: Color::Gray,
+            },
+            _ => Self {
+                accent: Color::Cyan,
+                highlight_fg: Color::White,
+                highlight_bg: Color::DarkGray,
+                muted: Color::Gray,
+            },
+        }
+    }
+
+    /// 설정 파일에서 지정한 테마
+    pub fn current() -> Self {
+        Self::by_name(&crate::utils::config::current().ui.theme)
+    }
+}
@@ -8,8 +8,10 @@ use ratatui::{
     Frame,
 };
 
-use crate::lib::models::ExtendedBook;
-use crate::tui::state::AppState;
+use crate::tui::state::{AppState, RECENT_DAYS};
+use crate::tui::theme::Theme;
+use libro_core::models::ExtendedBook;
+use libro_core::stats::BookDate;
 
 /// 도서 목록 화면을 렌더링합니다
 pub fn render_book_list(f: &mut Frame, area: Rect, state: &AppState) {
@@ -34,12 +36,22 @@ pub fn render_book_list(f: &mut Frame, area: Rect, state: &AppState) {
 
 /// 헤더를 렌더링합니다
 fn render_header(f: &mut Frame, area: Rect, state: &AppState) {
-    let title = format!("📚 Libro - 도서 목록 ({} 권)", state.books.len());
+    let mut title = format!("📚 Libro - 도서 목록 ({} 권)", state.books.len());
+    match state.book_order {
+        Some(BookDate::Added) => title.push_str(" · 추가일순"),
+        Some(BookDate::Modified) => title.push_str(" · 수정일순"),
+        None => {}
+    }
+    match state.recent_filter {
+        Some(BookDate::Added) => title.push_str(&format!(" · 최근 {}일 추가", RECENT_DAYS)),
+        Some(BookDate::Modified) => title.push_str(&format!(" · 최근 {}일 수정", RECENT_DAYS)),
+        None => {}
+    }
     let header = Paragraph::new(title)
         .block(Block::default().borders(Borders::ALL))
         .style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(Theme::current().accent)
                 .add_modifier(Modifier::BOLD),
         );
 
@@ -49,7 +61,7 @@ fn render_header(f: &mut Frame, area: Rect, state: &AppState) {
 /// 도서 목록을 렌더링합니다
 fn render_books(f: &mut Frame, area: Rect, state: &AppState) {
     if state.books.is_empty() {
-        render_empty_list(f, area);
+        render_empty_list(f, area, state);
         return;
     }
 
@@ -67,8 +79,8 @@ fn render_books(f: &mut Frame, area: Rect, state: &AppState) {
         .block(Block::default().borders(Borders::ALL).title("도서 목록"))
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
-                .fg(Color::White)
+                .bg(Theme::current().highlight_bg)
+                .fg(Theme::current().highlight_fg)
                 .add_modifier(Modifier::BOLD),
         );
 
@@ -79,11 +91,18 @@ fn render_books(f: &mut Frame, area: Rect, state: &AppState) {
 }
 
 /// 빈 목록을 렌더링합니다
-fn render_empty_list(f: &mut Frame, area: Rect) {
-    let empty_msg =
-        Paragraph::new("📖 등록된 도서가 없습니다.\n\n'a' 키를 눌러 도서를 추가해보세요!")
-            .block(Block::default().borders(Borders::ALL).title("도서 목록"))
-            .style(Style::default().fg(Color::Gray));
+fn render_empty_list(f: &mut Frame, area: Rect, state: &AppState) {
+    let message = if state.recent_filter.is_some() {
+        format!(
+            "📖 최근 {}일 동안 바뀐 도서가 없습니다.\n\n'f' 키로 필터를 바꿔보세요!",
+            RECENT_DAYS
+        )
+    } else {
+        "📖 등록된 도서가 없습니다.\n\n'a' 키를 눌러 도서를 추가해보세요!".to_string()
+    };
+    let empty_msg = Paragraph::new(message)
+        .block(Block::default().borders(Borders::ALL).title("도서 목록"))
+        .style(Style::default().fg(Color::Gray));
 
     f.render_widget(empty_msg, area);
 }
@@ -165,7 +184,7 @@ fn render_status_bar(f: &mut Frame, area: Rect, state: &AppState) {
         "💡 도서를 추가하려면 'a' 키를 누르세요".to_string()
     } else {
         format!(
-            "📍 {}/{} | j/k: 이동 | a: 추가 | e: 편집 | d: 삭제 | v: 리뷰 | /: 검색 | ?: 도움말",
+            "📍 {}/{} | j/k: 이동 | a: 추가 | e: 편집 | d: 삭제 | u: 되돌리기 | t: 휴지통 | o: 정렬 | f: 최근 | v: 리뷰 | /: 검색 | ?: 도움말",
             state.selected_book_index + 1,
             state.books.len()
         )
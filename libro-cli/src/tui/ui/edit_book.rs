@@ -8,13 +8,11 @@ use ratatui::{
     Frame,
 };
 
-use crate::{
-    lib::models::ExtendedBook,
-    tui::{
-        input::TextInput,
-        state::{AppMode, AppState},
-    },
+use crate::tui::{
+    input::TextInput,
+    state::{AppMode, AppState},
 };
+use libro_core::models::ExtendedBook;
 
 /// 도서 편집 화면을 렌더링합니다
 pub fn render_edit_book(f: &mut Frame, area: Rect, state: &AppState, text_input: &mut TextInput) {
@@ -85,6 +85,15 @@ fn get_normal_mode_help() -> Vec<Line<'static>> {
             ),
             Span::raw("  선택한 도서 삭제"),
         ]),
+        Line::from(vec![
+            Span::styled(
+                "  u",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  마지막 변경 되돌리기"),
+        ]),
         Line::from(vec![
             Span::styled(
                 "  v",
@@ -136,7 +145,52 @@ fn get_normal_mode_help() -> Vec<Line<'static>> {
                     .fg(Color::Green)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::raw("  리포트 보기"),
+            Span::raw("  리포트 보기 (1-7로 탭 전환)"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  L",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  서재 전환"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  t",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  휴지통 (Enter로 복원)"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  o",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  정렬 바꾸기 (ID → 추가일 → 수정일)"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  f",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  최근 30일 추가/수정된 도서만 보기"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  w",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  올해 독서 결산 (h/l로 연도 이동)"),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
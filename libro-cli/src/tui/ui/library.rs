@@ -0,0 +1,72 @@
+//! 서재 전환 화면 UI
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::tui::state::AppState;
+use crate::tui::theme::Theme;
+
+/// 서재 전환 화면을 렌더링합니다
+pub fn render_libraries(f: &mut Frame, area: Rect, state: &AppState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // 헤더
+            Constraint::Min(0),    // 서재 목록
+            Constraint::Length(3), // 상태바
+        ])
+        .split(area);
+
+    let header = Paragraph::new(format!("🗂️  서재 전환 (현재: {})", state.library_label))
+        .block(Block::default().borders(Borders::ALL))
+        .style(
+            Style::default()
+                .fg(Theme::current().accent)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_widget(header, chunks[0]);
+
+    let items: Vec<ListItem> = state
+        .libraries
+        .iter()
+        .map(|library| {
+            let marker = if library.active { "● " } else { "  " };
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Green)),
+                Span::styled(
+                    library.name.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  {}", library.path.display()),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("서재 목록"))
+        .highlight_style(
+            Style::default()
+                .bg(Theme::current().highlight_bg)
+                .fg(Theme::current().highlight_fg)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.library_selected_index));
+    f.render_stateful_widget(list, chunks[1], &mut list_state);
+
+    let status = Paragraph::new(
+        "j/k: 이동 | Enter: 전환 | Esc: 취소 | 새 서재: libro-cli library create <이름>",
+    )
+    .block(Block::default().borders(Borders::ALL))
+    .style(Style::default().fg(Theme::current().muted));
+    f.render_widget(status, chunks[2]);
+}
@@ -4,9 +4,12 @@ pub mod add_book;
 pub mod book_list;
 pub mod edit_book;
 pub mod help;
+pub mod library;
 pub mod report;
 pub mod review;
 pub mod search;
+pub mod trash;
+pub mod wrapped;
 
 // 나중에 구현할 UI 컴포넌트들
 // pub mod layout;
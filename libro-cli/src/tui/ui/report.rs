@@ -7,17 +7,25 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-use crate::lib::models::ExtendedBook;
 use crate::tui::state::AppState;
-use chrono::Datelike;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use libro_core::errors::validation::max_rating;
+use libro_core::models::ExtendedBook;
+use libro_core::stats::{
+    author_titles, finishes_by_day, latest_books, reading_pace, week_start, BookDate, LibraryStats,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ReportView {
     Authors, // 작가별 통계 (1키)
     Years,   // 연도별 통계 (2키)
     Recent,  // 최근 도서 목록 (3키)
+    Genres,  // 장르별 통계 (4키)
+    Ratings, // 평점 분포 (5키)
+    Pages,   // 분량 분포 (6키)
+    Pace,    // 독서 속도와 히트맵 (7키)
 }
 
 impl Default for ReportView {
@@ -41,14 +49,19 @@ pub fn render_report(f: &mut Frame, area: Rect, state: &AppState, current_view:
     render_header(f, chunks[0], current_view);
 
     // 리포트 내용
+    let stats = LibraryStats::from_books(&state.books);
     match current_view {
-        ReportView::Authors => render_authors_report(f, chunks[1], &state.books),
-        ReportView::Years => render_years_report(f, chunks[1], &state.books),
+        ReportView::Authors => render_authors_report(f, chunks[1], &state.books, &stats),
+        ReportView::Years => render_years_report(f, chunks[1], &state.books, &stats),
         ReportView::Recent => render_recent_books_report(f, chunks[1], &state.books),
+        ReportView::Genres => render_genres_report(f, chunks[1], &state.books, &stats),
+        ReportView::Ratings => render_ratings_report(f, chunks[1], &state.books, &stats),
+        ReportView::Pages => render_pages_report(f, chunks[1], &state.books, &stats),
+        ReportView::Pace => render_pace_report(f, chunks[1], &state.books),
     }
 
     // 상태바
-    render_status_bar(f, chunks[2], current_view);
+    render_status_bar(f, chunks[2]);
 }
 
 /// 헤더를 렌더링합니다
@@ -57,6 +70,10 @@ fn render_header(f: &mut Frame, area: Rect, current_view: &ReportView) {
         ReportView::Authors => "📊 리포트 - 작가별 통계",
         ReportView::Years => "📊 리포트 - 연도별 통계",
         ReportView::Recent => "📊 리포트 - 최근 도서",
+        ReportView::Genres => "📊 리포트 - 장르별 통계",
+        ReportView::Ratings => "📊 리포트 - 평점 분포",
+        ReportView::Pages => "📊 리포트 - 분량 분포",
+        ReportView::Pace => "📊 리포트 - 독서 속도",
     };
 
     let header = Paragraph::new(title)
@@ -71,35 +88,18 @@ fn render_header(f: &mut Frame, area: Rect, current_view: &ReportView) {
 }
 
 /// 작가별 통계를 렌더링합니다
-fn render_authors_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
+fn render_authors_report(f: &mut Frame, area: Rect, books: &[ExtendedBook], stats: &LibraryStats) {
     if books.is_empty() {
-        let content = Paragraph::new("📚 도서가 없습니다.")
-            .block(Block::default().borders(Borders::ALL).title("작가별 통계"))
-            .style(Style::default().fg(Color::Gray));
-        f.render_widget(content, area);
+        render_empty(f, area, "작가별 통계");
         return;
     }
 
-    // 작가별 통계 계산 (도서 수와 가장 최근 도서 ID 추적)
-    let mut author_stats: HashMap<String, (usize, i64)> = HashMap::new(); // (도서 수, 최근 도서 ID)
-    for book in books {
-        if let Some(book_id) = book.book.id {
-            for author in &book.authors {
-                let entry = author_stats.entry(author.name.clone()).or_insert((0, 0));
-                entry.0 += 1; // 도서 수 증가
-                entry.1 = entry.1.max(book_id); // 가장 최근 도서 ID 업데이트
-            }
-        }
-    }
-
-    let mut sorted_authors: Vec<_> = author_stats.into_iter().collect();
-    // 가장 최근 도서 ID 기준으로 내림차순 정렬 (최근 추가된 작가 우선)
-    sorted_authors.sort_by(|a, b| b.1 .1.cmp(&a.1 .1));
-    sorted_authors.truncate(10); // 상위 10명만 표시
+    // 많이 읽은 작가 순으로 상위 10명만 표시
+    let top_authors = &stats.authors[..stats.authors.len().min(10)];
 
     let mut lines = vec![
         Line::from(vec![Span::styled(
-            format!("👥 상위 {} 작가", sorted_authors.len()),
+            format!("👥 상위 {} 작가", top_authors.len()),
             Style::default()
                 .fg(Color::Green)
                 .add_modifier(Modifier::BOLD),
@@ -107,12 +107,12 @@ fn render_authors_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
         Line::from(""),
     ];
 
-    for (i, (author_name, (book_count, _latest_id))) in sorted_authors.iter().enumerate() {
+    for (i, count) in top_authors.iter().enumerate() {
         // 작가 이름과 도서 수
         lines.push(Line::from(vec![
             Span::styled(format!("{:2}. ", i + 1), Style::default().fg(Color::Gray)),
             Span::styled(
-                author_name.clone(),
+                count.author.clone(),
                 Style::default()
                     .fg(Color::White)
                     .add_modifier(Modifier::BOLD),
@@ -122,41 +122,38 @@ fn render_authors_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
         lines.push(Line::from(vec![
             Span::raw("    "),
             Span::styled(
-                format!("📚 {} 권", book_count),
+                format!("📚 {} 권", count.books),
                 Style::default().fg(Color::Blue),
             ),
         ]));
 
         // 해당 작가의 도서 목록 (최대 3권)
-        let author_books: Vec<_> = books
-            .iter()
-            .filter(|book| book.authors.iter().any(|a| a.name == *author_name))
-            .take(3)
-            .collect();
-
-        for (j, book) in author_books.iter().enumerate() {
-            let prefix = if j == author_books.len() - 1 && author_books.len() < *book_count {
+        let titles = author_titles(books, &count.author);
+        let shown = titles.len().min(3);
+
+        for (j, title) in titles[..shown].iter().enumerate() {
+            let prefix = if j == shown - 1 && shown == titles.len() {
                 "    └─ "
             } else {
                 "    ├─ "
             };
             lines.push(Line::from(vec![
                 Span::styled(prefix, Style::default().fg(Color::Gray)),
-                Span::styled(&book.book.title, Style::default().fg(Color::Gray)),
+                Span::styled(title.to_string(), Style::default().fg(Color::Gray)),
             ]));
         }
 
-        if author_books.len() < *book_count {
+        if shown < titles.len() {
             lines.push(Line::from(vec![
                 Span::styled("    └─ ", Style::default().fg(Color::Gray)),
                 Span::styled(
-                    format!("외 {} 권...", book_count - author_books.len()),
+                    format!("외 {} 권...", titles.len() - shown),
                     Style::default().fg(Color::Gray),
                 ),
             ]));
         }
 
-        if i < sorted_authors.len() - 1 {
+        if i < top_authors.len() - 1 {
             lines.push(Line::from(""));
         }
     }
@@ -169,41 +166,14 @@ fn render_authors_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
 }
 
 /// 연도별 통계를 렌더링합니다
-fn render_years_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
+fn render_years_report(f: &mut Frame, area: Rect, books: &[ExtendedBook], stats: &LibraryStats) {
     if books.is_empty() {
-        let content = Paragraph::new("📚 도서가 없습니다.")
-            .block(Block::default().borders(Borders::ALL).title("연도별 통계"))
-            .style(Style::default().fg(Color::Gray));
-        f.render_widget(content, area);
+        render_empty(f, area, "연도별 통계");
         return;
     }
 
-    // 기본 통계
-    let total_books = books.len();
-    let total_pages: i32 = books.iter().filter_map(|b| b.book.pages).sum();
-    let total_reviews = books.iter().map(|b| b.reviews.len()).sum::<usize>();
-
-    let avg_rating = if total_reviews > 0 {
-        let total_rating: i32 = books
-            .iter()
-            .flat_map(|b| &b.reviews)
-            .map(|r| r.rating)
-            .sum();
-        total_rating as f32 / total_reviews as f32
-    } else {
-        0.0
-    };
-
-    // 읽은 날짜 기준 연도별 통계
-    let mut year_counts = HashMap::new();
-    for book in books {
-        for review in &book.reviews {
-            if let Some(date_read) = review.date_read {
-                let read_year = date_read.year();
-                *year_counts.entry(read_year).or_insert(0) += 1;
-            }
-        }
-    }
+    // 기본 통계와 읽은 날짜 기준 연도별 통계
+    let totals = &stats.totals;
 
     let mut lines = vec![
         Line::from(vec![Span::styled(
@@ -216,7 +186,7 @@ fn render_years_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
         Line::from(vec![
             Span::raw("총 도서: "),
             Span::styled(
-                format!("{} 권", total_books),
+                format!("{} 권", totals.total_books),
                 Style::default()
                     .fg(Color::Blue)
                     .add_modifier(Modifier::BOLD),
@@ -225,7 +195,7 @@ fn render_years_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
         Line::from(vec![
             Span::raw("총 페이지: "),
             Span::styled(
-                format!("{} 페이지", total_pages),
+                format!("{} 페이지", totals.total_pages),
                 Style::default()
                     .fg(Color::Blue)
                     .add_modifier(Modifier::BOLD),
@@ -234,7 +204,7 @@ fn render_years_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
         Line::from(vec![
             Span::raw("총 리뷰: "),
             Span::styled(
-                format!("{} 개", total_reviews),
+                format!("{} 개", totals.total_reviews),
                 Style::default()
                     .fg(Color::Blue)
                     .add_modifier(Modifier::BOLD),
@@ -242,11 +212,11 @@ fn render_years_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
         ]),
     ];
 
-    if total_reviews > 0 {
+    if let Some(avg_rating) = totals.average_rating {
         lines.push(Line::from(vec![
             Span::raw("평균 평점: "),
             Span::styled(
-                format!("{:.1}/5", avg_rating),
+                format!("{:.1}/{}", avg_rating, totals.rating_scale),
                 Style::default()
                     .fg(Color::Yellow)
                     .add_modifier(Modifier::BOLD),
@@ -254,7 +224,28 @@ fn render_years_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
         ]));
     }
 
-    if !year_counts.is_empty() {
+    if let Some(average) = stats.pages.average_pages {
+        lines.push(Line::from(vec![
+            Span::raw("평균 분량: "),
+            Span::styled(
+                format!("{:.0} 페이지", average),
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+    if let Some(longest) = &stats.pages.longest {
+        lines.push(Line::from(vec![
+            Span::raw("가장 긴 책: "),
+            Span::styled(
+                format!("{} ({} 페이지)", longest.title, longest.pages),
+                Style::default().fg(Color::Blue),
+            ),
+        ]));
+    }
+
+    if !stats.years.is_empty() {
         lines.push(Line::from(""));
         lines.push(Line::from(vec![Span::styled(
             "📅 연도별 독서 현황 (읽은 날짜 기준)",
@@ -264,15 +255,18 @@ fn render_years_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
         )]));
         lines.push(Line::from(""));
 
-        let mut years: Vec<_> = year_counts.iter().collect();
-        years.sort_by_key(|(year, _)| *year);
-
-        for (year, count) in years {
-            let bar = "█".repeat(*count);
+        for count in &stats.years {
+            let bar = "█".repeat(count.books);
             lines.push(Line::from(vec![
-                Span::styled(format!("{}: ", year), Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("{}: ", count.year),
+                    Style::default().fg(Color::White),
+                ),
                 Span::styled(bar, Style::default().fg(Color::Green)),
-                Span::styled(format!(" ({} 권)", count), Style::default().fg(Color::Gray)),
+                Span::styled(
+                    format!(" ({} 권)", count.books),
+                    Style::default().fg(Color::Gray),
+                ),
             ]));
         }
     } else {
@@ -293,16 +287,11 @@ fn render_years_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
 /// 최근 도서 목록을 렌더링합니다
 fn render_recent_books_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
     if books.is_empty() {
-        let content = Paragraph::new("📚 도서가 없습니다.")
-            .block(Block::default().borders(Borders::ALL).title("최근 도서"))
-            .style(Style::default().fg(Color::Gray));
-        f.render_widget(content, area);
+        render_empty(f, area, "최근 도서");
         return;
     }
 
-    let mut sorted_books = books.to_vec();
-    sorted_books.sort_by(|a, b| b.book.id.cmp(&a.book.id));
-    sorted_books.truncate(10); // 최근 10권만 표시
+    let sorted_books = latest_books(books, BookDate::Added, 10); // 최근 추가된 10권만 표시
 
     let mut lines = vec![
         Line::from(vec![Span::styled(
@@ -361,7 +350,12 @@ fn render_recent_books_report(f: &mut Frame, area: Rect, books: &[ExtendedBook])
                 Span::raw("    "),
                 Span::styled(stars, Style::default().fg(Color::Yellow)),
                 Span::styled(
-                    format!(" {:.1}/5 ({} 리뷰)", avg_rating, book.reviews.len()),
+                    format!(
+                        " {:.1}/{} ({} 리뷰)",
+                        avg_rating,
+                        max_rating(),
+                        book.reviews.len()
+                    ),
                     Style::default().fg(Color::Gray),
                 ),
             ]));
@@ -379,14 +373,338 @@ fn render_recent_books_report(f: &mut Frame, area: Rect, books: &[ExtendedBook])
     f.render_widget(content, area);
 }
 
-/// 상태바를 렌더링합니다
-fn render_status_bar(f: &mut Frame, area: Rect, current_view: &ReportView) {
-    let status_text = match current_view {
-        ReportView::Authors => "💡 1: 작가 통계 | 2: 연도 통계 | 3: 최근 도서 | Esc: 뒤로가기",
-        ReportView::Years => "💡 1: 작가 통계 | 2: 연도 통계 | 3: 최근 도서 | Esc: 뒤로가기",
-        ReportView::Recent => "💡 1: 작가 통계 | 2: 연도 통계 | 3: 최근 도서 | Esc: 뒤로가기",
+/// 장르별 도서 수, 페이지, 평균 평점과 번역서/원서 비율을 렌더링합니다
+fn render_genres_report(f: &mut Frame, area: Rect, books: &[ExtendedBook], stats: &LibraryStats) {
+    if books.is_empty() {
+        render_empty(f, area, "장르별 통계");
+        return;
+    }
+
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            format!("🏷️  {} 개 장르", stats.genres.len()),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+    ];
+
+    for count in &stats.genres {
+        let mut info = format!("📚 {} 권 • 📄 {} 페이지", count.books, count.pages);
+        if let Some(rating) = count.average_rating {
+            info.push_str(&format!(
+                " • ⭐ {:.1}/{}",
+                rating, stats.totals.rating_scale
+            ));
+        }
+        lines.push(Line::from(vec![Span::styled(
+            count.genre.clone(),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(info, Style::default().fg(Color::Blue)),
+        ]));
+    }
+
+    let translated = stats.totals.translated_books;
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::raw("🌐 번역서 "),
+        Span::styled(
+            format!("{} 권", translated),
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" • 원서 "),
+        Span::styled(
+            format!("{} 권", stats.totals.total_books - translated),
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+
+    let content = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("장르별 통계"))
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(content, area);
+}
+
+/// 평점 분포를 막대로 렌더링합니다
+fn render_ratings_report(f: &mut Frame, area: Rect, books: &[ExtendedBook], stats: &LibraryStats) {
+    if books.is_empty() {
+        render_empty(f, area, "평점 분포");
+        return;
+    }
+
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            format!("⭐ 리뷰 {} 개", stats.totals.total_reviews),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+    ];
+
+    for count in &stats.ratings {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:>2}★ ", count.rating),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                "█".repeat(count.reviews),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                format!(" ({} 개)", count.reviews),
+                Style::default().fg(Color::Gray),
+            ),
+        ]));
+    }
+
+    if let Some(average) = stats.totals.average_rating {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw("평균 평점: "),
+            Span::styled(
+                format!("{:.1}/{}", average, stats.totals.rating_scale),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    let content = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("평점 분포"))
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(content, area);
+}
+
+/// 짧은/보통/긴 책 분포와 평균, 가장 긴 책과 짧은 책을 렌더링합니다
+fn render_pages_report(f: &mut Frame, area: Rect, books: &[ExtendedBook], stats: &LibraryStats) {
+    if books.is_empty() {
+        render_empty(f, area, "분량 분포");
+        return;
+    }
+
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            format!(
+                "📄 페이지 수가 있는 도서 {} 권",
+                stats.pages.books_with_pages
+            ),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+    ];
+
+    for bucket in &stats.page_lengths {
+        let label = match bucket.length.as_str() {
+            "short" => "짧은 책",
+            "medium" => "보통",
+            _ => "긴 책",
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} ({}): ", label, bucket.pages),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled("█".repeat(bucket.books), Style::default().fg(Color::Green)),
+            Span::styled(
+                format!(" ({} 권)", bucket.books),
+                Style::default().fg(Color::Gray),
+            ),
+        ]));
+    }
+
+    if let Some(average) = stats.pages.average_pages {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw("평균 분량: "),
+            Span::styled(
+                format!("{:.0} 페이지", average),
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+    for (label, book) in [
+        ("가장 긴 책: ", &stats.pages.longest),
+        ("가장 짧은 책: ", &stats.pages.shortest),
+    ] {
+        if let Some(book) = book {
+            lines.push(Line::from(vec![
+                Span::raw(label),
+                Span::styled(
+                    format!("{} ({} 페이지)", book.title, book.pages),
+                    Style::default().fg(Color::Blue),
+                ),
+            ]));
+        }
+    }
+
+    let content = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("분량 분포"))
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(content, area);
+}
+
+/// 독서 속도 요약과 최근 완독 히트맵을 렌더링합니다
+fn render_pace_report(f: &mut Frame, area: Rect, books: &[ExtendedBook]) {
+    let today = Local::now().date_naive();
+    let pace = reading_pace(books, today);
+    let (Some(first), Some(last)) = (&pace.first_finished, &pace.last_finished) else {
+        let content = Paragraph::new("📅 읽은 날짜가 기록된 도서가 없습니다.")
+            .block(Block::default().borders(Borders::ALL).title("독서 속도"))
+            .style(Style::default().fg(Color::Gray));
+        f.render_widget(content, area);
+        return;
     };
 
+    // 요일 라벨 4칸과 테두리를 빼고 한 주에 2칸씩
+    let weeks = (area.width.saturating_sub(6) / 2).clamp(1, 53) as usize;
+    let mut lines = heatmap_lines(&finishes_by_day(books), today, weeks);
+    lines.push(Line::from(""));
+
+    let value = Style::default()
+        .fg(Color::Blue)
+        .add_modifier(Modifier::BOLD);
+    lines.extend([
+        Line::from(vec![
+            Span::raw("완독: "),
+            Span::styled(
+                format!("{} 권 • {} 페이지", pace.finished, pace.pages),
+                value,
+            ),
+            Span::styled(
+                format!(" ({} ~ {})", first, last),
+                Style::default().fg(Color::Gray),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("연속 기록: "),
+            Span::styled(format!("현재 {} 주", pace.current_streak_weeks), value),
+            Span::raw(" • "),
+            Span::styled(format!("최장 {} 주", pace.longest_streak_weeks), value),
+        ]),
+    ]);
+    if let Some(days) = pace.average_days_between {
+        lines.push(Line::from(vec![
+            Span::raw("완독 간격: 평균 "),
+            Span::styled(format!("{:.1} 일", days), value),
+        ]));
+    }
+    if let Some(month) = pace.monthly.last() {
+        lines.push(Line::from(vec![
+            Span::raw("이번 달: "),
+            Span::styled(
+                format!("{} 권 • {} 페이지", month.books, month.pages),
+                value,
+            ),
+        ]));
+    }
+
+    let content =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("독서 속도"));
+
+    f.render_widget(content, area);
+}
+
+/// 최근 `weeks`주의 완독 수를 GitHub 스타일 달력으로 그립니다 (열: 주, 행: 월~일)
+fn heatmap_lines(
+    days: &BTreeMap<NaiveDate, usize>,
+    today: NaiveDate,
+    weeks: usize,
+) -> Vec<Line<'static>> {
+    let start = week_start(today) - Duration::weeks(weeks as i64 - 1);
+    let week = |i: usize| start + Duration::weeks(i as i64);
+
+    // 달이 바뀌는 주 위에 월 표시
+    let mut months = vec![Span::raw("    ")];
+    for i in 0..weeks {
+        let label = if i == 0 || week(i).month() != week(i - 1).month() {
+            format!("{:<2}", week(i).month())
+        } else {
+            "  ".to_string()
+        };
+        months.push(Span::styled(label, Style::default().fg(Color::Gray)));
+    }
+
+    let mut lines = vec![Line::from(months)];
+    for (weekday, label) in ["월  ", "    ", "수  ", "    ", "금  ", "    ", "    "]
+        .into_iter()
+        .enumerate()
+    {
+        let mut row = vec![Span::styled(label, Style::default().fg(Color::Gray))];
+        for i in 0..weeks {
+            let date = week(i) + Duration::days(weekday as i64);
+            let cell = if date > today {
+                Span::raw("  ")
+            } else {
+                match days.get(&date).copied().unwrap_or(0) {
+                    0 => Span::styled("· ", Style::default().fg(Color::DarkGray)),
+                    1 => Span::styled("▪ ", Style::default().fg(Color::Green)),
+                    2 => Span::styled("■ ", Style::default().fg(Color::Green)),
+                    _ => Span::styled(
+                        "■ ",
+                        Style::default()
+                            .fg(Color::LightGreen)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                }
+            };
+            row.push(cell);
+        }
+        lines.push(Line::from(row));
+    }
+
+    lines.push(Line::from(vec![
+        Span::styled("    적음 ", Style::default().fg(Color::Gray)),
+        Span::styled("· ", Style::default().fg(Color::DarkGray)),
+        Span::styled("▪ ", Style::default().fg(Color::Green)),
+        Span::styled("■ ", Style::default().fg(Color::Green)),
+        Span::styled(
+            "■ ",
+            Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("많음", Style::default().fg(Color::Gray)),
+    ]));
+    lines
+}
+
+/// 도서가 없을 때의 안내를 렌더링합니다
+fn render_empty(f: &mut Frame, area: Rect, title: &str) {
+    let content = Paragraph::new("📚 도서가 없습니다.")
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title.to_string()),
+        )
+        .style(Style::default().fg(Color::Gray));
+    f.render_widget(content, area);
+}
+
+/// 상태바를 렌더링합니다
+fn render_status_bar(f: &mut Frame, area: Rect) {
+    let status_text =
+        "💡 1: 작가 | 2: 연도 | 3: 최근 | 4: 장르 | 5: 평점 | 6: 분량 | 7: 속도 | Esc: 뒤로가기";
+
     let status_bar = Paragraph::new(status_text)
         .block(Block::default().borders(Borders::ALL))
         .style(Style::default().fg(Color::Gray));
@@ -93,7 +93,7 @@ fn render_search_results(f: &mut Frame, area: Rect, state: &AppState) {
     }
 
     // 검색 결과 필터링
-    let search_results: Vec<(usize, &crate::lib::models::ExtendedBook)> = state
+    let search_results: Vec<(usize, &libro_core::models::ExtendedBook)> = state
         .books
         .iter()
         .enumerate()
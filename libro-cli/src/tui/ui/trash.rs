@@ -0,0 +1,85 @@
+//! 휴지통 화면 UI
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::tui::state::AppState;
+use crate::tui::theme::Theme;
+use libro_core::trash::TrashKind;
+
+/// 휴지통 화면을 렌더링합니다
+pub fn render_trash(f: &mut Frame, area: Rect, state: &AppState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // 헤더
+            Constraint::Min(0),    // 삭제된 항목 목록
+            Constraint::Length(3), // 상태바
+        ])
+        .split(area);
+
+    let header = Paragraph::new(format!("🗑️  휴지통 ({}개)", state.trash_items.len()))
+        .block(Block::default().borders(Borders::ALL))
+        .style(
+            Style::default()
+                .fg(Theme::current().accent)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_widget(header, chunks[0]);
+
+    if state.trash_items.is_empty() {
+        let empty = Paragraph::new("휴지통이 비어 있습니다")
+            .block(Block::default().borders(Borders::ALL).title("삭제된 항목"))
+            .style(Style::default().fg(Theme::current().muted));
+        f.render_widget(empty, chunks[1]);
+    } else {
+        let items: Vec<ListItem> = state
+            .trash_items
+            .iter()
+            .map(|item| {
+                let (label, detail) = match item.kind {
+                    TrashKind::Book => ("도서", format!("리뷰 {}개", item.reviews)),
+                    TrashKind::Review => ("리뷰", format!("도서 #{}", item.book_id)),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} #{} ", label, item.id),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::styled(
+                        item.title.clone(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("  {} | 삭제: {}", detail, item.deleted_at),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("삭제된 항목"))
+            .highlight_style(
+                Style::default()
+                    .bg(Theme::current().highlight_bg)
+                    .fg(Theme::current().highlight_fg)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(state.trash_selected_index));
+        f.render_stateful_widget(list, chunks[1], &mut list_state);
+    }
+
+    let status =
+        Paragraph::new("j/k: 이동 | Enter: 복원 | Esc: 뒤로 | 영구 삭제: libro-cli trash purge")
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(Theme::current().muted));
+    f.render_widget(status, chunks[2]);
+}
@@ -0,0 +1,226 @@
+//! 한 해 독서 결산 화면 UI (전체 화면)
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::tui::state::AppState;
+use crate::tui::theme::Theme;
+use crate::utils::markdown::{format_change, format_rating};
+use libro_core::stats::{year_in_review, YearInReview};
+
+/// 결산 화면을 터미널 전체에 렌더링합니다
+pub fn render_wrapped(f: &mut Frame, area: Rect, state: &AppState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // 헤더
+            Constraint::Min(0),    // 결산 내용
+            Constraint::Length(3), // 상태바
+        ])
+        .split(area);
+
+    let wrapped = year_in_review(&state.books, state.wrapped_year);
+
+    let header = Paragraph::new(format!("🎁 {} 독서 결산", wrapped.year))
+        .block(Block::default().borders(Borders::ALL))
+        .style(
+            Style::default()
+                .fg(Theme::current().accent)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_widget(header, chunks[0]);
+
+    if wrapped.totals.total_books == 0 {
+        let empty = Paragraph::new(format!("📅 {}년에 읽은 도서가 없습니다.", wrapped.year))
+            .block(Block::default().borders(Borders::ALL).title("결산"))
+            .style(Style::default().fg(Theme::current().muted));
+        f.render_widget(empty, chunks[1]);
+    } else {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+        render_summary(f, columns[0], &wrapped);
+        render_rankings(f, columns[1], &wrapped);
+    }
+
+    let status = Paragraph::new("h/l: 이전/다음 해 | Esc: 닫기 | q: 종료")
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().fg(Theme::current().muted));
+    f.render_widget(status, chunks[2]);
+}
+
+/// 총계, 처음/마지막 책, 가장 긴/짧은 책과 전년 대비 변화
+fn render_summary(f: &mut Frame, area: Rect, wrapped: &YearInReview) {
+    let totals = &wrapped.totals;
+    let value = Style::default()
+        .fg(Color::Blue)
+        .add_modifier(Modifier::BOLD);
+    let muted = Style::default().fg(Color::Gray);
+
+    let mut lines = vec![Line::from(vec![
+        Span::raw("📚 "),
+        Span::styled(
+            format!(
+                "{} 권 • {} 페이지 • 작가 {} 명",
+                totals.total_books, totals.total_pages, totals.total_authors
+            ),
+            value,
+        ),
+    ])];
+    if let Some(average) = totals.average_rating {
+        lines.push(Line::from(vec![
+            Span::raw("⭐ 평균 평점: "),
+            Span::styled(format!("{:.1}/{}", average, totals.rating_scale), value),
+        ]));
+    }
+    if let Some(share) = wrapped.translated_share {
+        lines.push(Line::from(vec![
+            Span::raw("🌐 번역서: "),
+            Span::styled(
+                format!(
+                    "{} / {} 권 ({:.0}%)",
+                    totals.translated_books,
+                    totals.total_books,
+                    share * 100.0
+                ),
+                value,
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+    for (label, book) in [
+        ("🌱 처음 읽은 책: ", &wrapped.first_book),
+        ("🏁 마지막 책: ", &wrapped.last_book),
+    ] {
+        if let Some(book) = book {
+            lines.push(Line::from(vec![
+                Span::raw(label),
+                Span::styled(
+                    book.title.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!(" ({})", book.date_read), muted),
+            ]));
+        }
+    }
+    for (label, book) in [
+        ("📏 가장 긴 책: ", &wrapped.longest),
+        ("📐 가장 짧은 책: ", &wrapped.shortest),
+    ] {
+        if let Some(book) = book {
+            lines.push(Line::from(vec![
+                Span::raw(label),
+                Span::styled(
+                    book.title.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!(" ({} 페이지)", book.pages), muted),
+            ]));
+        }
+    }
+
+    let changes = wrapped.changes();
+    if !changes.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("📈 {}년 대비", wrapped.year - 1),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for change in changes {
+            let color = if change.delta() > 0.0 {
+                Color::Green
+            } else if change.delta() < 0.0 {
+                Color::Red
+            } else {
+                Color::Gray
+            };
+            lines.push(Line::from(vec![
+                Span::raw(format!("{:<10}", change_label(change.label))),
+                Span::styled(
+                    format!(
+                        "{:>7} → {:<7}",
+                        format_rating(change.previous),
+                        format_rating(change.current)
+                    ),
+                    muted,
+                ),
+                Span::styled(format_change(change.delta()), Style::default().fg(color)),
+            ]));
+        }
+    }
+
+    let content =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("한 해 요약"));
+    f.render_widget(content, area);
+}
+
+/// 최고 평점 도서, 많이 읽은 작가와 장르
+fn render_rankings(f: &mut Frame, area: Rect, wrapped: &YearInReview) {
+    let rank = Style::default().fg(Color::Yellow);
+    let mut lines = Vec::new();
+
+    let heading = |title: &str| {
+        Line::from(Span::styled(
+            title.to_string(),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+
+    lines.push(heading("🏆 최고 평점"));
+    for (i, book) in wrapped.top_rated.iter().enumerate() {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}. ", i + 1), rank),
+            Span::styled(
+                book.title.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                " - {} ⭐ {}/{}",
+                book.authors, book.rating, wrapped.totals.rating_scale
+            )),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(heading("✍️  많이 읽은 작가"));
+    for (i, author) in wrapped.authors.iter().enumerate() {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}. ", i + 1), rank),
+            Span::raw(format!("{} ({} 권)", author.author, author.books)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(heading("🏷️  많이 읽은 장르"));
+    for (i, genre) in wrapped.genres.iter().enumerate() {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}. ", i + 1), rank),
+            Span::raw(format!("{} ({} 권)", genre.genre, genre.books)),
+        ]));
+    }
+
+    let content = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("순위"));
+    f.render_widget(content, area);
+}
+
+/// 전년 대비 항목 이름을 화면 언어로
+fn change_label(label: &str) -> &str {
+    match label {
+        "Books" => "도서",
+        "Pages" => "페이지",
+        "Authors" => "작가",
+        "Translated" => "번역서",
+        "Average rating" => "평균 평점",
+        other => other,
+    }
+}
@@ -0,0 +1,494 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::utils::library::{self, DEFAULT_LIBRARY};
+use libro_core::errors::{LibroError, LibroResult};
+
+/// Environment variable that points at an alternative config file
+pub const CONFIG_ENV: &str = "LIBRO_CONFIG";
+
+/// Built-in TUI themes
+pub const THEMES: &[&str] = &["dark", "light", "mono"];
+
+/// TUI actions that can be rebound under `[ui.keys]`
+pub const KEY_ACTIONS: &[&str] = &[
+    "quit",
+    "help",
+    "move_down",
+    "move_up",
+    "move_left",
+    "move_right",
+    "add_book",
+    "edit_book",
+    "delete",
+    "undo",
+    "add_review",
+    "new_review",
+    "search",
+    "report",
+    "libraries",
+    "trash",
+    "sort_books",
+    "recent",
+    "wrapped",
+];
+
+/// Keys accepted by `config get` and `config set`
+pub const KEYS: &[&str] = &[
+    "database.path",
+    "database.library",
+    "database.backend",
+    "defaults.genres",
+    "defaults.date_format",
+    "defaults.rating_scale",
+    "ui.theme",
+    "ui.keys.<action>",
+];
+
+const MIN_RATING_SCALE: i32 = 2;
+const MAX_RATING_SCALE: i32 = 10;
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Settings read from `config.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub database: DatabaseConfig,
+    pub defaults: DefaultsConfig,
+    pub ui: UiConfig,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    /// Database file; the data directory is used when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Active named library, set by `library switch`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub library: Option<String>,
+    /// How libraries are stored
+    #[serde(skip_serializing_if = "Backend::is_default")]
+    pub backend: Backend,
+}
+
+/// Storage backend for libraries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// A SQLite database file
+    #[default]
+    Sqlite,
+    /// A directory with a TOML file per book, next to where the database would be
+    Files,
+}
+
+impl Backend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Backend::Sqlite => "sqlite",
+            Backend::Files => "files",
+        }
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Backend::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DefaultsConfig {
+    /// Genres offered when adding a book; each interface's own list when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genres: Option<Vec<String>>,
+    /// strftime-style format used to display dates
+    pub date_format: String,
+    /// Highest rating a review can have
+    pub rating_scale: i32,
+}
+
+impl Default for DefaultsConfig {
+    fn default() -> Self {
+        Self {
+            genres: None,
+            date_format: "%Y-%m-%d".to_string(),
+            rating_scale: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    pub theme: String,
+    /// Extra key bindings for the TUI, by action name
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, char>,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            theme: "dark".to_string(),
+            keys: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Parse and validate a config file's contents
+    pub fn from_toml(content: &str) -> LibroResult<Self> {
+        let config: Config = toml::from_str(content)
+            .map_err(|e| LibroError::invalid_input(format!("Invalid config file: {}", e)))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn to_toml(&self) -> LibroResult<String> {
+        toml::to_string_pretty(self)
+            .map_err(|e| LibroError::internal(format!("Could not write config: {}", e)))
+    }
+
+    /// Check values that TOML types alone cannot
+    pub fn validate(&self) -> LibroResult<()> {
+        validate_date_format(&self.defaults.date_format)?;
+        validate_rating_scale(self.defaults.rating_scale)?;
+        validate_theme(&self.ui.theme)?;
+        if let Some(name) = &self.database.library {
+            library::validate_name(name)?;
+        }
+        for action in self.ui.keys.keys() {
+            validate_action(action)?;
+        }
+        Ok(())
+    }
+
+    /// Value of a dotted key, formatted the way `set` accepts it
+    pub fn get(&self, key: &str) -> LibroResult<String> {
+        let value = match key {
+            "database.path" => self.database.path.clone().unwrap_or_default(),
+            "database.library" => self
+                .database
+                .library
+                .clone()
+                .unwrap_or_else(|| DEFAULT_LIBRARY.to_string()),
+            "database.backend" => self.database.backend.as_str().to_string(),
+            "defaults.genres" => self
+                .defaults
+                .genres
+                .as_ref()
+                .map(|genres| genres.join(", "))
+                .unwrap_or_default(),
+            "defaults.date_format" => self.defaults.date_format.clone(),
+            "defaults.rating_scale" => self.defaults.rating_scale.to_string(),
+            "ui.theme" => self.ui.theme.clone(),
+            _ => match key.strip_prefix("ui.keys.") {
+                Some(action) => {
+                    validate_action(action)?;
+                    self.ui
+                        .keys
+                        .get(action)
+                        .map(|c| c.to_string())
+                        .unwrap_or_default()
+                }
+                None => return Err(unknown_key(key)),
+            },
+        };
+        Ok(value)
+    }
+
+    /// Every key with its value, including the configured key bindings
+    pub fn entries(&self) -> LibroResult<Vec<(String, String)>> {
+        let mut entries = Vec::new();
+        for key in KEYS.iter().filter(|key| !key.contains('<')) {
+            entries.push((key.to_string(), self.get(key)?));
+        }
+        for (action, key) in &self.ui.keys {
+            entries.push((format!("ui.keys.{}", action), key.to_string()));
+        }
+        Ok(entries)
+    }
+
+    /// Set a dotted key from its string form; an empty value resets it
+    pub fn set(&mut self, key: &str, value: &str) -> LibroResult<()> {
+        let value = value.trim();
+        match key {
+            "database.path" => {
+                self.database.path = Some(value.to_string()).filter(|v| !v.is_empty());
+            }
+            "database.library" if value.is_empty() || value == DEFAULT_LIBRARY => {
+                self.database.library = None;
+            }
+            "database.library" => {
+                library::validate_name(value)?;
+                self.database.library = Some(value.to_string());
+            }
+            "database.backend" if value.is_empty() => self.database.backend = Backend::default(),
+            "database.backend" => self.database.backend = parse_backend(value)?,
+            "defaults.genres" => {
+                let genres: Vec<String> = value
+                    .split(',')
+                    .map(|genre| genre.trim().to_string())
+                    .filter(|genre| !genre.is_empty())
+                    .collect();
+                self.defaults.genres = Some(genres).filter(|g| !g.is_empty());
+            }
+            "defaults.date_format" if value.is_empty() => {
+                self.defaults.date_format = DefaultsConfig::default().date_format;
+            }
+            "defaults.date_format" => {
+                validate_date_format(value)?;
+                self.defaults.date_format = value.to_string();
+            }
+            "defaults.rating_scale" if value.is_empty() => {
+                self.defaults.rating_scale = DefaultsConfig::default().rating_scale;
+            }
+            "defaults.rating_scale" => {
+                let scale = value.parse()?;
+                validate_rating_scale(scale)?;
+                self.defaults.rating_scale = scale;
+            }
+            "ui.theme" if value.is_empty() => self.ui.theme = UiConfig::default().theme,
+            "ui.theme" => {
+                validate_theme(value)?;
+                self.ui.theme = value.to_string();
+            }
+            _ => {
+                let action = key
+                    .strip_prefix("ui.keys.")
+                    .ok_or_else(|| unknown_key(key))?;
+                validate_action(action)?;
+                let mut chars = value.chars();
+                match (chars.next(), chars.next()) {
+                    (None, _) => {
+                        self.ui.keys.remove(action);
+                    }
+                    (Some(c), None) if !c.is_whitespace() => {
+                        self.ui.keys.insert(action.to_string(), c);
+                    }
+                    _ => {
+                        return Err(LibroError::invalid_input(format!(
+                            "Key binding must be a single character, got '{}'",
+                            value
+                        )))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Location of the config file: `$LIBRO_CONFIG`, else `<config dir>/libro/config.toml`
+pub fn config_path() -> PathBuf {
+    match env::var(CONFIG_ENV) {
+        Ok(path) if !path.trim().is_empty() => PathBuf::from(path),
+        _ => dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("libro")
+            .join("config.toml"),
+    }
+}
+
+/// Read a config file; a missing file means the defaults
+pub fn load_from(path: &Path) -> LibroResult<Config> {
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    Config::from_toml(&fs::read_to_string(path)?)
+}
+
+/// Write a config file, creating its directory if needed
+pub fn save_to(path: &Path, config: &Config) -> LibroResult<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    fs::write(path, config.to_toml()?)?;
+    Ok(())
+}
+
+/// Load the config file for this run.
+///
+/// A broken file is reported and the defaults are used instead, so commands
+/// such as `config set` can still repair it.
+pub fn init() -> LibroResult<()> {
+    let path = config_path();
+    let (config, result) = match load_from(&path) {
+        Ok(config) => (config, Ok(())),
+        Err(e) => (
+            Config::default(),
+            Err(LibroError::invalid_input(format!(
+                "{} ({})",
+                e,
+                path.display()
+            ))),
+        ),
+    };
+    let _ = CONFIG.set(config);
+    result
+}
+
+/// The config for this run, loaded on first use
+pub fn current() -> &'static Config {
+    CONFIG.get_or_init(|| load_from(&config_path()).unwrap_or_default())
+}
+
+fn validate_date_format(format: &str) -> LibroResult<()> {
+    use chrono::format::{Item, StrftimeItems};
+
+    if format.trim().is_empty() || StrftimeItems::new(format).any(|item| item == Item::Error) {
+        return Err(LibroError::invalid_field(
+            "defaults.date_format",
+            "strftime",
+            format!("Invalid date format '{}'", format),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_rating_scale(scale: i32) -> LibroResult<()> {
+    if !(MIN_RATING_SCALE..=MAX_RATING_SCALE).contains(&scale) {
+        return Err(LibroError::invalid_field(
+            "defaults.rating_scale",
+            format!("range:{}-{}", MIN_RATING_SCALE, MAX_RATING_SCALE),
+            format!(
+                "Rating scale must be between {} and {}",
+                MIN_RATING_SCALE, MAX_RATING_SCALE
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_backend(value: &str) -> LibroResult<Backend> {
+    Backend::from_str(value, false).map_err(|_| {
+        LibroError::invalid_field(
+            "database.backend",
+            "one_of:sqlite,files",
+            format!(
+                "Unknown backend '{}' (expected one of: sqlite, files)",
+                value
+            ),
+        )
+    })
+}
+
+fn validate_theme(theme: &str) -> LibroResult<()> {
+    if !THEMES.contains(&theme) {
+        return Err(LibroError::invalid_field(
+            "ui.theme",
+            format!("one_of:{}", THEMES.join(",")),
+            format!(
+                "Unknown theme '{}' (expected one of: {})",
+                theme,
+                THEMES.join(", ")
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_action(action: &str) -> LibroResult<()> {
+    if !KEY_ACTIONS.contains(&action) {
+        return Err(LibroError::invalid_field(
+            "ui.keys",
+            format!("one_of:{}", KEY_ACTIONS.join(",")),
+            format!(
+                "Unknown key action '{}' (expected one of: {})",
+                action,
+                KEY_ACTIONS.join(", ")
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn unknown_key(key: &str) -> LibroError {
+    LibroError::invalid_input(format!(
+        "Unknown config key '{}' (expected one of: {})",
+        key,
+        KEYS.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_partial_file_uses_defaults() {
+        let config =
+            Config::from_toml("[defaults]\nrating_scale = 10\n\n[ui.keys]\nquit = \"x\"\n")
+                .unwrap();
+
+        assert_eq!(config.defaults.rating_scale, 10);
+        assert_eq!(config.defaults.date_format, "%Y-%m-%d");
+        assert_eq!(config.ui.theme, "dark");
+        assert_eq!(config.ui.keys.get("quit"), Some(&'x'));
+        assert_eq!(config.database.path, None);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_values() {
+        assert!(Config::from_toml("[defaults]\nrating_scale = 1\n").is_err());
+        assert!(Config::from_toml("[defaults]\ndate_format = \"%Q\"\n").is_err());
+        assert!(Config::from_toml("[ui]\ntheme = \"neon\"\n").is_err());
+        assert!(Config::from_toml("[ui.keys]\nfly = \"f\"\n").is_err());
+        assert!(Config::from_toml("[database]\nurl = \"x\"\n").is_err());
+        assert!(Config::from_toml("[database]\nlibrary = \"../x\"\n").is_err());
+    }
+
+    #[test]
+    fn test_get_and_set() {
+        let mut config = Config::default();
+
+        config.set("defaults.genres", "SF, Essay,,Poetry").unwrap();
+        assert_eq!(config.get("defaults.genres").unwrap(), "SF, Essay, Poetry");
+
+        config.set("defaults.date_format", "%d.%m.%Y").unwrap();
+        config.set("ui.keys.add_book", "+").unwrap();
+        assert_eq!(config.get("ui.keys.add_book").unwrap(), "+");
+
+        assert!(config.set("ui.keys.add_book", "ab").is_err());
+        assert!(config.set("defaults.rating_scale", "eleven").is_err());
+        assert!(config.set("colour", "red").is_err());
+        assert!(config.set("database.backend", "csv").is_err());
+
+        config.set("database.backend", "files").unwrap();
+        assert_eq!(config.database.backend, Backend::Files);
+        assert_eq!(config.get("database.backend").unwrap(), "files");
+        assert!(config.get("ui.keys.fly").is_err());
+
+        // Empty values go back to the default
+        config.set("defaults.date_format", "").unwrap();
+        config.set("ui.keys.add_book", "").unwrap();
+        assert_eq!(config.defaults.date_format, "%Y-%m-%d");
+        assert!(config.ui.keys.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("libro").join("config.toml");
+        assert_eq!(load_from(&path).unwrap(), Config::default());
+
+        let mut config = Config::default();
+        config.set("database.path", "/data/books.db").unwrap();
+        config.set("database.backend", "files").unwrap();
+        config.set("ui.theme", "light").unwrap();
+        config.set("ui.keys.quit", "x").unwrap();
+        save_to(&path, &config).unwrap();
+
+        assert_eq!(load_from(&path).unwrap(), config);
+    }
+}
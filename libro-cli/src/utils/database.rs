@@ -1,8 +1,165 @@
 use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-/// Get the database path from environment variable or use default
+use crate::utils::config::{self, Backend};
+use crate::utils::library;
+use libro_core::db_operations::Database;
+use libro_core::errors::{LibroError, LibroResult};
+use libro_core::files::FileStore;
+use libro_core::store::LibraryStore;
+
+/// Number of timestamped backups kept by default
+pub const DEFAULT_BACKUP_KEEP: usize = 10;
+
+/// Database path given with `--db`
+static DB_PATH_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Library given with `--library`
+static LIBRARY_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Where this run's database comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbSource {
+    /// An explicit file from `--db` or `LIBRO_DB_PATH`
+    Path(String),
+    /// A named library (see `utils::library`)
+    Library(String),
+}
+
+/// Use `path` for this run regardless of the environment and config file
+pub fn set_db_path_override(path: String) {
+    let _ = DB_PATH_OVERRIDE.set(path);
+}
+
+/// Use the library `name` for this run instead of the active one
+pub fn set_library_override(name: String) {
+    let _ = LIBRARY_OVERRIDE.set(name);
+}
+
+/// Get the database path.
+///
+/// Precedence: `--db` flag, `--library` flag, `LIBRO_DB_PATH`, then the
+/// library chosen with `library switch`. The default library lives at
+/// `database.path` from the config file, or `libro.db` in the user's data
+/// directory.
 pub fn get_db_path() -> String {
-    env::var("LIBRO_DB_PATH").unwrap_or_else(|_| "libro.db".to_string())
+    match db_source() {
+        DbSource::Path(path) => path,
+        DbSource::Library(name) => {
+            let path = library::library_path(&name);
+            if let Some(parent) = path.parent() {
+                if !parent.as_os_str().is_empty() {
+                    let _ = fs::create_dir_all(parent);
+                }
+            }
+            path.to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// Where this run's database comes from, after flags, environment and config
+pub fn db_source() -> DbSource {
+    resolve_db_source(
+        DB_PATH_OVERRIDE.get().cloned(),
+        LIBRARY_OVERRIDE.get().cloned(),
+        env::var("LIBRO_DB_PATH").ok(),
+        library::active_library(),
+    )
+}
+
+/// Apply the precedence order to the candidate sources; empty values are skipped
+pub fn resolve_db_source(
+    flag: Option<String>,
+    library_flag: Option<String>,
+    env_value: Option<String>,
+    active_library: String,
+) -> DbSource {
+    let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+
+    if let Some(path) = non_empty(flag) {
+        DbSource::Path(path)
+    } else if let Some(name) = non_empty(library_flag) {
+        DbSource::Library(name)
+    } else if let Some(path) = non_empty(env_value) {
+        DbSource::Path(path)
+    } else {
+        DbSource::Library(active_library)
+    }
+}
+
+/// Backend chosen with `database.backend` in the config file
+pub fn backend() -> Backend {
+    config::current().database.backend
+}
+
+/// Where `backend` keeps the library for a database path.
+///
+/// The files backend uses a directory named after the database file without
+/// its `.db` extension, so `libro.db` becomes `libro/`.
+pub fn store_path(db_path: impl AsRef<Path>, backend: Backend) -> PathBuf {
+    let db_path = db_path.as_ref();
+    match backend {
+        Backend::Sqlite => db_path.to_path_buf(),
+        Backend::Files => db_path.with_extension(""),
+    }
+}
+
+/// Whether `path` already holds a library stored with `backend`
+pub fn store_exists(path: &Path, backend: Backend) -> bool {
+    match backend {
+        Backend::Sqlite => path.is_file(),
+        Backend::Files => FileStore::exists(path),
+    }
+}
+
+/// Open (creating if needed) the library stored at `path` with `backend`
+pub fn open_backend(path: &Path, backend: Backend) -> LibroResult<Box<dyn LibraryStore>> {
+    Ok(match backend {
+        Backend::Sqlite => Box::new(Database::new(&path.to_string_lossy())?),
+        Backend::Files => Box::new(FileStore::open(path)?),
+    })
+}
+
+/// Open this run's library with the configured backend
+pub fn open_store() -> LibroResult<Box<dyn LibraryStore>> {
+    let backend = backend();
+    open_backend(&store_path(get_db_path(), backend), backend)
+}
+
+/// Fail for commands that work on the SQLite file itself
+pub fn require_sqlite(command: &str) -> LibroResult<()> {
+    if backend() != Backend::Sqlite {
+        return Err(LibroError::invalid_input(format!(
+            "`{}` needs the sqlite backend (database.backend is '{}')",
+            command,
+            backend().as_str()
+        )));
+    }
+    Ok(())
+}
+
+/// `libro/libro.db` under the user's data directory
+pub fn default_db_path() -> PathBuf {
+    dirs::data_dir()
+        .map(|dir| dir.join("libro").join("libro.db"))
+        .unwrap_or_else(|| PathBuf::from("libro.db"))
+}
+
+/// Default backup directory: `backups/` next to the database file
+pub fn default_backup_dir(db_path: &str) -> PathBuf {
+    Path::new(db_path)
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join("backups")
+}
+
+/// Whether the TUI should take a backup when it exits (`LIBRO_BACKUP_ON_EXIT`)
+pub fn backup_on_exit_enabled() -> bool {
+    env::var("LIBRO_BACKUP_ON_EXIT")
+        .map(|value| !matches!(value.trim(), "" | "0" | "false" | "no"))
+        .unwrap_or(false)
 }
 
 #[cfg(test)]
@@ -12,16 +169,52 @@ mod tests {
 
     #[test]
     fn test_default_db_path() {
-        // Save current value if it exists
-        let original = env::var("LIBRO_DB_PATH").ok();
+        assert!(default_db_path().ends_with("libro.db"));
+    }
 
-        env::remove_var("LIBRO_DB_PATH");
-        assert_eq!(get_db_path(), "libro.db");
+    #[test]
+    fn test_db_path_precedence() {
+        let some = |s: &str| Some(s.to_string());
+        let active = || "home".to_string();
 
-        // Restore original value if it existed
-        if let Some(value) = original {
-            env::set_var("LIBRO_DB_PATH", value);
-        }
+        assert_eq!(
+            resolve_db_source(some("flag.db"), some("club"), some("env.db"), active()),
+            DbSource::Path("flag.db".to_string())
+        );
+        assert_eq!(
+            resolve_db_source(None, some("club"), some("env.db"), active()),
+            DbSource::Library("club".to_string())
+        );
+        assert_eq!(
+            resolve_db_source(None, None, some("env.db"), active()),
+            DbSource::Path("env.db".to_string())
+        );
+        assert_eq!(
+            resolve_db_source(None, None, some(""), active()),
+            DbSource::Library("home".to_string())
+        );
+    }
+
+    #[test]
+    fn test_store_path() {
+        assert_eq!(
+            store_path("/data/libro.db", Backend::Sqlite),
+            PathBuf::from("/data/libro.db")
+        );
+        assert_eq!(
+            store_path("/data/libro.db", Backend::Files),
+            PathBuf::from("/data/libro")
+        );
+        assert_eq!(store_path("books", Backend::Files), PathBuf::from("books"));
+    }
+
+    #[test]
+    fn test_default_backup_dir() {
+        assert_eq!(default_backup_dir("libro.db"), PathBuf::from("backups"));
+        assert_eq!(
+            default_backup_dir("/data/books/libro.db"),
+            PathBuf::from("/data/books/backups")
+        );
     }
 
     #[test]
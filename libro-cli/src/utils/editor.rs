@@ -0,0 +1,318 @@
+use chrono::NaiveDate;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::utils::date::{format_iso_date, parse_and_validate_date};
+use libro_core::errors::{validation, LibroError, LibroResult};
+
+const FENCE: &str = "---";
+const ERROR_PREFIX: &str = "# ERROR:";
+
+/// Review fields as written in the editor template
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewDraft {
+    pub rating: Option<i32>,
+    pub date_read: Option<NaiveDate>,
+    pub text: String,
+}
+
+/// A draft that passed validation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedReview {
+    pub rating: i32,
+    pub date_read: Option<NaiveDate>,
+    pub text: String,
+}
+
+/// The user's editor: `$VISUAL`, then `$EDITOR`
+pub fn editor_command() -> Option<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|key| env::var(key).ok())
+        .find(|value| !value.trim().is_empty())
+}
+
+/// Render the editor template: a frontmatter block followed by the review text
+pub fn render_template(book_title: &str, draft: &ReviewDraft) -> String {
+    let rating = draft.rating.map(|r| r.to_string()).unwrap_or_default();
+    let date = draft
+        .date_read
+        .map(|d| format_iso_date(&d))
+        .unwrap_or_default();
+    format!(
+        "{fence}\n\
+         # Review for '{title}'\n\
+         # rating: 1-{max}, date_read: YYYY-MM-DD\n\
+         # Write the review below the closing line. Leave it empty to cancel.\n\
+         rating: {rating}\n\
+         date_read: {date}\n\
+         {fence}\n\
+         {text}\n",
+        fence = FENCE,
+        title = book_title,
+        max = validation::max_rating(),
+        rating = rating,
+        date = date,
+        text = draft.text.trim_end()
+    )
+}
+
+/// Parse an edited template back into a validated review
+pub fn parse_review(content: &str) -> LibroResult<ParsedReview> {
+    let mut lines = content.lines();
+    if lines.next().map(str::trim) != Some(FENCE) {
+        return Err(LibroError::invalid_input(
+            "The review must start with a '---' frontmatter block",
+        ));
+    }
+
+    let mut rating = None;
+    let mut date_read = None;
+    let mut closed = false;
+    for line in lines.by_ref() {
+        let line = line.trim();
+        if line == FENCE {
+            closed = true;
+            break;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = line.split_once(':').ok_or_else(|| {
+            LibroError::invalid_input(format!("Expected 'key: value', got '{}'", line))
+        })?;
+        let value = value.trim();
+        match key.trim() {
+            "rating" if !value.is_empty() => {
+                let parsed: i32 = value.parse()?;
+                validation::validate_rating(parsed)?;
+                rating = Some(parsed);
+            }
+            "rating" => {}
+            "date_read" if !value.is_empty() => date_read = Some(parse_and_validate_date(value)?),
+            "date_read" => {}
+            other => {
+                return Err(LibroError::invalid_input(format!(
+                    "Unknown field '{}'",
+                    other
+                )))
+            }
+        }
+    }
+    if !closed {
+        return Err(LibroError::invalid_input(
+            "The frontmatter block is missing its closing '---'",
+        ));
+    }
+
+    let text = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+    validation::validate_non_empty(&text, "Review text")?;
+    let rating = rating
+        .ok_or_else(|| LibroError::invalid_field("rating", "required", "Rating is required"))?;
+
+    Ok(ParsedReview {
+        rating,
+        date_read,
+        text,
+    })
+}
+
+/// Put an error note at the top of the frontmatter, replacing any earlier one
+pub fn annotate_error(content: &str, error: &str) -> String {
+    // Only notes inside the frontmatter are ours; the body is left alone
+    let mut fences = 0;
+    let kept: Vec<&str> = content
+        .lines()
+        .filter(|line| {
+            if line.trim() == FENCE {
+                fences += 1;
+            }
+            fences >= 2 || !line.starts_with(ERROR_PREFIX)
+        })
+        .collect();
+
+    let note = format!("{} {}", ERROR_PREFIX, error);
+    match kept.first() {
+        Some(first) if first.trim() == FENCE => {
+            let mut lines = vec![FENCE.to_string(), note];
+            lines.extend(kept[1..].iter().map(|l| l.to_string()));
+            lines.join("\n") + "\n"
+        }
+        _ => format!(
+            "{}\n{}\nrating:\ndate_read:\n{}\n{}\n",
+            FENCE,
+            note,
+            FENCE,
+            kept.join("\n")
+        ),
+    }
+}
+
+/// Open the editor on a template until it parses, or the text is left empty.
+///
+/// Returns `UserCancelled` when the review text is empty.
+pub fn edit_review(
+    editor: &str,
+    book_title: &str,
+    draft: &ReviewDraft,
+) -> LibroResult<ParsedReview> {
+    let path = temp_file_path();
+    let mut content = render_template(book_title, draft);
+
+    let result = loop {
+        fs::write(&path, &content)?;
+        if let Err(e) = launch(editor, &path) {
+            break Err(e);
+        }
+        content = fs::read_to_string(&path)?;
+
+        if body_is_empty(&content) {
+            break Err(LibroError::UserCancelled);
+        }
+        match parse_review(&content) {
+            Ok(review) => break Ok(review),
+            Err(e) => content = annotate_error(&content, &e.to_string()),
+        }
+    };
+
+    let _ = fs::remove_file(&path);
+    result
+}
+
+fn launch(editor: &str, path: &Path) -> LibroResult<()> {
+    let (program, args) = split_editor_command(editor)?;
+    let status = Command::new(program).args(args).arg(path).status()?;
+    if !status.success() {
+        return Err(LibroError::invalid_input(format!(
+            "Editor '{}' exited with {}",
+            editor, status
+        )));
+    }
+    Ok(())
+}
+
+/// Split an editor command into the program and its arguments.
+///
+/// Editors are often configured with arguments, e.g. `code --wait`, and
+/// quoting works as in a shell. A value naming an existing file is taken as
+/// the program even when its path contains spaces.
+fn split_editor_command(editor: &str) -> LibroResult<(String, Vec<String>)> {
+    if Path::new(editor).is_file() {
+        return Ok((editor.to_string(), Vec::new()));
+    }
+    let mut parts = shell_words::split(editor).map_err(|e| {
+        LibroError::invalid_input(format!("Editor command '{}' is invalid: {}", editor, e))
+    })?;
+    if parts.is_empty() {
+        return Err(LibroError::invalid_input("Editor command is empty"));
+    }
+    let program = parts.remove(0);
+    Ok((program, parts))
+}
+
+fn body_is_empty(content: &str) -> bool {
+    let mut lines = content.lines();
+    if lines.next().map(str::trim) != Some(FENCE) {
+        return content.trim().is_empty();
+    }
+    if !lines.by_ref().any(|line| line.trim() == FENCE) {
+        return false;
+    }
+    lines.all(|line| line.trim().is_empty())
+}
+
+fn temp_file_path() -> PathBuf {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    env::temp_dir().join(format!("libro-review-{}-{}.md", std::process::id(), stamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft() -> ReviewDraft {
+        ReviewDraft {
+            rating: Some(4),
+            date_read: NaiveDate::from_ymd_opt(2024, 3, 1),
+            text: "First paragraph.\n\nSecond paragraph.".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_template_round_trip() {
+        let parsed = parse_review(&render_template("Exhalation", &draft())).unwrap();
+        assert_eq!(parsed.rating, 4);
+        assert_eq!(parsed.date_read, NaiveDate::from_ymd_opt(2024, 3, 1));
+        assert_eq!(parsed.text, "First paragraph.\n\nSecond paragraph.");
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_fields() {
+        let bad_rating = "---\nrating: 9\n---\nText\n";
+        assert!(parse_review(bad_rating).is_err());
+
+        let missing_rating = "---\nrating:\ndate_read:\n---\nText\n";
+        assert!(parse_review(missing_rating).is_err());
+
+        let unknown = "---\nrating: 3\nmood: happy\n---\nText\n";
+        assert!(parse_review(unknown).is_err());
+
+        let no_frontmatter = "Just text";
+        assert!(parse_review(no_frontmatter).is_err());
+    }
+
+    #[test]
+    fn test_annotate_error_replaces_previous_note() {
+        let content = "---\nrating: 9\n---\nText\n";
+        let once = annotate_error(content, "Rating must be between 1 and 5");
+        let twice = annotate_error(&once, "Still wrong");
+
+        assert!(twice.starts_with("---\n# ERROR: Still wrong\n"));
+        assert_eq!(twice.matches(ERROR_PREFIX).count(), 1);
+        assert!(twice.ends_with("---\nText\n"));
+    }
+
+    #[test]
+    fn test_split_editor_command() {
+        let (program, args) = split_editor_command("code --wait").unwrap();
+        assert_eq!(
+            (program.as_str(), args),
+            ("code", vec!["--wait".to_string()])
+        );
+
+        let (program, args) =
+            split_editor_command("'/opt/My Editor/edit' --title \"Libro review\"").unwrap();
+        assert_eq!(program, "/opt/My Editor/edit");
+        assert_eq!(args, ["--title", "Libro review"]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let spaced = dir.path().join("my editor");
+        fs::write(&spaced, "").unwrap();
+        let (program, args) = split_editor_command(spaced.to_str().unwrap()).unwrap();
+        assert_eq!(program, spaced.to_str().unwrap());
+        assert!(args.is_empty());
+
+        assert!(split_editor_command("   ").is_err());
+        assert!(split_editor_command("vim 'unclosed").is_err());
+    }
+
+    #[test]
+    fn test_body_is_empty() {
+        let empty = render_template(
+            "Exhalation",
+            &ReviewDraft {
+                rating: None,
+                date_read: None,
+                text: String::new(),
+            },
+        );
+        assert!(body_is_empty(&empty));
+        assert!(!body_is_empty(&render_template("Exhalation", &draft())));
+    }
+}
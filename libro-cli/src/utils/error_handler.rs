@@ -1,14 +1,94 @@
 use console::style;
 use std::process;
 
-use crate::lib::errors::{LibroError, LibroResult};
+use crate::utils::output::{is_machine_readable, output_format, OutputFormat};
+use libro_core::errors::{LibroError, LibroResult};
 
-/// Handle errors at the CLI level with user-friendly messages
+/// Exit status for errors without a more specific status (internal errors)
+pub const EXIT_FAILURE: i32 = 1;
+
+/// Exit status for invalid arguments or input, the same status clap uses
+pub const EXIT_USAGE: i32 = 2;
+
+/// Exit status when a value fails validation
+pub const EXIT_VALIDATION: i32 = 3;
+
+/// Exit status when a requested book, review or writer does not exist
+pub const EXIT_NOT_FOUND: i32 = 4;
+
+/// Exit status for database errors
+pub const EXIT_DATABASE: i32 = 5;
+
+/// Exit status when another process holds a lock on the database
+pub const EXIT_DATABASE_LOCKED: i32 = 6;
+
+/// Exit status for file system errors
+pub const EXIT_IO: i32 = 7;
+
+/// Exit status for malformed JSON input
+pub const EXIT_DATA: i32 = 8;
+
+/// Exit status when the user declines a confirmation or leaves the editor
+/// empty, the status shells use for a command interrupted with Ctrl+C
+pub const EXIT_CANCELLED: i32 = 130;
+
+/// Exit status for an error code from `LibroError::code`
+pub fn exit_status(error: &LibroError) -> i32 {
+    match error.code() {
+        "cancelled" => EXIT_CANCELLED,
+        "invalid_input" => EXIT_USAGE,
+        "validation" | "date_parse" => EXIT_VALIDATION,
+        "book_not_found" | "review_not_found" | "writer_not_found" => EXIT_NOT_FOUND,
+        "database" => EXIT_DATABASE,
+        "database_locked" => EXIT_DATABASE_LOCKED,
+        "io" => EXIT_IO,
+        "json" => EXIT_DATA,
+        _ => EXIT_FAILURE,
+    }
+}
+
+/// Print an error as a single JSON object on stderr
+fn print_json_error(error: &LibroError) {
+    let mut body = serde_json::json!({
+        "code": error.code(),
+        "message": error.to_string(),
+        "exit_status": exit_status(error),
+    });
+    if let LibroError::Validation {
+        field, constraint, ..
+    } = error
+    {
+        body["field"] = serde_json::json!(field);
+        body["constraint"] = serde_json::json!(constraint);
+    }
+    eprintln!("{}", serde_json::json!({ "error": body }));
+}
+
+/// Handle errors at the CLI level with user-friendly messages.
+///
+/// Exits with the status for the error's code (see `exit_status`).
 pub fn handle_cli_error(error: LibroError) -> ! {
+    let status = exit_status(&error);
+    if matches!(output_format(), OutputFormat::Json | OutputFormat::Ndjson) {
+        print_json_error(&error);
+        process::exit(status);
+    }
+
     match error {
-        LibroError::Validation { message } => {
+        LibroError::Validation {
+            message,
+            field,
+            constraint,
+        } => {
             eprintln!("{} {}", style("❌ Validation Error:").bold().red(), message);
-            process::exit(1);
+            if let (Some(field), Some(constraint)) = (field, constraint) {
+                eprintln!(
+                    "{} {} ({})",
+                    style("Field:").bold().white(),
+                    field,
+                    constraint
+                );
+            }
         }
         LibroError::BookNotFound { id } => {
             eprintln!(
@@ -16,7 +96,6 @@ pub fn handle_cli_error(error: LibroError) -> ! {
                 style("❌ Error:").bold().red(),
                 id
             );
-            process::exit(1);
         }
         LibroError::ReviewNotFound { book_id } => {
             eprintln!(
@@ -24,7 +103,13 @@ pub fn handle_cli_error(error: LibroError) -> ! {
                 style("❌ Error:").bold().red(),
                 book_id
             );
-            process::exit(1);
+        }
+        LibroError::ReviewIdNotFound { id } => {
+            eprintln!(
+                "{} Review with ID {} not found",
+                style("❌ Error:").bold().red(),
+                id
+            );
         }
         LibroError::WriterNotFound { name } => {
             eprintln!(
@@ -32,7 +117,6 @@ pub fn handle_cli_error(error: LibroError) -> ! {
                 style("❌ Error:").bold().red(),
                 name
             );
-            process::exit(1);
         }
         LibroError::InvalidInput { message } => {
             eprintln!("{} {}", style("❌ Invalid Input:").bold().red(), message);
@@ -40,7 +124,6 @@ pub fn handle_cli_error(error: LibroError) -> ! {
                 "{} Please check your input and try again.",
                 style("💡 Hint:").bold().yellow()
             );
-            process::exit(1);
         }
         LibroError::Database(db_error) => {
             eprintln!(
@@ -50,7 +133,12 @@ pub fn handle_cli_error(error: LibroError) -> ! {
 
             // Check for common database errors
             let error_msg = db_error.to_string();
-            if error_msg.contains("FOREIGN KEY constraint failed") {
+            if status == EXIT_DATABASE_LOCKED {
+                eprintln!(
+                    "{} The database is in use by another process; try again when it is closed.",
+                    style("💡 Hint:").bold().yellow()
+                );
+            } else if error_msg.contains("FOREIGN KEY constraint failed") {
                 eprintln!(
                     "{} Cannot delete this item because it's referenced by other data.",
                     style("💡 Hint:").bold().yellow()
@@ -64,7 +152,6 @@ pub fn handle_cli_error(error: LibroError) -> ! {
             } else {
                 eprintln!("{} {}", style("Details:").bold().white(), error_msg);
             }
-            process::exit(1);
         }
         LibroError::Io(io_error) => {
             eprintln!(
@@ -72,7 +159,6 @@ pub fn handle_cli_error(error: LibroError) -> ! {
                 style("❌ IO Error:").bold().red(),
                 io_error
             );
-            process::exit(1);
         }
         LibroError::DateParse(date_error) => {
             eprintln!(
@@ -84,7 +170,6 @@ pub fn handle_cli_error(error: LibroError) -> ! {
                 "{} Use format YYYY-MM-DD (e.g., 2023-12-01)",
                 style("💡 Hint:").bold().yellow()
             );
-            process::exit(1);
         }
         LibroError::Json(json_error) => {
             eprintln!(
@@ -92,14 +177,12 @@ pub fn handle_cli_error(error: LibroError) -> ! {
                 style("❌ JSON Error:").bold().red(),
                 json_error
             );
-            process::exit(1);
         }
         LibroError::UserCancelled => {
             eprintln!(
                 "{} Operation cancelled by user",
                 style("ℹ️  Info:").bold().blue()
             );
-            process::exit(0);
         }
         LibroError::Internal { message } => {
             eprintln!(
@@ -111,9 +194,10 @@ pub fn handle_cli_error(error: LibroError) -> ! {
                 "{} This is likely a bug. Please report it.",
                 style("💡 Hint:").bold().yellow()
             );
-            process::exit(1);
         }
     }
+
+    process::exit(status);
 }
 
 /// Handle results with automatic error handling
@@ -124,20 +208,38 @@ pub fn handle_result<T>(result: LibroResult<T>) -> T {
     }
 }
 
+/// Print a status line; with a machine-readable `--format` it goes to stderr
+/// so stdout only carries data
+fn print_status(line: String) {
+    if is_machine_readable() {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
 /// Print a success message
 pub fn print_success(message: &str) {
-    println!("{} {}", style("✅ Success:").bold().green(), message);
+    print_status(format!(
+        "{} {}",
+        style("✅ Success:").bold().green(),
+        message
+    ));
 }
 
 /// Print an info message
 pub fn print_info(message: &str) {
-    println!("{} {}", style("ℹ️  Info:").bold().blue(), message);
+    print_status(format!("{} {}", style("ℹ️  Info:").bold().blue(), message));
 }
 
 /// Print a warning message
 #[allow(dead_code)]
 pub fn print_warning(message: &str) {
-    println!("{} {}", style("⚠️  Warning:").bold().yellow(), message);
+    print_status(format!(
+        "{} {}",
+        style("⚠️  Warning:").bold().yellow(),
+        message
+    ));
 }
 
 /// Print a debug message (only in debug mode)
@@ -175,7 +277,7 @@ pub mod validation {
         match year {
             Some(year) => {
                 let year = year as i32;
-                crate::lib::errors::validation::validate_year(year)?;
+                libro_core::errors::validation::validate_year(year)?;
                 Ok(Some(year))
             }
             None => Ok(None),
@@ -241,6 +343,34 @@ mod tests {
         assert!(validation::validate_year_option(Some(500)).is_err());
     }
 
+    #[test]
+    fn test_exit_status() {
+        assert_eq!(
+            exit_status(&LibroError::BookNotFound { id: 1 }),
+            EXIT_NOT_FOUND
+        );
+        assert_eq!(
+            exit_status(&LibroError::invalid_field("rating", "range:1-5", "bad")),
+            EXIT_VALIDATION
+        );
+        assert_eq!(exit_status(&LibroError::invalid_input("bad")), EXIT_USAGE);
+        assert_eq!(exit_status(&LibroError::internal("bug")), EXIT_FAILURE);
+        assert_eq!(exit_status(&LibroError::UserCancelled), EXIT_CANCELLED);
+
+        let busy = rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            None,
+        );
+        assert_eq!(
+            exit_status(&LibroError::Database(busy)),
+            EXIT_DATABASE_LOCKED
+        );
+        assert_eq!(
+            exit_status(&LibroError::Database(rusqlite::Error::InvalidQuery)),
+            EXIT_DATABASE
+        );
+    }
+
     #[test]
     fn test_print_functions() {
         // These functions should not panic
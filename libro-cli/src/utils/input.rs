@@ -2,9 +2,10 @@ use chrono::NaiveDate;
 use console::style;
 use dialoguer::{Confirm, Input, MultiSelect, Select};
 
-use crate::lib::errors::{LibroError, LibroResult};
-use crate::lib::models::*;
+use crate::utils::config;
 use crate::utils::date::{current_date, parse_and_validate_date};
+use libro_core::errors::{validation::max_rating, LibroError, LibroResult};
+use libro_core::models::*;
 
 /// Prompt for book information interactively
 pub fn prompt_for_book() -> LibroResult<NewBook> {
@@ -111,19 +112,22 @@ pub fn prompt_for_book() -> LibroResult<NewBook> {
         Some(year_input.parse()?)
     };
 
-    // Genre (required, with predefined options)
-    let genres = vec![
-        "Fiction",
-        "Non-fiction",
-        "Science & Technology",
-        "History & Biography",
-        "Self-Help & Business",
-        "Arts & Literature",
-        "Philosophy & Religion",
-        "Health & Lifestyle",
-        "Children & Young Adult",
-        "Other",
-    ];
+    // Genre (required, with predefined options from the config file)
+    let mut genres: Vec<&str> = match &config::current().defaults.genres {
+        Some(configured) => configured.iter().map(String::as_str).collect(),
+        None => vec![
+            "Fiction",
+            "Non-fiction",
+            "Science & Technology",
+            "History & Biography",
+            "Self-Help & Business",
+            "Arts & Literature",
+            "Philosophy & Religion",
+            "Health & Lifestyle",
+            "Children & Young Adult",
+        ],
+    };
+    genres.push("Other");
 
     let selection = Select::new()
         .with_prompt(format!("{} {}", style("*").red().bold(), "Genre"))
@@ -200,13 +204,14 @@ pub fn prompt_for_review(book_id: i64, book_title: &str) -> LibroResult<NewRevie
         None
     };
 
-    // Rating (required, 1-5)
+    // Rating (required, 1 to the configured scale)
+    let max = max_rating();
     let rating: i32 = Input::new()
-        .with_prompt("Rating (1-5 stars)")
-        .validate_with(|input: &String| -> Result<(), &str> {
+        .with_prompt(format!("Rating (1-{} stars)", max))
+        .validate_with(|input: &String| -> Result<(), String> {
             match input.parse::<i32>() {
-                Ok(n) if (1..=5).contains(&n) => Ok(()),
-                _ => Err("Please enter a rating between 1 and 5"),
+                Ok(n) if (1..=max).contains(&n) => Ok(()),
+                _ => Err(format!("Please enter a rating between 1 and {}", max)),
             }
         })
         .interact_text()?
@@ -342,7 +347,7 @@ pub fn prompt_edit_review(existing_review: &Review, book_title: &str) -> LibroRe
     println!("{}", "─".repeat(40));
 
     println!("Current review:");
-    println!("  Rating: {}/5", existing_review.rating);
+    println!("  Rating: {}/{}", existing_review.rating, max_rating());
     if let Some(date) = existing_review.date_read {
         println!("  Date read: {}", date);
     }
@@ -388,13 +393,14 @@ pub fn prompt_edit_review(existing_review: &Review, book_title: &str) -> LibroRe
         .default(false)
         .interact()?
     {
+        let max = max_rating();
         Input::new()
-            .with_prompt("New rating (1-5)")
+            .with_prompt(format!("New rating (1-{})", max))
             .with_initial_text(existing_review.rating.to_string())
-            .validate_with(|input: &String| -> Result<(), &str> {
+            .validate_with(|input: &String| -> Result<(), String> {
                 match input.parse::<i32>() {
-                    Ok(n) if (1..=5).contains(&n) => Ok(()),
-                    _ => Err("Please enter a rating between 1 and 5"),
+                    Ok(n) if (1..=max).contains(&n) => Ok(()),
+                    _ => Err(format!("Please enter a rating between 1 and {}", max)),
                 }
             })
             .interact_text()?
@@ -430,6 +436,8 @@ pub fn prompt_edit_review(existing_review: &Review, book_title: &str) -> LibroRe
         date_read,
         rating,
         review: review.trim().to_string(),
+        created_at: None,
+        updated_at: None,
     })
 }
 
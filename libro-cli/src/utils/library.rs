@@ -0,0 +1,152 @@
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::utils::config::{self, config_path, load_from, save_to};
+use crate::utils::database::{backend, default_db_path, store_exists, store_path};
+use libro_core::errors::{LibroError, LibroResult};
+
+/// Name of the library that uses `database.path` (or the default database)
+pub const DEFAULT_LIBRARY: &str = "default";
+
+const MAX_NAME_LENGTH: usize = 64;
+
+/// A library and where its backend keeps it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LibraryInfo {
+    pub name: String,
+    pub path: PathBuf,
+    pub active: bool,
+}
+
+/// Library names become file names, so keep them to letters, digits, `-` and `_`
+pub fn validate_name(name: &str) -> LibroResult<()> {
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH || !valid_chars {
+        return Err(LibroError::invalid_field(
+            "library",
+            format!("pattern:[A-Za-z0-9_-]{{1,{}}}", MAX_NAME_LENGTH),
+            format!(
+                "Invalid library name '{}': use up to {} letters, digits, '-' or '_'",
+                name, MAX_NAME_LENGTH
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Directory holding the named libraries: `libro/libraries/` in the data directory
+pub fn libraries_dir() -> PathBuf {
+    default_db_path()
+        .parent()
+        .map(|dir| dir.join("libraries"))
+        .unwrap_or_else(|| PathBuf::from("libraries"))
+}
+
+/// Database file for a library
+pub fn library_path(name: &str) -> PathBuf {
+    if name == DEFAULT_LIBRARY {
+        return match &config::current().database.path {
+            Some(path) => PathBuf::from(path),
+            None => default_db_path(),
+        };
+    }
+    libraries_dir().join(format!("{}.db", name))
+}
+
+/// Database file or files directory for a library, depending on the backend
+pub fn library_store_path(name: &str) -> PathBuf {
+    store_path(library_path(name), backend())
+}
+
+/// Whether a library has been created (the default library always exists)
+pub fn library_exists(name: &str) -> bool {
+    name == DEFAULT_LIBRARY || store_exists(&library_store_path(name), backend())
+}
+
+/// Make sure `name` refers to an existing library
+pub fn ensure_exists(name: &str) -> LibroResult<()> {
+    validate_name(name)?;
+    if !library_exists(name) {
+        return Err(LibroError::invalid_input(format!(
+            "Library '{}' does not exist (create it with `libro-cli library create {}`)",
+            name, name
+        )));
+    }
+    Ok(())
+}
+
+/// The library remembered by `library switch`
+pub fn active_library() -> String {
+    config::current()
+        .database
+        .library
+        .clone()
+        .unwrap_or_else(|| DEFAULT_LIBRARY.to_string())
+}
+
+/// Remember `name` as the active library in the config file
+pub fn set_active_library(name: &str) -> LibroResult<()> {
+    let path = config_path();
+    let mut config = load_from(&path)?;
+    config.set("database.library", name)?;
+    save_to(&path, &config)
+}
+
+/// The default library followed by the named ones, sorted by name
+pub fn list_libraries() -> LibroResult<Vec<LibraryInfo>> {
+    let active = active_library();
+    let mut names = Vec::new();
+
+    let dir = libraries_dir();
+    if dir.is_dir() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let name = path.file_stem().and_then(|name| name.to_str());
+            if let Some(name) = name {
+                if validate_name(name).is_ok()
+                    && name != DEFAULT_LIBRARY
+                    && library_store_path(name) == path
+                    && library_exists(name)
+                {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+    names.sort();
+    names.insert(0, DEFAULT_LIBRARY.to_string());
+
+    Ok(names
+        .into_iter()
+        .map(|name| LibraryInfo {
+            path: library_store_path(&name),
+            active: name == active,
+            name,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("book-club").is_ok());
+        assert!(validate_name("home_2024").is_ok());
+
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../escape").is_err());
+        assert!(validate_name("with space").is_err());
+        assert!(validate_name(&"x".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test_library_paths() {
+        assert!(library_path("book-club").ends_with("libraries/book-club.db"));
+        assert!(library_exists(DEFAULT_LIBRARY));
+    }
+}
@@ -0,0 +1,593 @@
+use crate::utils::date::format_iso_date;
+use libro_core::errors::{validation::max_rating, LibroResult};
+use libro_core::models::ExtendedBook;
+use libro_core::stats::YearInReview;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Line separating generated content from notes the user added by hand.
+/// Written as an Obsidian comment so it is hidden in reading view.
+pub const PRESERVE_MARKER: &str = "%% libro: everything below this line is kept on re-export %%";
+
+/// Result of writing a vault to disk
+#[derive(Debug, Clone, Default)]
+pub struct VaultSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+}
+
+/// Export books as an Obsidian-style vault: `books/` holds one note per
+/// book and `authors/` one note per author, linked with wiki-links.
+/// Existing notes are matched by their `libro_id` frontmatter and updated in
+/// place, keeping anything below `PRESERVE_MARKER`.
+pub fn export_vault(books: &[ExtendedBook], dir: &Path) -> LibroResult<VaultSummary> {
+    let books_dir = dir.join("books");
+    let authors_dir = dir.join("authors");
+    fs::create_dir_all(&books_dir)?;
+    fs::create_dir_all(&authors_dir)?;
+
+    let note_names = assign_book_note_names(books, &books_dir)?;
+    let mut summary = VaultSummary::default();
+
+    // 도서 노트
+    for book in books {
+        let Some(id) = book.book.id else { continue };
+        let name = &note_names[&id];
+        let path = books_dir.join(format!("{}.md", name));
+        write_note(&path, &render_book_note(book), &mut summary)?;
+    }
+
+    // 작가 노트 (이름순으로 도서 목록 수집)
+    let mut author_books: BTreeMap<&str, Vec<&ExtendedBook>> = BTreeMap::new();
+    for book in books {
+        for author in &book.authors {
+            author_books.entry(&author.name).or_default().push(book);
+        }
+    }
+    for (author, books) in &author_books {
+        let path = authors_dir.join(format!("{}.md", sanitize_file_name(author)));
+        let content = render_author_note(author, books, &note_names);
+        write_note(&path, &content, &mut summary)?;
+    }
+
+    Ok(summary)
+}
+
+/// Render the generated part of a book note (frontmatter, metadata, reviews)
+pub fn render_book_note(book: &ExtendedBook) -> String {
+    let mut out = String::new();
+
+    out.push_str("---\n");
+    if let Some(id) = book.book.id {
+        out.push_str(&format!("libro_id: {}\n", id));
+    }
+    out.push_str(&format!("title: {}\n", yaml_string(&book.book.title)));
+    push_yaml_list(
+        &mut out,
+        "authors",
+        book.authors.iter().map(|a| a.name.as_str()),
+    );
+    push_yaml_list(
+        &mut out,
+        "translators",
+        book.translators.iter().map(|t| t.name.as_str()),
+    );
+    out.push_str(&format!("genre: {}\n", yaml_string(&book.book.genre)));
+    if let Some(pages) = book.book.pages {
+        out.push_str(&format!("pages: {}\n", pages));
+    }
+    if let Some(year) = book.book.pub_year {
+        out.push_str(&format!("pub_year: {}\n", year));
+    }
+    if !book.reviews.is_empty() {
+        let avg =
+            book.reviews.iter().map(|r| r.rating).sum::<i32>() as f64 / book.reviews.len() as f64;
+        out.push_str(&format!("rating: {}\n", format_rating(avg)));
+    }
+    if let Some(date) = book.reviews.iter().filter_map(|r| r.date_read).max() {
+        out.push_str(&format!("date_read: {}\n", format_iso_date(&date)));
+    }
+    out.push_str("---\n\n");
+
+    out.push_str(&format!("# {}\n\n", book.book.title));
+
+    let author_links: Vec<String> = book
+        .authors
+        .iter()
+        .map(|a| format!("[[{}]]", sanitize_file_name(&a.name)))
+        .collect();
+    out.push_str(&format!("**Authors:** {}\n", author_links.join(", ")));
+    if !book.translators.is_empty() {
+        let translators: Vec<&str> = book.translators.iter().map(|t| t.name.as_str()).collect();
+        out.push_str(&format!("**Translators:** {}\n", translators.join(", ")));
+    }
+    out.push_str(&format!("**Genre:** {}\n", book.book.genre));
+
+    // 리뷰는 읽은 날짜 순서로 (get_reviews는 최신순으로 반환)
+    let mut reviews: Vec<_> = book.reviews.iter().collect();
+    reviews.sort_by(|a, b| a.date_read.cmp(&b.date_read).then(a.id.cmp(&b.id)));
+
+    for review in reviews {
+        let date = review
+            .date_read
+            .map(|d| format_iso_date(&d))
+            .unwrap_or_else(|| "undated".to_string());
+        out.push_str(&format!(
+            "\n## Review — {} {}\n\n",
+            date,
+            stars(review.rating)
+        ));
+        out.push_str(review.review.trim_end());
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Render the generated part of an author note
+pub fn render_author_note(
+    author: &str,
+    books: &[&ExtendedBook],
+    note_names: &HashMap<i64, String>,
+) -> String {
+    let mut out = String::new();
+    out.push_str("---\n");
+    out.push_str(&format!("name: {}\n", yaml_string(author)));
+    out.push_str(&format!("books: {}\n", books.len()));
+    out.push_str("---\n\n");
+    out.push_str(&format!("# {}\n\n", author));
+
+    for book in books {
+        let link = book
+            .book
+            .id
+            .and_then(|id| note_names.get(&id))
+            .cloned()
+            .unwrap_or_else(|| sanitize_file_name(&book.book.title));
+        match book.book.pub_year {
+            Some(year) => out.push_str(&format!("- [[{}]] ({})\n", link, year)),
+            None => out.push_str(&format!("- [[{}]]\n", link)),
+        }
+    }
+
+    out
+}
+
+/// Merge freshly generated content with the preserved tail of an existing note
+pub fn merge_with_existing(generated: &str, existing: Option<&str>) -> String {
+    let preserved = match existing {
+        Some(existing) => match existing.find(PRESERVE_MARKER) {
+            Some(pos) => existing[pos + PRESERVE_MARKER.len()..]
+                .trim_start_matches('\n')
+                .to_string(),
+            // 마커가 없는 기존 파일은 사용자가 만든 것으로 보고 통째로 보존
+            None => existing.to_string(),
+        },
+        None => String::new(),
+    };
+
+    let mut out = generated.trim_end().to_string();
+    out.push_str("\n\n");
+    out.push_str(PRESERVE_MARKER);
+    out.push('\n');
+    if !preserved.is_empty() {
+        out.push('\n');
+        out.push_str(&preserved);
+    }
+    out
+}
+
+/// Make a string safe to use as a note file name and wiki-link target
+pub fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('.').trim();
+    if cleaned.is_empty() {
+        "untitled".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// Pick a stable note name for every book, reusing existing notes by `libro_id`
+fn assign_book_note_names(
+    books: &[ExtendedBook],
+    books_dir: &Path,
+) -> LibroResult<HashMap<i64, String>> {
+    let existing = scan_existing_book_notes(books_dir)?;
+    let mut names: HashMap<i64, String> = HashMap::new();
+    let mut taken: HashSet<String> = existing.values().cloned().collect();
+
+    for book in books {
+        let Some(id) = book.book.id else { continue };
+        if let Some(name) = existing.get(&id) {
+            names.insert(id, name.clone());
+        }
+    }
+
+    for book in books {
+        let Some(id) = book.book.id else { continue };
+        if names.contains_key(&id) {
+            continue;
+        }
+        let base = sanitize_file_name(&book.book.title);
+        let name = if taken.contains(&base) {
+            format!("{} ({})", base, id)
+        } else {
+            base
+        };
+        taken.insert(name.clone());
+        names.insert(id, name);
+    }
+
+    Ok(names)
+}
+
+/// Map `libro_id` frontmatter values to note names for notes already on disk
+fn scan_existing_book_notes(books_dir: &Path) -> LibroResult<HashMap<i64, String>> {
+    let mut found = HashMap::new();
+    for entry in fs::read_dir(books_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let content = fs::read_to_string(&path)?;
+        if let Some(id) = frontmatter_libro_id(&content) {
+            found.insert(id, stem.to_string());
+        }
+    }
+    Ok(found)
+}
+
+fn frontmatter_libro_id(content: &str) -> Option<i64> {
+    let mut lines = content.lines();
+    if lines.next()? != "---" {
+        return None;
+    }
+    lines
+        .take_while(|line| *line != "---")
+        .find_map(|line| line.strip_prefix("libro_id:"))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+fn write_note(path: &Path, generated: &str, summary: &mut VaultSummary) -> LibroResult<()> {
+    let existing = if path.exists() {
+        Some(fs::read_to_string(path)?)
+    } else {
+        None
+    };
+    let content = merge_with_existing(generated, existing.as_deref());
+
+    match existing {
+        Some(old) if old == content => summary.unchanged += 1,
+        Some(_) => {
+            fs::write(path, content)?;
+            summary.updated += 1;
+        }
+        None => {
+            fs::write(path, content)?;
+            summary.created += 1;
+        }
+    }
+    Ok(())
+}
+
+fn push_yaml_list<'a>(out: &mut String, key: &str, items: impl Iterator<Item = &'a str>) {
+    let items: Vec<&str> = items.collect();
+    if items.is_empty() {
+        out.push_str(&format!("{}: []\n", key));
+    } else {
+        out.push_str(&format!("{}:\n", key));
+        for item in items {
+            out.push_str(&format!("  - {}\n", yaml_string(item)));
+        }
+    }
+}
+
+/// Render a year in review as a standalone Markdown document
+pub fn render_year_in_review(wrapped: &YearInReview) -> String {
+    let totals = &wrapped.totals;
+    let mut out = format!("# {} in Books\n\n", wrapped.year);
+
+    if totals.total_books == 0 {
+        out.push_str(&format!("No books read in {}.\n", wrapped.year));
+        return out;
+    }
+
+    out.push_str(&format!("- **Books:** {}\n", totals.total_books));
+    out.push_str(&format!("- **Pages:** {}\n", totals.total_pages));
+    if let Some(average) = totals.average_rating {
+        out.push_str(&format!(
+            "- **Average rating:** {}/{}\n",
+            format_rating(average),
+            totals.rating_scale
+        ));
+    }
+    if let Some(share) = wrapped.translated_share {
+        out.push_str(&format!(
+            "- **Translated:** {} of {} ({:.0}%)\n",
+            totals.translated_books,
+            totals.total_books,
+            share * 100.0
+        ));
+    }
+    for (label, book) in [
+        ("First book", &wrapped.first_book),
+        ("Last book", &wrapped.last_book),
+    ] {
+        if let Some(book) = book {
+            out.push_str(&format!(
+                "- **{}:** {} — {} ({})\n",
+                label, book.title, book.authors, book.date_read
+            ));
+        }
+    }
+    for (label, book) in [
+        ("Longest", &wrapped.longest),
+        ("Shortest", &wrapped.shortest),
+    ] {
+        if let Some(book) = book {
+            out.push_str(&format!(
+                "- **{}:** {} ({} pages)\n",
+                label, book.title, book.pages
+            ));
+        }
+    }
+
+    out.push_str("\n## Top Rated\n\n");
+    for (rank, book) in wrapped.top_rated.iter().enumerate() {
+        out.push_str(&format!(
+            "{}. {} — {} {}\n",
+            rank + 1,
+            book.title,
+            book.authors,
+            stars(book.rating)
+        ));
+    }
+
+    out.push_str("\n## Most-Read Authors\n\n");
+    for (rank, author) in wrapped.authors.iter().enumerate() {
+        out.push_str(&format!(
+            "{}. {} ({} book{})\n",
+            rank + 1,
+            author.author,
+            author.books,
+            if author.books == 1 { "" } else { "s" }
+        ));
+    }
+
+    out.push_str("\n## Top Genres\n\n");
+    for (rank, genre) in wrapped.genres.iter().enumerate() {
+        out.push_str(&format!(
+            "{}. {} ({} book{})\n",
+            rank + 1,
+            genre.genre,
+            genre.books,
+            if genre.books == 1 { "" } else { "s" }
+        ));
+    }
+
+    let changes = wrapped.changes();
+    if !changes.is_empty() {
+        out.push_str(&format!("\n## Compared with {}\n\n", wrapped.year - 1));
+        out.push_str(&format!(
+            "| | {} | {} | Change |\n|---|---:|---:|---:|\n",
+            wrapped.year - 1,
+            wrapped.year
+        ));
+        for change in changes {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                change.label,
+                format_rating(change.previous),
+                format_rating(change.current),
+                format_change(change.delta())
+            ));
+        }
+    }
+
+    out
+}
+
+/// A signed difference such as `+4`, `-0.5` or `±0`
+pub fn format_change(delta: f64) -> String {
+    if delta == 0.0 {
+        "±0".to_string()
+    } else if delta > 0.0 {
+        format!("+{}", format_rating(delta))
+    } else {
+        format!("-{}", format_rating(-delta))
+    }
+}
+
+/// Quote a value as a YAML double-quoted scalar
+fn yaml_string(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{}\"", escaped)
+}
+
+/// A number without a trailing `.0`, else with one decimal
+pub fn format_rating(rating: f64) -> String {
+    if rating.fract() == 0.0 {
+        format!("{}", rating as i64)
+    } else {
+        format!("{:.1}", rating)
+    }
+}
+
+fn stars(rating: i32) -> String {
+    let max = max_rating();
+    let filled = rating.clamp(0, max);
+    format!(
+        "{}{}",
+        "★".repeat(filled as usize),
+        "☆".repeat((max - filled) as usize)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use libro_core::models::{Book, Review, Writer, WriterType};
+
+    fn sample_book() -> ExtendedBook {
+        ExtendedBook {
+            book: Book {
+                id: Some(7),
+                title: "Invisible Cities: \"Le città\"".to_string(),
+                pages: Some(165),
+                pub_year: Some(1972),
+                genre: "Fiction".to_string(),
+                created_at: None,
+                updated_at: None,
+                deleted_at: None,
+            },
+            authors: vec![Writer {
+                id: Some(1),
+                name: "Italo Calvino".to_string(),
+                writer_type: WriterType::Author,
+            }],
+            translators: vec![Writer {
+                id: Some(2),
+                name: "William Weaver".to_string(),
+                writer_type: WriterType::Translator,
+            }],
+            reviews: vec![
+                Review {
+                    id: Some(2),
+                    book_id: 7,
+                    date_read: Some(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()),
+                    rating: 4,
+                    review: "Second read.".to_string(),
+                    created_at: None,
+                    updated_at: None,
+                },
+                Review {
+                    id: Some(1),
+                    book_id: 7,
+                    date_read: Some(NaiveDate::from_ymd_opt(2020, 5, 9).unwrap()),
+                    rating: 5,
+                    review: "Dreamlike.".to_string(),
+                    created_at: None,
+                    updated_at: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_book_note_frontmatter_and_links() {
+        let note = render_book_note(&sample_book());
+        assert!(note.starts_with("---\nlibro_id: 7\n"));
+        assert!(note.contains("title: \"Invisible Cities: \\\"Le città\\\"\"\n"));
+        assert!(note.contains("authors:\n  - \"Italo Calvino\"\n"));
+        assert!(note.contains("translators:\n  - \"William Weaver\"\n"));
+        assert!(note.contains("rating: 4.5\n"));
+        assert!(note.contains("date_read: 2024-03-01\n"));
+        assert!(note.contains("**Authors:** [[Italo Calvino]]"));
+
+        // 오래된 리뷰가 먼저 나온다
+        let first = note.find("Dreamlike.").unwrap();
+        let second = note.find("Second read.").unwrap();
+        assert!(first < second);
+    }
+
+    #[test]
+    fn test_merge_keeps_user_section() {
+        let first = merge_with_existing("# Generated v1\n", None);
+        let edited = format!("{}\nMy own notes\n", first);
+
+        let second = merge_with_existing("# Generated v2\n", Some(&edited));
+        assert!(second.starts_with("# Generated v2"));
+        assert!(second.contains(PRESERVE_MARKER));
+        assert!(second.ends_with("My own notes\n"));
+        assert!(!second.contains("Generated v1"));
+    }
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("a/b: c?"), "a-b- c-");
+        assert_eq!(sanitize_file_name("  ..  "), "untitled");
+        assert_eq!(sanitize_file_name("채식주의자"), "채식주의자");
+    }
+
+    #[test]
+    fn test_export_vault_updates_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut book = sample_book();
+
+        let summary = export_vault(&[book.clone()], dir.path()).unwrap();
+        assert_eq!(summary.created, 2);
+
+        let note_path = dir
+            .path()
+            .join("books")
+            .join("Invisible Cities- -Le città-.md");
+        let mut content = fs::read_to_string(&note_path).unwrap();
+        content.push_str("\n## My thoughts\nKeep me\n");
+        fs::write(&note_path, content).unwrap();
+
+        // 제목이 바뀌어도 libro_id로 같은 노트를 찾아 갱신
+        book.book.title = "Invisible Cities".to_string();
+        let summary = export_vault(&[book], dir.path()).unwrap();
+        assert_eq!(summary.updated, 1);
+        assert_eq!(summary.unchanged, 1);
+        assert_eq!(summary.created, 0);
+
+        let content = fs::read_to_string(&note_path).unwrap();
+        assert!(content.contains("title: \"Invisible Cities\""));
+        assert!(content.ends_with("## My thoughts\nKeep me\n"));
+
+        let author =
+            fs::read_to_string(dir.path().join("authors").join("Italo Calvino.md")).unwrap();
+        assert!(author.contains("- [[Invisible Cities- -Le città-]] (1972)"));
+    }
+
+    #[test]
+    fn test_year_in_review_markdown() {
+        let mut other = sample_book();
+        other.book.id = Some(8);
+        other.book.title = "Cosmicomics".to_string();
+        other.book.pages = Some(153);
+        other.translators.clear();
+        other.reviews = vec![Review {
+            id: Some(3),
+            book_id: 8,
+            date_read: Some(NaiveDate::from_ymd_opt(2024, 7, 2).unwrap()),
+            rating: 5,
+            review: String::new(),
+            created_at: None,
+            updated_at: None,
+        }];
+        let books = [sample_book(), other];
+
+        let markdown = render_year_in_review(&libro_core::stats::year_in_review(&books, 2024));
+        assert!(markdown.starts_with("# 2024 in Books\n\n- **Books:** 2\n"));
+        assert!(markdown.contains("- **Translated:** 1 of 2 (50%)\n"));
+        assert!(markdown.contains(
+            "- **First book:** Invisible Cities: \"Le città\" — Italo Calvino (2024-03-01)\n"
+        ));
+        assert!(markdown.contains("- **Shortest:** Cosmicomics (153 pages)\n"));
+        assert!(markdown.contains("1. Cosmicomics — Italo Calvino ★★★★★\n"));
+        assert!(markdown.contains("1. Italo Calvino (2 books)\n"));
+        // 이전 해 기록이 없으면 비교 표를 생략
+        assert!(!markdown.contains("Compared with"));
+
+        let markdown = render_year_in_review(&libro_core::stats::year_in_review(&books, 2025));
+        assert!(markdown.contains("No books read in 2025."));
+        assert_eq!(format_change(-0.5), "-0.5");
+        assert_eq!(format_change(2.0), "+2");
+    }
+}
@@ -1,5 +1,11 @@
+pub mod config;
 pub mod database;
 pub mod date;
+pub mod editor;
 pub mod error_handler;
 pub mod input;
+pub mod library;
+pub mod markdown;
 pub mod output;
+pub mod site;
+pub mod svg;
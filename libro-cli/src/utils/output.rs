@@ -1,19 +1,184 @@
-use crate::lib::errors::LibroResult;
-use crate::lib::models::*;
 use crate::utils::date::{format_date, relative_date_description};
-use chrono::Datelike;
+use clap::ValueEnum;
 use console::style;
+use libro_core::errors::{validation::max_rating, LibroError, LibroResult};
+use libro_core::models::*;
+use libro_core::stats::LibraryStats;
+use serde::Serialize;
 use serde_json;
+use std::sync::OnceLock;
 use tabled::{Table, Tabled};
 
 /// Display format options
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
 pub enum OutputFormat {
+    /// Human-readable tables and text
+    #[default]
     Table,
+    /// JSON document
     Json,
+    /// Newline-delimited JSON, one record per line
+    Ndjson,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Tab-separated values with a header row
+    Tsv,
+    /// YAML document
+    Yaml,
+    /// Markdown vault (`export`) or document (`report --wrapped`)
+    Markdown,
+    /// One short entry per book
+    #[value(skip)]
     Summary,
 }
 
+impl OutputFormat {
+    /// Name as written on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::Summary => "summary",
+        }
+    }
+
+    /// Whether this format is meant for scripts rather than people
+    pub fn is_machine_readable(self) -> bool {
+        !matches!(self, OutputFormat::Table | OutputFormat::Summary)
+    }
+}
+
+/// Format chosen with `--format` for this run
+static FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// Set the output format for this run
+pub fn set_output_format(format: OutputFormat) {
+    let _ = FORMAT.set(format);
+}
+
+/// The output format for this run (`table` unless `--format` was given)
+pub fn output_format() -> OutputFormat {
+    FORMAT.get().copied().unwrap_or_default()
+}
+
+/// Whether this run should print data for scripts instead of text for people
+pub fn is_machine_readable() -> bool {
+    output_format().is_machine_readable()
+}
+
+/// Render records in a machine-readable format.
+///
+/// CSV and TSV need flat records; nested fields are reported as an error.
+pub fn render_records<T: Serialize>(records: &[T], format: OutputFormat) -> LibroResult<String> {
+    match format {
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(records)? + "\n"),
+        OutputFormat::Ndjson => {
+            let mut out = String::new();
+            for record in records {
+                out.push_str(&serde_json::to_string(record)?);
+                out.push('\n');
+            }
+            Ok(out)
+        }
+        OutputFormat::Yaml => serde_yaml::to_string(records).map_err(yaml_error),
+        OutputFormat::Csv => render_delimited(records, b','),
+        OutputFormat::Tsv => render_delimited(records, b'\t'),
+        OutputFormat::Table | OutputFormat::Summary | OutputFormat::Markdown => Err(
+            LibroError::internal(format!("{:?} is not a record format", format)),
+        ),
+    }
+}
+
+/// Render a single record; JSON and YAML print it as an object, not a list
+pub fn render_record<T: Serialize>(record: &T, format: OutputFormat) -> LibroResult<String> {
+    match format {
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(record)? + "\n"),
+        OutputFormat::Yaml => serde_yaml::to_string(record).map_err(yaml_error),
+        _ => render_records(std::slice::from_ref(record), format),
+    }
+}
+
+/// Print records in this run's output format
+pub fn print_records<T: Serialize>(records: &[T]) -> LibroResult<()> {
+    print!("{}", render_records(records, output_format())?);
+    Ok(())
+}
+
+/// Print a single record in this run's output format
+pub fn print_record<T: Serialize>(record: &T) -> LibroResult<()> {
+    print!("{}", render_record(record, output_format())?);
+    Ok(())
+}
+
+fn render_delimited<T: Serialize>(records: &[T], delimiter: u8) -> LibroResult<String> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(Vec::new());
+    for record in records {
+        writer
+            .serialize(record)
+            .map_err(|e| LibroError::internal(format!("Could not write CSV: {}", e)))?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| LibroError::internal(format!("Could not write CSV: {}", e)))?;
+    String::from_utf8(bytes).map_err(|e| LibroError::internal(e.to_string()))
+}
+
+fn yaml_error(error: serde_yaml::Error) -> LibroError {
+    LibroError::internal(format!("Could not write YAML: {}", error))
+}
+
+/// A book flattened to one row, for CSV and TSV
+#[derive(Debug, Clone, Serialize)]
+pub struct BookRecord {
+    pub id: Option<i64>,
+    pub title: String,
+    pub authors: String,
+    pub translators: String,
+    pub genre: String,
+    pub pages: Option<i32>,
+    pub pub_year: Option<i32>,
+    pub reviews: usize,
+    pub average_rating: Option<f64>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl From<&ExtendedBook> for BookRecord {
+    fn from(book: &ExtendedBook) -> Self {
+        let names = |writers: &[Writer]| {
+            writers
+                .iter()
+                .map(|w| w.name.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        };
+        let average_rating = (!book.reviews.is_empty()).then(|| {
+            book.reviews.iter().map(|r| r.rating).sum::<i32>() as f64 / book.reviews.len() as f64
+        });
+
+        BookRecord {
+            id: book.book.id,
+            title: book.book.title.clone(),
+            authors: names(&book.authors),
+            translators: names(&book.translators),
+            genre: book.book.genre.clone(),
+            pages: book.book.pages,
+            pub_year: book.book.pub_year,
+            reviews: book.reviews.len(),
+            average_rating,
+            created_at: book.book.created_at.clone(),
+            updated_at: book.book.updated_at.clone(),
+        }
+    }
+}
+
 /// Trait for objects that can be displayed in different formats
 pub trait Displayable {
     fn display(&self, format: &OutputFormat) -> LibroResult<String>;
@@ -26,6 +191,10 @@ impl Displayable for ExtendedBook {
             OutputFormat::Json => Ok(serde_json::to_string_pretty(self)?),
             OutputFormat::Table => Ok(format_book_table(self)),
             OutputFormat::Summary => Ok(format_book_summary(self)),
+            OutputFormat::Csv | OutputFormat::Tsv => {
+                render_record(&BookRecord::from(self), *format)
+            }
+            _ => render_record(self, *format),
         }
     }
 }
@@ -37,6 +206,11 @@ impl Displayable for Vec<ExtendedBook> {
             OutputFormat::Json => Ok(serde_json::to_string_pretty(self)?),
             OutputFormat::Table => Ok(format_books_table(self)),
             OutputFormat::Summary => Ok(format_books_summary(self)),
+            OutputFormat::Csv | OutputFormat::Tsv => {
+                let rows: Vec<BookRecord> = self.iter().map(BookRecord::from).collect();
+                render_records(&rows, *format)
+            }
+            _ => render_records(self, *format),
         }
     }
 }
@@ -75,15 +249,29 @@ fn format_book_table(book: &ExtendedBook) -> String {
 
     output.push_str(&format!("Genre: {}\n", &book.book.genre));
 
+    if let Some(created_at) = &book.book.created_at {
+        output.push_str(&format!("Added: {}\n", created_at));
+    }
+
+    if let Some(updated_at) = book
+        .book
+        .updated_at
+        .as_ref()
+        .filter(|u| book.book.created_at.as_ref() != Some(*u))
+    {
+        output.push_str(&format!("Modified: {}\n", updated_at));
+    }
+
     // Reviews with detailed display
     if !book.reviews.is_empty() {
         let avg_rating =
             book.reviews.iter().map(|r| r.rating).sum::<i32>() as f32 / book.reviews.len() as f32;
         output.push('\n');
         output.push_str(&format!(
-            "⭐ Reviews ({}) - Average: {:.1}/5\n",
+            "⭐ Reviews ({}) - Average: {:.1}/{}\n",
             book.reviews.len(),
-            avg_rating
+            avg_rating,
+            max_rating()
         ));
         output.push_str(&"═".repeat(50));
         output.push('\n');
@@ -181,7 +369,7 @@ fn format_book_summary(book: &ExtendedBook) -> String {
     if !book.reviews.is_empty() {
         let avg_rating =
             book.reviews.iter().map(|r| r.rating).sum::<i32>() as f32 / book.reviews.len() as f32;
-        summary.push_str(&format!(" - ⭐ {:.1}/5", avg_rating));
+        summary.push_str(&format!(" - ⭐ {:.1}/{}", avg_rating, max_rating()));
     }
 
     summary
@@ -194,7 +382,7 @@ fn format_review_summary(review: &Review) -> String {
 
     // Rating stars
     let stars = "⭐".repeat(review.rating as usize);
-    output.push_str(&format!("{} {}/5", stars, review.rating));
+    output.push_str(&format!("{} {}/{}", stars, review.rating, max_rating()));
 
     // Date
     if let Some(date) = review.date_read {
@@ -220,7 +408,12 @@ fn format_review_detailed(review: &Review) -> String {
 
     // Rating stars
     let stars = "⭐".repeat(review.rating as usize);
-    output.push_str(&format!("{} {}/5", style(&stars).yellow(), review.rating));
+    output.push_str(&format!(
+        "{} {}/{}",
+        style(&stars).yellow(),
+        review.rating,
+        max_rating()
+    ));
 
     // Date
     if let Some(date) = review.date_read {
@@ -253,7 +446,7 @@ fn truncate_string(s: &str, max_len: usize) -> String {
 }
 
 /// Format reading statistics
-pub fn format_reading_stats(books: &[ExtendedBook]) -> String {
+pub fn format_reading_stats(stats: &LibraryStats) -> String {
     let mut output = String::new();
 
     output.push_str(&format!(
@@ -264,89 +457,46 @@ pub fn format_reading_stats(books: &[ExtendedBook]) -> String {
     output.push('\n');
 
     // Basic stats
-    let total_books = books.len();
-    let total_pages: i32 = books.iter().filter_map(|b| b.book.pages).sum();
-    let total_reviews = books.iter().map(|b| b.reviews.len()).sum::<usize>();
-
-    output.push_str(&format!("Total Books: {}\n", total_books));
-    output.push_str(&format!("Total Pages: {}\n", total_pages));
-    output.push_str(&format!("Total Reviews: {}\n", total_reviews));
-
-    if total_reviews > 0 {
-        let total_rating: i32 = books
-            .iter()
-            .flat_map(|b| &b.reviews)
-            .map(|r| r.rating)
-            .sum();
-        let avg_rating = total_rating as f32 / total_reviews as f32;
-        output.push_str(&format!("Average Rating: {:.1}/5\n", avg_rating));
-    }
-
-    // Year breakdown by reading dates
-    let mut year_counts = std::collections::HashMap::new();
-    for book in books {
-        for review in &book.reviews {
-            if let Some(date_read) = review.date_read {
-                let read_year = date_read.year();
-                *year_counts.entry(read_year).or_insert(0) += 1;
-            }
-        }
-    }
-
-    if !year_counts.is_empty() {
-        output.push('\n');
-        output.push_str("Books by Year:\n");
-        let mut years: Vec<_> = year_counts.iter().collect();
-        years.sort_by_key(|(year, _)| *year);
-        for (year, count) in years {
-            output.push_str(&format!("  {}: {} book(s)\n", year, count));
-        }
-    }
-
-    output
-}
-
-/// Format author statistics
-pub fn format_author_stats(books: &[ExtendedBook]) -> String {
-    let mut output = String::new();
-
+    let totals = &stats.totals;
+    output.push_str(&format!("Total Books: {}\n", totals.total_books));
+    output.push_str(&format!("Total Authors: {}\n", totals.total_authors));
+    output.push_str(&format!("Total Pages: {}\n", totals.total_pages));
+    output.push_str(&format!("Total Reviews: {}\n", totals.total_reviews));
     output.push_str(&format!(
-        "👥 {}\n",
-        style("Author Statistics").bold().blue()
+        "Translated: {} of {} book(s)\n",
+        totals.translated_books, totals.total_books
     ));
-    output.push_str(&"═".repeat(40));
-    output.push('\n');
 
-    // Count books per author
-    let mut author_counts = std::collections::HashMap::new();
-    for book in books {
-        for author in &book.authors {
-            *author_counts.entry(author.name.clone()).or_insert(0) += 1;
-        }
+    if let Some(average) = totals.average_rating {
+        output.push_str(&format!(
+            "Average Rating: {:.1}/{}\n",
+            average, totals.rating_scale
+        ));
     }
 
-    if author_counts.is_empty() {
-        output.push_str("No authors found.\n");
-        return output;
+    if let Some(average) = stats.pages.average_pages {
+        output.push_str(&format!("Average Length: {:.0} pages\n", average));
     }
-
-    // Sort by count (descending)
-    let mut authors: Vec<_> = author_counts.iter().collect();
-    authors.sort_by(|a, b| b.1.cmp(a.1));
-
-    output.push_str(&format!("Total Authors: {}\n\n", authors.len()));
-    output.push_str("Most Read Authors:\n");
-
-    for (i, (author, count)) in authors.iter().take(10).enumerate() {
+    if let (Some(longest), Some(shortest)) = (&stats.pages.longest, &stats.pages.shortest) {
+        output.push_str(&format!(
+            "Longest: {} ({} pages)\n",
+            longest.title, longest.pages
+        ));
         output.push_str(&format!(
-            "{}. {} ({} book{})\n",
-            i + 1,
-            author,
-            count,
-            if **count == 1 { "" } else { "s" }
+            "Shortest: {} ({} pages)\n",
+            shortest.title, shortest.pages
         ));
     }
 
+    // Year breakdown by reading dates
+    if !stats.years.is_empty() {
+        output.push('\n');
+        output.push_str("Books by Year:\n");
+        for count in &stats.years {
+            output.push_str(&format!("  {}: {} book(s)\n", count.year, count.books));
+        }
+    }
+
     output
 }
 
@@ -363,6 +513,9 @@ mod tests {
                 pages: Some(200),
                 pub_year: Some(2023),
                 genre: "Fiction".to_string(),
+                created_at: None,
+                updated_at: None,
+                deleted_at: None,
             },
             authors: vec![Writer {
                 id: Some(1),
@@ -376,6 +529,8 @@ mod tests {
                 date_read: Some(NaiveDate::from_ymd_opt(2023, 12, 1).unwrap()),
                 rating: 4,
                 review: "Great book!".to_string(),
+                created_at: None,
+                updated_at: None,
             }],
         }
     }
@@ -396,6 +551,57 @@ mod tests {
         assert!(result.unwrap().contains("Test Book"));
     }
 
+    #[test]
+    fn test_render_records() {
+        let rows = vec![BookRecord::from(&create_test_book())];
+
+        let csv = render_records(&rows, OutputFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "id,title,authors,translators,genre,pages,pub_year,reviews,average_rating,created_at,updated_at"
+            )
+        );
+        assert_eq!(
+            lines.next(),
+            Some("1,Test Book,Test Author,,Fiction,200,2023,1,4.0,,")
+        );
+
+        let tsv = render_records(&rows, OutputFormat::Tsv).unwrap();
+        assert!(tsv.starts_with("id\ttitle\tauthors"));
+
+        let ndjson = render_records(&rows, OutputFormat::Ndjson).unwrap();
+        assert_eq!(ndjson.lines().count(), 1);
+        let value: serde_json::Value = serde_json::from_str(ndjson.trim()).unwrap();
+        assert_eq!(value["title"], "Test Book");
+
+        let yaml = render_records(&rows, OutputFormat::Yaml).unwrap();
+        assert!(yaml.starts_with("- id: 1"));
+
+        let empty: Vec<BookRecord> = Vec::new();
+        assert_eq!(render_records(&empty, OutputFormat::Json).unwrap(), "[]\n");
+        assert!(render_records(&rows, OutputFormat::Table).is_err());
+    }
+
+    #[test]
+    fn test_render_record_is_an_object() {
+        let record = BookRecord::from(&create_test_book());
+        let json = render_record(&record, OutputFormat::Json).unwrap();
+        assert!(json.starts_with('{'));
+        let yaml = render_record(&record, OutputFormat::Yaml).unwrap();
+        assert!(yaml.starts_with("id: 1"));
+    }
+
+    #[test]
+    fn test_format_reading_stats() {
+        let stats = LibraryStats::from_books(&[create_test_book()]);
+        let output = format_reading_stats(&stats);
+        assert!(output.contains("Total Books: 1"));
+        assert!(output.contains("Total Authors: 1"));
+        assert!(output.contains("Longest: Test Book (200 pages)"));
+    }
+
     #[test]
     fn test_truncate_string() {
         assert_eq!(truncate_string("short", 10), "short");
@@ -0,0 +1,506 @@
+use crate::utils::date::format_iso_date;
+use chrono::Datelike;
+use libro_core::errors::{validation::max_rating, LibroResult};
+use libro_core::models::{ExtendedBook, Writer};
+use libro_core::stats::LibraryStats;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories that only ever contain generated pages
+const GENERATED_DIRS: [&str; 3] = ["books", "authors", "years"];
+
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', 'Noto Sans KR', sans-serif; max-width: 52rem; margin: 2rem auto; padding: 0 1rem; color: #222; line-height: 1.5; }
+header nav a { margin-right: 1rem; }
+a { color: #2b5797; text-decoration: none; }
+a:hover { text-decoration: underline; }
+h1 { border-bottom: 2px solid #ddd; padding-bottom: .3rem; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: .3rem .5rem; border-bottom: 1px solid #eee; }
+.muted { color: #777; }
+.stars { color: #d4a017; }
+.review { border-left: 3px solid #ddd; padding-left: 1rem; margin: 1rem 0; white-space: pre-wrap; }
+.bar { background: #5b8def; height: 1rem; display: inline-block; vertical-align: middle; }
+";
+
+/// Result of generating a site
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SiteSummary {
+    pub pages: usize,
+    pub removed: usize,
+}
+
+/// A rendered page: path relative to the site root and its HTML
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub path: PathBuf,
+    pub html: String,
+}
+
+/// Render every page of the site. Output depends only on `books`, so
+/// rebuilding an unchanged library yields byte-identical files.
+pub fn render_site(books: &[ExtendedBook]) -> Vec<Page> {
+    let stats = LibraryStats::from_books(books);
+    let mut books: Vec<&ExtendedBook> = books.iter().collect();
+    books.sort_by(|a, b| {
+        a.book
+            .title
+            .cmp(&b.book.title)
+            .then(a.book.id.cmp(&b.book.id))
+    });
+
+    let mut pages = vec![
+        Page {
+            path: PathBuf::from("index.html"),
+            html: render_index(&books),
+        },
+        Page {
+            path: PathBuf::from("stats.html"),
+            html: render_stats(&books, &stats),
+        },
+    ];
+
+    for book in &books {
+        if let Some(id) = book.book.id {
+            pages.push(Page {
+                path: PathBuf::from(format!("books/{}.html", id)),
+                html: render_book(book),
+            });
+        }
+    }
+
+    for (author, author_books) in group_by_author(&books) {
+        if let Some(id) = author.id {
+            pages.push(Page {
+                path: PathBuf::from(format!("authors/{}.html", id)),
+                html: render_author(&author, &author_books),
+            });
+        }
+    }
+
+    for (year, year_books) in group_by_read_year(&books) {
+        pages.push(Page {
+            path: PathBuf::from(format!("years/{}.html", year)),
+            html: render_year(year, &year_books),
+        });
+    }
+
+    pages
+}
+
+/// Write the site to `dir`, removing stale pages left by earlier builds
+pub fn generate_site(books: &[ExtendedBook], dir: &Path) -> LibroResult<SiteSummary> {
+    let pages = render_site(books);
+    let mut summary = SiteSummary::default();
+
+    let generated: BTreeSet<PathBuf> = pages.iter().map(|p| dir.join(&p.path)).collect();
+    for sub in GENERATED_DIRS {
+        let sub_dir = dir.join(sub);
+        fs::create_dir_all(&sub_dir)?;
+        for entry in fs::read_dir(&sub_dir)? {
+            let path = entry?.path();
+            let is_html = path.extension().and_then(|e| e.to_str()) == Some("html");
+            if is_html && !generated.contains(&path) {
+                fs::remove_file(&path)?;
+                summary.removed += 1;
+            }
+        }
+    }
+
+    for page in &pages {
+        fs::write(dir.join(&page.path), &page.html)?;
+        summary.pages += 1;
+    }
+
+    Ok(summary)
+}
+
+/// Escape text for use in HTML element content and attribute values
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn layout(title: &str, root: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"ko\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title} · Libro</title>\n<style>{style}</style>\n</head>\n<body>\n\
+         <header><nav><a href=\"{root}index.html\">📚 Books</a>\
+         <a href=\"{root}stats.html\">📊 Statistics</a></nav></header>\n\
+         <main>\n{body}</main>\n</body>\n</html>\n",
+        title = escape_html(title),
+        style = STYLE,
+        root = root,
+        body = body
+    )
+}
+
+fn render_index(books: &[&ExtendedBook]) -> String {
+    let mut body = format!(
+        "<h1>Reading Log</h1>\n<p class=\"muted\">{} book(s)</p>\n",
+        books.len()
+    );
+    body.push_str(&book_table(books, ""));
+
+    let years: BTreeSet<i32> = group_by_read_year(books).keys().copied().collect();
+    if !years.is_empty() {
+        body.push_str("<h2>By Year Read</h2>\n<p>");
+        let links: Vec<String> = years
+            .iter()
+            .rev()
+            .map(|y| format!("<a href=\"years/{0}.html\">{0}</a>", y))
+            .collect();
+        body.push_str(&links.join(" · "));
+        body.push_str("</p>\n");
+    }
+
+    layout("Reading Log", "", &body)
+}
+
+fn render_book(book: &ExtendedBook) -> String {
+    let mut body = format!("<h1>{}</h1>\n<table>\n", escape_html(&book.book.title));
+
+    body.push_str(&format!(
+        "<tr><th>Authors</th><td>{}</td></tr>\n",
+        writer_links(&book.authors, "../")
+    ));
+    if !book.translators.is_empty() {
+        let names: Vec<String> = book
+            .translators
+            .iter()
+            .map(|t| escape_html(&t.name))
+            .collect();
+        body.push_str(&format!(
+            "<tr><th>Translators</th><td>{}</td></tr>\n",
+            names.join(", ")
+        ));
+    }
+    body.push_str(&format!(
+        "<tr><th>Genre</th><td>{}</td></tr>\n",
+        escape_html(&book.book.genre)
+    ));
+    if let Some(pages) = book.book.pages {
+        body.push_str(&format!("<tr><th>Pages</th><td>{}</td></tr>\n", pages));
+    }
+    if let Some(year) = book.book.pub_year {
+        body.push_str(&format!("<tr><th>Published</th><td>{}</td></tr>\n", year));
+    }
+    if let Some(avg) = average_rating(book) {
+        body.push_str(&format!(
+            "<tr><th>Rating</th><td>{:.1}/{}</td></tr>\n",
+            avg,
+            max_rating()
+        ));
+    }
+    body.push_str("</table>\n");
+
+    let mut reviews: Vec<_> = book.reviews.iter().collect();
+    reviews.sort_by(|a, b| a.date_read.cmp(&b.date_read).then(a.id.cmp(&b.id)));
+
+    body.push_str(&format!("<h2>Reviews ({})</h2>\n", reviews.len()));
+    for review in reviews {
+        let date = review
+            .date_read
+            .map(|d| format_iso_date(&d))
+            .unwrap_or_else(|| "undated".to_string());
+        body.push_str(&format!(
+            "<p><span class=\"stars\">{}</span> {}/{} <span class=\"muted\">· {}</span></p>\n\
+             <div class=\"review\">{}</div>\n",
+            "★".repeat(review.rating.clamp(0, max_rating()) as usize),
+            review.rating,
+            max_rating(),
+            date,
+            escape_html(review.review.trim())
+        ));
+    }
+
+    layout(&book.book.title, "../", &body)
+}
+
+fn render_author(author: &Writer, books: &[&ExtendedBook]) -> String {
+    let mut body = format!(
+        "<h1>{}</h1>\n<p class=\"muted\">{} book(s)</p>\n",
+        escape_html(&author.name),
+        books.len()
+    );
+    body.push_str(&book_table(books, "../"));
+    layout(&author.name, "../", &body)
+}
+
+fn render_year(year: i32, books: &[&ExtendedBook]) -> String {
+    let pages: i32 = books.iter().filter_map(|b| b.book.pages).sum();
+    let mut body = format!(
+        "<h1>Read in {}</h1>\n<p class=\"muted\">{} book(s) · {} pages</p>\n",
+        year,
+        books.len(),
+        pages
+    );
+    body.push_str(&book_table(books, "../"));
+    layout(&format!("Read in {}", year), "../", &body)
+}
+
+fn render_stats(books: &[&ExtendedBook], stats: &LibraryStats) -> String {
+    let totals = &stats.totals;
+    let mut body = String::from("<h1>Reading Statistics</h1>\n<table>\n");
+    body.push_str(&format!(
+        "<tr><th>Total Books</th><td>{}</td></tr>\n",
+        totals.total_books
+    ));
+    body.push_str(&format!(
+        "<tr><th>Total Pages</th><td>{}</td></tr>\n",
+        totals.total_pages
+    ));
+    body.push_str(&format!(
+        "<tr><th>Total Reviews</th><td>{}</td></tr>\n",
+        totals.total_reviews
+    ));
+    if let Some(avg) = totals.average_rating {
+        body.push_str(&format!(
+            "<tr><th>Average Rating</th><td>{:.1}/{}</td></tr>\n",
+            avg, totals.rating_scale
+        ));
+    }
+    body.push_str("</table>\n");
+
+    // 읽은 날짜 기준 연도별 도서 수 (CLI 리포트와 같은 기준)
+    if !stats.years.is_empty() {
+        let max = stats.years.iter().map(|c| c.books).max().unwrap_or(1);
+        body.push_str("<h2>Books by Year</h2>\n<table>\n");
+        for count in &stats.years {
+            body.push_str(&format!(
+                "<tr><td><a href=\"years/{0}.html\">{0}</a></td>\
+                 <td><span class=\"bar\" style=\"width: {1}%\"></span> {2}</td></tr>\n",
+                count.year,
+                count.books * 100 / max,
+                count.books
+            ));
+        }
+        body.push_str("</table>\n");
+    }
+
+    let mut authors: Vec<(Writer, Vec<&ExtendedBook>)> =
+        group_by_author(books).into_iter().collect();
+    authors.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.name.cmp(&b.0.name)));
+    if !authors.is_empty() {
+        body.push_str("<h2>Most Read Authors</h2>\n<ol>\n");
+        for (author, author_books) in authors.iter().take(10) {
+            body.push_str(&format!(
+                "<li>{} <span class=\"muted\">({} book{})</span></li>\n",
+                writer_links(std::slice::from_ref(author), ""),
+                author_books.len(),
+                if author_books.len() == 1 { "" } else { "s" }
+            ));
+        }
+        body.push_str("</ol>\n");
+    }
+
+    layout("Reading Statistics", "", &body)
+}
+
+fn book_table(books: &[&ExtendedBook], root: &str) -> String {
+    let mut out = String::from(
+        "<table>\n<tr><th>Title</th><th>Authors</th><th>Year</th><th>Rating</th></tr>\n",
+    );
+    for book in books {
+        let Some(id) = book.book.id else { continue };
+        out.push_str(&format!(
+            "<tr><td><a href=\"{root}books/{id}.html\">{title}</a></td><td>{authors}</td>\
+             <td>{year}</td><td>{rating}</td></tr>\n",
+            root = root,
+            id = id,
+            title = escape_html(&book.book.title),
+            authors = writer_links(&book.authors, root),
+            year = book
+                .book
+                .pub_year
+                .map(|y| y.to_string())
+                .unwrap_or_default(),
+            rating = average_rating(book)
+                .map(|r| format!("{:.1}", r))
+                .unwrap_or_default()
+        ));
+    }
+    out.push_str("</table>\n");
+    out
+}
+
+fn writer_links(writers: &[Writer], root: &str) -> String {
+    writers
+        .iter()
+        .map(|w| match w.id {
+            Some(id) => format!(
+                "<a href=\"{}authors/{}.html\">{}</a>",
+                root,
+                id,
+                escape_html(&w.name)
+            ),
+            None => escape_html(&w.name),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn average_rating(book: &ExtendedBook) -> Option<f32> {
+    if book.reviews.is_empty() {
+        None
+    } else {
+        Some(book.reviews.iter().map(|r| r.rating).sum::<i32>() as f32 / book.reviews.len() as f32)
+    }
+}
+
+/// Group books by author ID, keeping the input (title) order within each group
+fn group_by_author<'a>(books: &[&'a ExtendedBook]) -> Vec<(Writer, Vec<&'a ExtendedBook>)> {
+    let mut groups: BTreeMap<i64, (Writer, Vec<&ExtendedBook>)> = BTreeMap::new();
+    for book in books {
+        for author in &book.authors {
+            if let Some(id) = author.id {
+                groups
+                    .entry(id)
+                    .or_insert_with(|| (author.clone(), Vec::new()))
+                    .1
+                    .push(book);
+            }
+        }
+    }
+    groups.into_values().collect()
+}
+
+/// Group books by the years they were read in (a book read twice appears in both)
+fn group_by_read_year<'a>(books: &[&'a ExtendedBook]) -> BTreeMap<i32, Vec<&'a ExtendedBook>> {
+    let mut groups: BTreeMap<i32, Vec<&ExtendedBook>> = BTreeMap::new();
+    for book in books {
+        let years: BTreeSet<i32> = book
+            .reviews
+            .iter()
+            .filter_map(|r| r.date_read.map(|d| d.year()))
+            .collect();
+        for year in years {
+            groups.entry(year).or_default().push(book);
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use libro_core::models::{Book, Review, WriterType};
+
+    fn sample_books() -> Vec<ExtendedBook> {
+        let author = Writer {
+            id: Some(3),
+            name: "Ted <Chiang>".to_string(),
+            writer_type: WriterType::Author,
+        };
+        vec![
+            ExtendedBook {
+                book: Book {
+                    id: Some(2),
+                    title: "Stories of Your Life".to_string(),
+                    pages: Some(281),
+                    pub_year: Some(2002),
+                    genre: "SF".to_string(),
+                    created_at: None,
+                    updated_at: None,
+                    deleted_at: None,
+                },
+                authors: vec![author.clone()],
+                translators: vec![],
+                reviews: vec![Review {
+                    id: Some(1),
+                    book_id: 2,
+                    date_read: Some(NaiveDate::from_ymd_opt(2023, 6, 1).unwrap()),
+                    rating: 5,
+                    review: "Heptapods & <linguistics>".to_string(),
+                    created_at: None,
+                    updated_at: None,
+                }],
+            },
+            ExtendedBook {
+                book: Book {
+                    id: Some(1),
+                    title: "Exhalation".to_string(),
+                    pages: None,
+                    pub_year: Some(2019),
+                    genre: "SF".to_string(),
+                    created_at: None,
+                    updated_at: None,
+                    deleted_at: None,
+                },
+                authors: vec![author],
+                translators: vec![],
+                reviews: vec![],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_site_pages() {
+        let pages = render_site(&sample_books());
+        let paths: Vec<String> = pages
+            .iter()
+            .map(|p| p.path.to_string_lossy().replace('\\', "/"))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "index.html",
+                "stats.html",
+                "books/1.html",
+                "books/2.html",
+                "authors/3.html",
+                "years/2023.html"
+            ]
+        );
+
+        let book = &pages[3].html;
+        assert!(book.contains("Heptapods &amp; &lt;linguistics&gt;"));
+        assert!(book.contains("<a href=\"../authors/3.html\">Ted &lt;Chiang&gt;</a>"));
+        assert!(!book.contains("http"));
+    }
+
+    #[test]
+    fn test_render_site_is_deterministic() {
+        let mut reversed = sample_books();
+        reversed.reverse();
+
+        let first: Vec<String> = render_site(&sample_books())
+            .into_iter()
+            .map(|p| p.html)
+            .collect();
+        let second: Vec<String> = render_site(&reversed).into_iter().map(|p| p.html).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_generate_site_removes_stale_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("books")).unwrap();
+        fs::write(dir.path().join("books/99.html"), "old").unwrap();
+
+        let summary = generate_site(&sample_books(), dir.path()).unwrap();
+        assert_eq!(summary.pages, 6);
+        assert_eq!(summary.removed, 1);
+        assert!(dir.path().join("books/2.html").exists());
+        assert!(!dir.path().join("books/99.html").exists());
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(escape_html("a<b>&\"'"), "a&lt;b&gt;&amp;&quot;&#39;");
+    }
+}
@@ -0,0 +1,284 @@
+//! Standalone SVG chart rendering for reports.
+//!
+//! Output is plain SVG 1.1 with no scripts, fonts or external references, and
+//! every coordinate is an integer so the same data always produces the same
+//! bytes.
+
+const WIDTH: u32 = 640;
+const TITLE_HEIGHT: u32 = 48;
+const BAR_HEIGHT: u32 = 22;
+const BAR_GAP: u32 = 8;
+const LABEL_WIDTH: u32 = 180;
+const VALUE_WIDTH: u32 = 60;
+const LINE_HEIGHT: u32 = 360;
+const PLOT_MARGIN: u32 = 50;
+const MAX_LABEL_CHARS: usize = 24;
+const BAR_COLOR: &str = "#5b8def";
+const LINE_COLOR: &str = "#e4572e";
+
+/// A labelled series of values to chart
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartData {
+    pub title: String,
+    pub points: Vec<(String, f64)>,
+}
+
+impl ChartData {
+    pub fn new(title: impl Into<String>, points: Vec<(String, f64)>) -> Self {
+        ChartData {
+            title: title.into(),
+            points,
+        }
+    }
+}
+
+/// Render a horizontal bar chart, one row per point
+pub fn render_bar_chart(data: &ChartData) -> String {
+    let rows = data.points.len().max(1) as u32;
+    let height = TITLE_HEIGHT + rows * (BAR_HEIGHT + BAR_GAP) + BAR_GAP;
+    let plot_width = WIDTH - LABEL_WIDTH - VALUE_WIDTH;
+    let max = max_value(data);
+
+    let mut out = svg_open(WIDTH, height, &data.title);
+
+    if data.points.is_empty() {
+        out.push_str(&empty_message(TITLE_HEIGHT + BAR_HEIGHT));
+    }
+
+    for (i, (label, value)) in data.points.iter().enumerate() {
+        let y = TITLE_HEIGHT + i as u32 * (BAR_HEIGHT + BAR_GAP);
+        let bar_width = scale(*value, max, plot_width);
+        let text_y = y + BAR_HEIGHT / 2 + 4;
+
+        out.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",
+            LABEL_WIDTH - 8,
+            text_y,
+            escape_xml(&truncate_label(label))
+        ));
+        out.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+            LABEL_WIDTH, y, bar_width, BAR_HEIGHT, BAR_COLOR
+        ));
+        out.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\">{}</text>\n",
+            LABEL_WIDTH + bar_width + 6,
+            text_y,
+            format_value(*value)
+        ));
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Render a line chart with evenly spaced points along the x axis
+pub fn render_line_chart(data: &ChartData) -> String {
+    let left = PLOT_MARGIN;
+    let right = WIDTH - PLOT_MARGIN / 2;
+    let top = TITLE_HEIGHT;
+    let bottom = LINE_HEIGHT - PLOT_MARGIN;
+    let plot_height = bottom - top;
+    let max = max_value(data);
+
+    let mut out = svg_open(WIDTH, LINE_HEIGHT, &data.title);
+
+    // 축
+    out.push_str(&format!(
+        "  <line x1=\"{l}\" y1=\"{t}\" x2=\"{l}\" y2=\"{b}\" stroke=\"#999999\"/>\n\
+         \x20 <line x1=\"{l}\" y1=\"{b}\" x2=\"{r}\" y2=\"{b}\" stroke=\"#999999\"/>\n",
+        l = left,
+        t = top,
+        b = bottom,
+        r = right
+    ));
+
+    // y축 눈금: 0, 최댓값의 절반, 최댓값
+    for tick in [0.0, max / 2.0, max] {
+        let y = bottom - scale(tick, max, plot_height);
+        out.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" text-anchor=\"end\" fill=\"#666666\">{}</text>\n",
+            left - 6,
+            y + 4,
+            format_value(tick)
+        ));
+    }
+
+    if data.points.is_empty() {
+        out.push_str(&empty_message(top + plot_height / 2));
+        out.push_str("</svg>\n");
+        return out;
+    }
+
+    let step = if data.points.len() > 1 {
+        (right - left - 20) / (data.points.len() as u32 - 1)
+    } else {
+        0
+    };
+    let coords: Vec<(u32, u32)> = data
+        .points
+        .iter()
+        .enumerate()
+        .map(|(i, (_, value))| {
+            let x = left + 10 + i as u32 * step;
+            let y = bottom - scale(*value, max, plot_height);
+            (x, y)
+        })
+        .collect();
+
+    let polyline: Vec<String> = coords.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+    out.push_str(&format!(
+        "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+        polyline.join(" "),
+        LINE_COLOR
+    ));
+
+    for ((x, y), (label, value)) in coords.iter().zip(&data.points) {
+        out.push_str(&format!(
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"4\" fill=\"{}\"/>\n",
+            x, y, LINE_COLOR
+        ));
+        out.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+            x,
+            y.saturating_sub(10),
+            format_value(*value)
+        ));
+        out.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#666666\">{}</text>\n",
+            x,
+            bottom + 18,
+            escape_xml(&truncate_label(label))
+        ));
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Escape text for SVG/XML content and attribute values
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn svg_open(width: u32, height: u32, title: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">\n\
+         \x20 <title>{t}</title>\n\
+         \x20 <rect width=\"{w}\" height=\"{h}\" fill=\"#ffffff\"/>\n\
+         \x20 <text x=\"{cx}\" y=\"30\" text-anchor=\"middle\" font-size=\"16\" \
+         font-weight=\"bold\">{t}</text>\n",
+        w = width,
+        h = height,
+        cx = width / 2,
+        t = escape_xml(title)
+    )
+}
+
+fn empty_message(y: u32) -> String {
+    format!(
+        "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#999999\">No data</text>\n",
+        WIDTH / 2,
+        y
+    )
+}
+
+fn max_value(data: &ChartData) -> f64 {
+    data.points
+        .iter()
+        .map(|(_, v)| *v)
+        .fold(0.0, f64::max)
+        .max(1.0)
+}
+
+fn scale(value: f64, max: f64, extent: u32) -> u32 {
+    ((value.max(0.0) / max) * extent as f64).round() as u32
+}
+
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+fn truncate_label(label: &str) -> String {
+    if label.chars().count() <= MAX_LABEL_CHARS {
+        label.to_string()
+    } else {
+        let truncated: String = label.chars().take(MAX_LABEL_CHARS - 1).collect();
+        format!("{}…", truncated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Compare against a checked-in golden file.
+    /// Run with `UPDATE_GOLDEN=1` to rewrite the files after intentional changes.
+    fn assert_golden(name: &str, actual: &str) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("golden")
+            .join(name);
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&path, actual).unwrap();
+            return;
+        }
+
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("missing golden file {}", path.display()));
+        assert_eq!(actual, expected, "SVG output differs from {}", name);
+    }
+
+    fn years() -> ChartData {
+        ChartData::new(
+            "Books Read per Year",
+            vec![
+                ("2021".to_string(), 3.0),
+                ("2022".to_string(), 7.0),
+                ("2023".to_string(), 5.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_line_chart_golden() {
+        assert_golden("years.svg", &render_line_chart(&years()));
+    }
+
+    #[test]
+    fn test_bar_chart_golden() {
+        let authors = ChartData::new(
+            "Top Authors",
+            vec![
+                ("Ursula K. Le Guin".to_string(), 4.0),
+                ("한강".to_string(), 2.0),
+                ("Tom & Jerry <Collected Works of Everyone>".to_string(), 1.0),
+            ],
+        );
+        assert_golden("authors.svg", &render_bar_chart(&authors));
+    }
+
+    #[test]
+    fn test_empty_charts_golden() {
+        let empty = ChartData::new("Rating Distribution", vec![]);
+        assert_golden("empty_bar.svg", &render_bar_chart(&empty));
+        assert_golden("empty_line.svg", &render_line_chart(&empty));
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("<a & 'b'>"), "&lt;a &amp; &apos;b&apos;&gt;");
+    }
+}
@@ -1,9 +1,23 @@
 use assert_cmd::Command;
 use predicates::prelude::*;
+use std::path::Path;
+
+/// A `libro-cli` command kept away from the developer's own setup: the
+/// database, config and data directories all default to places under `home`
+fn libro_cli(home: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("libro-cli").unwrap();
+    cmd.env("LIBRO_DB_PATH", home.join("libro.db"))
+        .env("XDG_CONFIG_HOME", home.join("config"))
+        .env("XDG_DATA_HOME", home.join("data"))
+        .env_remove("LIBRO_CONFIG")
+        .env_remove("LIBRO_BACKUP_ON_EXIT");
+    cmd
+}
 
 #[test]
 fn test_help_message() {
-    let mut cmd = Command::cargo_bin("libro-cli").unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = libro_cli(dir.path());
     cmd.arg("--help");
     cmd.assert().success().stdout(predicate::str::contains(
         "A command-line book tracking tool",
@@ -12,19 +26,19 @@ fn test_help_message() {
 
 #[test]
 fn test_add_command() {
-    let mut cmd = Command::cargo_bin("libro-cli").unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = libro_cli(dir.path());
     cmd.arg("add");
     cmd.assert()
         .failure() // Will fail due to no terminal for interactive input
         .stderr(predicate::str::contains("IO Error"));
 }
 
-
-
 #[test]
 fn test_browse_command() {
-    let mut cmd = Command::cargo_bin("libro-cli").unwrap();
-    cmd.args(&["browse"]);
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["browse"]);
     cmd.assert()
         .success()
         .stdout(predicate::str::contains("No books found"));
@@ -32,8 +46,9 @@ fn test_browse_command() {
 
 #[test]
 fn test_browse_with_query() {
-    let mut cmd = Command::cargo_bin("libro-cli").unwrap();
-    cmd.args(&["browse", "test"]);
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["browse", "test"]);
     cmd.assert()
         .success()
         .stdout(predicate::str::contains("No books found matching"));
@@ -41,8 +56,9 @@ fn test_browse_with_query() {
 
 #[test]
 fn test_report_books() {
-    let mut cmd = Command::cargo_bin("libro-cli").unwrap();
-    cmd.args(&["report", "--books"]);
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["report", "--books"]);
     cmd.assert()
         .success()
         .stdout(predicate::str::contains("No books found"));
@@ -50,8 +66,9 @@ fn test_report_books() {
 
 #[test]
 fn test_report_reviews() {
-    let mut cmd = Command::cargo_bin("libro-cli").unwrap();
-    cmd.args(&["report", "--reviews"]);
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["report", "--reviews"]);
     cmd.assert()
         .success()
         .stdout(predicate::str::contains("No books found"));
@@ -59,19 +76,19 @@ fn test_report_reviews() {
 
 #[test]
 fn test_report_authors() {
-    let mut cmd = Command::cargo_bin("libro-cli").unwrap();
-    cmd.args(&["report", "--authors"]);
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["report", "--authors"]);
     cmd.assert()
         .success()
         .stdout(predicate::str::contains("No books found"));
 }
 
-
-
 #[test]
 fn test_report_with_years_flag() {
-    let mut cmd = Command::cargo_bin("libro-cli").unwrap();
-    cmd.args(&["report", "--years"]);
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["report", "--years"]);
     cmd.assert()
         .success()
         .stdout(predicate::str::contains("No books found"));
@@ -79,20 +96,1126 @@ fn test_report_with_years_flag() {
 
 #[test]
 fn test_review_with_id() {
-    let mut cmd = Command::cargo_bin("libro-cli").unwrap();
-    cmd.args(&["review", "5"]);
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["review", "5"]);
     cmd.assert()
         .failure()
         .stderr(predicate::str::contains("Book with ID 5 not found"));
 }
 
-
-
 #[test]
 fn test_invalid_command() {
-    let mut cmd = Command::cargo_bin("libro-cli").unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = libro_cli(dir.path());
     cmd.arg("invalid");
     cmd.assert()
         .failure()
         .stderr(predicate::str::contains("error"));
 }
+
+#[test]
+fn test_export_import_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir.path().join("library.json");
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", dir.path().join("source.db"))
+        .args(["export", "--format", "json", "--out"])
+        .arg(&archive);
+    cmd.assert().success();
+
+    let contents = std::fs::read_to_string(&archive).unwrap();
+    assert!(contents.contains("\"format\": \"libro-archive\""));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", dir.path().join("target.db"))
+        .arg("import")
+        .arg(&archive);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 0 book(s)"));
+}
+
+#[test]
+fn test_import_rejects_foreign_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("foreign.json");
+    std::fs::write(
+        &file,
+        r#"{"format":"other","version":1,"books":[],"writers":[],"book_writers":[],"reviews":[]}"#,
+    )
+    .unwrap();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", dir.path().join("target.db"))
+        .arg("import")
+        .arg(&file);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Not a libro archive"));
+}
+
+#[test]
+fn test_backup_and_restore() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+    let backups = dir.path().join("backups");
+
+    // Create the database first
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db).arg("browse");
+    cmd.assert().success();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["backup", "--keep", "3"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Backed up"));
+
+    let backup = std::fs::read_dir(&backups)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .arg("restore")
+        .arg(&backup)
+        .arg("--yes");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "schema version {}",
+            libro_core::db::SCHEMA_VERSION
+        )));
+    assert!(dir.path().join("libro.db.pre-restore").exists());
+}
+
+#[test]
+fn test_restore_rejects_non_database() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("notes.db");
+    std::fs::write(&file, "not a database").unwrap();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", dir.path().join("libro.db"))
+        .arg("restore")
+        .arg(&file)
+        .arg("--yes");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("not a readable SQLite database"));
+}
+
+#[test]
+fn test_db_diff_and_merge() {
+    let dir = tempfile::tempdir().unwrap();
+    let laptop = dir.path().join("laptop.db");
+    let desktop = dir.path().join("desktop.db");
+    let archive = dir.path().join("book.json");
+    std::fs::write(
+        &archive,
+        r#"{"format":"libro-archive","version":1,
+            "books":[{"id":1,"title":"Exhalation","pages":350,"pub_year":2019,"genre":"SF"}],
+            "writers":[{"id":1,"name":"Ted Chiang","writer_type":"Author"}],
+            "book_writers":[{"book_id":1,"writer_id":1,"writer_type":"Author"}],
+            "reviews":[{"id":1,"book_id":1,"date_read":"2023-05-01","rating":5,"review":"Superb"}]}"#,
+    )
+    .unwrap();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &desktop)
+        .arg("import")
+        .arg(&archive);
+    cmd.assert().success();
+
+    // Create an empty laptop database
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &laptop).arg("browse");
+    cmd.assert().success();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["db", "diff"]).arg(&laptop).arg(&desktop);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Exhalation — Ted Chiang"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &laptop)
+        .args(["db", "merge", "--from"])
+        .arg(&desktop);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("1 book(s) added"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["db", "diff"]).arg(&laptop).arg(&desktop);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("same books"));
+}
+
+#[test]
+fn test_add_with_flags_prints_ids() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", dir.path().join("libro.db"))
+        .args(["add", "--title", "The Vegetarian", "--author", "Han Kang"])
+        .args(["--translator", "Deborah Smith", "--genre", "Fiction"])
+        .args(["--pages", "188", "--year", "2007"])
+        .args([
+            "--rating",
+            "5",
+            "--date",
+            "2023-04-01",
+            "--review",
+            "Unsettling",
+        ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::diff("{\"book_id\":1,\"review_id\":1}\n"));
+}
+
+#[test]
+fn test_add_from_json_stdin() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", dir.path().join("libro.db"))
+        .args(["add", "--from-json", "-"])
+        .write_stdin(r#"{"book":{"title":"Exhalation","authors":["Ted Chiang"],"genre":"SF"}}"#);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::diff("{\"book_id\":1,\"review_id\":null}\n"));
+}
+
+#[test]
+fn test_add_with_flags_validates_input() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", dir.path().join("libro.db")).args([
+        "add",
+        "--title",
+        "No Author",
+        "--genre",
+        "Fiction",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("At least one author is required"));
+
+    let add = |extra: &[&str]| {
+        let mut cmd = libro_cli(dir.path());
+        cmd.args(["add", "--title", "Q", "--author", "Z", "--genre", "SF"])
+            .args(extra);
+        cmd
+    };
+    add(&["--author", "", "--format", "json"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains(r#""field":"authors""#));
+    add(&["--translator", " ", "--format", "json"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains(r#""field":"translators""#));
+
+    // A rating and review text only make sense together
+    add(&["--rating", "4"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--review <REVIEW>"));
+    add(&["--review", "Good"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--rating <RATING>"));
+
+    // None of the rejected books were saved
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["browse", "--format", "json"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"Q\"").not());
+}
+
+#[test]
+fn test_delete_book_dry_run_and_yes() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["add", "--title", "Exhalation", "--author", "Ted Chiang"])
+        .args(["--genre", "SF", "--rating", "5", "--review", "Superb"]);
+    cmd.assert().success();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["delete", "book", "1", "--dry-run"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Reviews: 1"))
+        .stdout(predicate::str::contains(
+            "Writers only on this book: Ted Chiang",
+        ))
+        .stdout(predicate::str::contains("nothing was deleted"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["delete", "book", "1", "--yes"]);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Moved book #1 with 1 review(s) to the trash",
+    ));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["delete", "book", "1", "--yes"]);
+    cmd.assert().code(4);
+}
+
+#[test]
+fn test_delete_review_not_found() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", dir.path().join("libro.db"))
+        .args(["delete", "review", "42", "--yes"]);
+    cmd.assert()
+        .code(4)
+        .stderr(predicate::str::contains("Review with ID 42 not found"));
+}
+
+#[test]
+fn test_review_with_flags() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["add", "--title", "Exhalation", "--author", "Ted Chiang"])
+        .args(["--genre", "SF"]);
+    cmd.assert().success();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["review", "1", "--rating", "4", "--date", "2024-01-02"])
+        .args(["--file", "-"])
+        .write_stdin("First paragraph.\n\nSecond paragraph.\n");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Review ID: 1"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["review", "1", "--edit", "1", "--rating", "5"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Review updated"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db).args(["browse", "--json"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"rating\": 5"))
+        .stdout(predicate::str::contains("Second paragraph."));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_review_with_editor() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+
+    // A fake editor that overwrites the template with a finished review
+    let editor = dir.path().join("editor.sh");
+    std::fs::write(
+        &editor,
+        "#!/bin/sh\nprintf -- '---\\nrating: 3\\ndate_read: 2023-07-01\\n---\\nWritten in an editor\\n' > \"$1\"\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&editor, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["add", "--title", "Exhalation", "--author", "Ted Chiang"])
+        .args(["--genre", "SF"]);
+    cmd.assert().success();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .env_remove("VISUAL")
+        .env("EDITOR", &editor)
+        .args(["review", "1"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Review added successfully"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db).args(["browse", "--json"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Written in an editor"));
+}
+
+#[test]
+fn test_config_set_get_and_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("libro").join("config.toml");
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_CONFIG", &config).args(["config", "path"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("config.toml"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_CONFIG", &config)
+        .args(["config", "set", "defaults.rating_scale", "10"]);
+    cmd.assert().success();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_CONFIG", &config)
+        .args(["config", "get", "defaults.rating_scale"]);
+    cmd.assert().success().stdout(predicate::str::diff("10\n"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_CONFIG", &config)
+        .args(["config", "set", "ui.theme", "neon"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown theme"));
+}
+
+#[test]
+fn test_config_db_path_and_precedence() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.toml");
+    let configured = dir.path().join("configured.db");
+    let flagged = dir.path().join("flagged.db");
+    std::fs::write(
+        &config,
+        format!(
+            "[database]\npath = {:?}\n\n[defaults]\nrating_scale = 10\n",
+            configured.to_str().unwrap()
+        ),
+    )
+    .unwrap();
+
+    // The config file's database is used when LIBRO_DB_PATH is unset
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_CONFIG", &config)
+        .env_remove("LIBRO_DB_PATH")
+        .args(["add", "--title", "Exhalation", "--author", "Ted Chiang"])
+        .args(["--genre", "SF", "--rating", "8", "--review", "Great"]);
+    cmd.assert().success();
+    assert!(configured.exists());
+
+    // --db wins over both the environment and the config file
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_CONFIG", &config)
+        .env("LIBRO_DB_PATH", dir.path().join("env.db"))
+        .args(["--db", flagged.to_str().unwrap(), "browse"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("No books found"));
+    assert!(flagged.exists());
+    assert!(!dir.path().join("env.db").exists());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_named_libraries() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.toml");
+    let libro = |args: &[&str]| {
+        let mut cmd = libro_cli(dir.path());
+        cmd.env("LIBRO_CONFIG", &config)
+            .env("XDG_DATA_HOME", dir.path())
+            .env_remove("LIBRO_DB_PATH")
+            .args(args);
+        cmd
+    };
+
+    libro(&["library", "create", "club", "--switch"])
+        .assert()
+        .success();
+    libro(&[
+        "add",
+        "--title",
+        "Exhalation",
+        "--author",
+        "Ted Chiang",
+        "--genre",
+        "SF",
+    ])
+    .assert()
+    .success();
+    assert!(dir.path().join("libro/libraries/club.db").exists());
+
+    libro(&["library", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("* club"));
+    libro(&["--library", "default", "browse"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No books found"));
+    libro(&["--library", "nope", "browse"]).assert().failure();
+
+    // The active library can't be removed
+    libro(&["library", "remove", "club", "--yes"])
+        .assert()
+        .failure();
+    libro(&["library", "switch", "default"]).assert().success();
+    libro(&["library", "remove", "club", "--yes"])
+        .assert()
+        .success();
+    assert!(!dir.path().join("libro/libraries/club.db").exists());
+}
+
+#[test]
+fn test_machine_readable_formats() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+
+    for (title, author) in [("Exhalation", "Ted Chiang"), ("Stories", "Ted Chiang")] {
+        let mut cmd = libro_cli(dir.path());
+        cmd.env("LIBRO_DB_PATH", &db)
+            .args(["add", "--title", title, "--author", author])
+            .args(["--genre", "SF", "--rating", "4", "--review", "Good"]);
+        cmd.assert().success();
+    }
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["report", "--authors", "--format", "csv"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::diff("author,books\nTed Chiang,2\n"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["browse", "--format", "ndjson"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let lines: Vec<&str> = std::str::from_utf8(&output).unwrap().lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with('{'));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["report", "--format", "yaml"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("total_books: 2"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["delete", "book", "1", "--yes", "--format", "json"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"deleted\": true"))
+        .stderr(predicate::str::contains("Moved book #1"));
+}
+
+#[test]
+fn test_json_errors_on_stderr() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", dir.path().join("libro.db"))
+        .args(["delete", "review", "42", "--yes", "--format", "json"]);
+    cmd.assert()
+        .code(4)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            r#"{"error":{"code":"review_not_found""#,
+        ));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", dir.path().join("libro.db"))
+        .args(["browse", "--format", "markdown"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("only supported by export"));
+}
+
+#[test]
+fn test_exit_statuses() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+    let run = |args: &[&str]| {
+        let mut cmd = libro_cli(dir.path());
+        cmd.env("LIBRO_DB_PATH", &db).args(args);
+        cmd
+    };
+
+    // 2: usage errors, from clap or from our own argument checks
+    run(&["no-such-command"]).assert().code(2);
+    run(&["export", "--format", "csv"]).assert().code(2);
+
+    // 3: validation, with the field and constraint in JSON errors
+    run(&["add", "--title", "T", "--author", "A", "--genre", "SF"])
+        .args(["--rating", "9", "--review", "x", "--format", "json"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains(r#""field":"rating""#))
+        .stderr(predicate::str::contains(r#""constraint":"range:1-5""#));
+    run(&["add", "--title", "T", "--author", "A", "--genre", "SF"])
+        .args(["--rating", "4", "--review", "x", "--date", "2023-13-45"])
+        .assert()
+        .code(3);
+
+    // 4: not found
+    run(&["review", "99", "--rating", "4", "--text", "x"])
+        .assert()
+        .code(4);
+
+    // 7: file system errors
+    run(&["import", "missing.json"]).assert().code(7);
+
+    // 130: cancelled, here by leaving the review empty in the editor
+    run(&["add", "--title", "T", "--author", "A", "--genre", "SF"])
+        .assert()
+        .success();
+    run(&["review", "1"])
+        .env_remove("VISUAL")
+        .env("EDITOR", "true")
+        .assert()
+        .code(130)
+        .stderr(predicate::str::contains("Operation cancelled"));
+
+    // 8: malformed JSON input
+    run(&["add", "--from-json", "-"])
+        .write_stdin("not json")
+        .assert()
+        .code(8);
+
+    // 5: not a database
+    let text = dir.path().join("notes.txt");
+    std::fs::write(&text, "definitely not sqlite ".repeat(100)).unwrap();
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &text).arg("browse");
+    cmd.assert()
+        .code(5)
+        .stderr(predicate::str::contains("Database Error"));
+
+    // 6: another process holds the database
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    conn.execute_batch("BEGIN EXCLUSIVE").unwrap();
+    run(&["browse", "--format", "json"])
+        .assert()
+        .code(6)
+        .stderr(predicate::str::contains(r#""code":"database_locked""#));
+}
+
+#[test]
+fn test_completions_and_man_pages() {
+    let dir = tempfile::tempdir().unwrap();
+    for shell in ["bash", "zsh", "fish"] {
+        let mut cmd = libro_cli(dir.path());
+        cmd.args(["completions", shell]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("libro-cli __complete"));
+    }
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["completions", "zsh"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let script = String::from_utf8(output).unwrap();
+    assert_eq!(script.matches("compdef _libro-cli libro-cli").count(), 1);
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.arg("man");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(".TH libro-cli 1"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.args(["man", "--out"]).arg(dir.path());
+    cmd.assert().success();
+    assert!(dir.path().join("libro-cli-review.1").is_file());
+    assert!(!dir.path().join("libro-cli-__complete.1").exists());
+}
+
+#[test]
+fn test_dynamic_completion_candidates() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+
+    // Completing against a missing database lists nothing and creates nothing
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db).args(["__complete", "books"]);
+    cmd.assert().success().stdout(predicate::str::is_empty());
+    assert!(!db.exists());
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["add", "--title", "Solaris", "--author", "Stanisław Lem"])
+        .args(["--translator", "Bill Johnston", "--genre", "SF"]);
+    cmd.assert().success();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db).args(["__complete", "books"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::diff("1\tSolaris\n"));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["__complete", "writers"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::diff("Bill Johnston\nStanisław Lem\n"));
+}
+
+#[test]
+fn test_doctor_reports_and_fixes() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+
+    for (title, genre) in [("Solaris", "SF"), ("Dune", "SF"), ("Hyperion", "sf ")] {
+        let mut cmd = libro_cli(dir.path());
+        cmd.env("LIBRO_DB_PATH", &db).args([
+            "add", "--title", title, "--author", "Someone", "--genre", genre,
+        ]);
+        cmd.assert().success();
+    }
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    conn.execute_batch(
+        "INSERT INTO writers (name, type) VALUES ('Ghost', 'author');
+         INSERT INTO reviews (book_id, rating, review) VALUES (1, NULL, 'Unrated');",
+    )
+    .unwrap();
+    drop(conn);
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db).arg("doctor");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Writer #2 'Ghost' has no books"))
+        .stdout(predicate::str::contains("'sf ' should be 'SF'"))
+        .stdout(predicate::str::contains(
+            "Review #1 of 'Solaris' has no rating",
+        ));
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &db)
+        .args(["doctor", "--fix", "--format", "csv"]);
+    cmd.assert().success().stdout(predicate::str::diff(
+        "category,message,fixable\ninvalid_ratings,Review #1 of 'Solaris' has no rating,false\n",
+    ));
+}
+
+#[test]
+fn test_convert_to_files_and_back() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+    let config = dir.path().join("config.toml");
+    let libro = |args: &[&str]| {
+        let mut cmd = libro_cli(dir.path());
+        cmd.env("LIBRO_DB_PATH", &db)
+            .env("LIBRO_CONFIG", &config)
+            .args(args);
+        cmd
+    };
+
+    libro(&[
+        "add",
+        "--title",
+        "Solaris",
+        "--author",
+        "Stanisław Lem",
+        "--genre",
+        "SF",
+        "--rating",
+        "5",
+        "--review",
+        "Ocean",
+    ])
+    .assert()
+    .success();
+
+    libro(&["convert", "--to", "files"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "config set database.backend files",
+        ));
+    let book = dir.path().join("libro/books/0001-solaris.toml");
+    assert!(std::fs::read_to_string(book)
+        .unwrap()
+        .contains("Stanisław Lem"));
+
+    // Converting again would overwrite the copy
+    libro(&["convert", "--to", "files"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    libro(&["config", "set", "database.backend", "files"])
+        .assert()
+        .success();
+    libro(&[
+        "add",
+        "--title",
+        "Dune",
+        "--author",
+        "Frank Herbert",
+        "--genre",
+        "SF",
+    ])
+    .assert()
+    .success();
+    libro(&["backup"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs the sqlite backend"));
+
+    let back = dir.path().join("back.db");
+    libro(&["convert", "--to", "sqlite", "--out", back.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let mut cmd = libro_cli(dir.path());
+    cmd.env("LIBRO_DB_PATH", &back)
+        .args(["browse", "--format", "csv"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Solaris"))
+        .stdout(predicate::str::contains("Dune"));
+}
+
+#[test]
+fn test_history_and_undo() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+    let libro = |args: &[&str]| {
+        let mut cmd = libro_cli(dir.path());
+        cmd.env("LIBRO_DB_PATH", &db).args(args);
+        cmd
+    };
+
+    libro(&[
+        "add", "--title", "Solaris", "--author", "Lem", "--genre", "SF",
+    ])
+    .assert()
+    .success();
+    libro(&["delete", "book", "1", "--yes"]).assert().success();
+
+    let output = libro(&["history", "--book", "1", "--format", "json"])
+        .output()
+        .unwrap();
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(rows[0]["operation"], "delete book");
+    assert_eq!(rows[0]["books"], "#1 Solaris");
+    assert_eq!(rows[1]["operation"], "add book");
+
+    libro(&["undo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Undid #2 delete book #1 Solaris"));
+    libro(&["browse", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Solaris"));
+
+    libro(&["undo", "5"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Only 1 change(s) were left"));
+    libro(&["undo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing to undo"));
+    libro(&["history"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(undone)"));
+}
+
+#[test]
+fn test_trash_list_restore_and_purge() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+    let libro = |args: &[&str]| {
+        let mut cmd = libro_cli(dir.path());
+        cmd.env("LIBRO_DB_PATH", &db).args(args);
+        cmd
+    };
+
+    libro(&[
+        "add", "--title", "Solaris", "--author", "Lem", "--genre", "SF", "--rating", "4",
+        "--review", "Ocean",
+    ])
+    .assert()
+    .success();
+    libro(&["trash", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("The trash is empty"));
+    libro(&["delete", "book", "1", "--yes"]).assert().success();
+
+    let output = libro(&["trash", "list", "--format", "json"])
+        .output()
+        .unwrap();
+    let items: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(items[0]["kind"], "book");
+    assert_eq!(items[0]["title"], "Solaris");
+    assert_eq!(items[0]["reviews"], 1);
+
+    libro(&["trash", "restore", "book", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Restored book #1"));
+    libro(&["browse", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Solaris"));
+
+    libro(&["delete", "book", "1", "--yes"]).assert().success();
+    libro(&["trash", "purge", "--older-than", "2w", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing in the trash to purge"));
+    libro(&["trash", "purge", "--older-than", "soon", "--yes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not an age like 30d"));
+    libro(&["trash", "purge", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Purged 1 item(s)"));
+    libro(&["trash", "restore", "book", "1"]).assert().code(4);
+}
+
+#[test]
+fn test_sort_and_filter_by_date_added() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+    let libro = |args: &[&str]| {
+        let mut cmd = libro_cli(dir.path());
+        cmd.env("LIBRO_DB_PATH", &db).args(args);
+        cmd
+    };
+
+    for title in ["Dune", "Solaris"] {
+        libro(&[
+            "add", "--title", title, "--author", "Someone", "--genre", "SF",
+        ])
+        .assert()
+        .success();
+    }
+
+    let output = libro(&["browse", "--sort", "added", "--json"])
+        .output()
+        .unwrap();
+    let books: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(books[0]["title"], "Solaris");
+    assert!(books[0]["created_at"].is_string());
+    assert!(books[0]["updated_at"].is_string());
+
+    let output = libro(&["browse", "--added-since", "2999-01-01", "--json"])
+        .output()
+        .unwrap();
+    let books: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(books, serde_json::json!([]));
+
+    libro(&["report", "--books", "--sort", "modified"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Recently Modified 2 Books"));
+    libro(&["report", "--books", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("created_at,updated_at"));
+    libro(&["browse", "--modified-since", "someday"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_report_genres_and_pages() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+    let libro = |args: &[&str]| {
+        let mut cmd = libro_cli(dir.path());
+        cmd.env("LIBRO_DB_PATH", &db).args(args);
+        cmd
+    };
+
+    libro(&[
+        "add", "--title", "Dune", "--author", "Herbert", "--genre", "SF", "--pages", "600",
+        "--rating", "5", "--review", "Spice",
+    ])
+    .assert()
+    .success();
+    libro(&[
+        "add",
+        "--title",
+        "Ficciones",
+        "--author",
+        "Borges",
+        "--translator",
+        "Kerrigan",
+        "--genre",
+        "Fiction",
+        "--pages",
+        "150",
+    ])
+    .assert()
+    .success();
+
+    let output = libro(&["report", "--genres", "--format", "json"])
+        .output()
+        .unwrap();
+    let genres: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(genres[0]["genre"], "Fiction");
+    assert_eq!(genres[1]["pages"], 600);
+    assert_eq!(genres[1]["average_rating"], 5.0);
+
+    libro(&["report", "--genres"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Translated: 1 • Original: 1"));
+    libro(&["report", "--pages"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("short (1-199)"))
+        .stdout(predicate::str::contains("Longest: Dune (600 pages)"));
+    libro(&["report", "--pages", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("long,400+,1"));
+}
+
+#[test]
+fn test_report_pace() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+    let libro = |args: &[&str]| {
+        let mut cmd = libro_cli(dir.path());
+        cmd.env("LIBRO_DB_PATH", &db).args(args);
+        cmd
+    };
+
+    libro(&["report", "--pace"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No books found"));
+    for (title, date) in [("Dune", "2024-01-02"), ("Solaris", "2024-01-09")] {
+        libro(&[
+            "add", "--title", title, "--author", "Someone", "--genre", "SF", "--pages", "300",
+            "--rating", "4", "--review", "Good", "--date", date,
+        ])
+        .assert()
+        .success();
+    }
+
+    libro(&["report", "--pace"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Average: 7.0 days between finishes",
+        ))
+        .stdout(predicate::str::contains("Longest streak: 2 weeks"));
+
+    // Machine output lists the same newest --limit periods as the text
+    let output = libro(&["report", "--pace", "--format", "json"])
+        .output()
+        .unwrap();
+    let pace: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(pace["monthly"].as_array().unwrap().len(), 10);
+    assert_eq!(pace["weekly"].as_array().unwrap().len(), 10);
+
+    let output = libro(&["report", "--pace", "--limit", "1000", "--format", "json"])
+        .output()
+        .unwrap();
+    let pace: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(pace["finished"], 2);
+    assert_eq!(pace["pages"], 600);
+    assert_eq!(pace["monthly"][0]["period"], "2024-01");
+    assert_eq!(pace["monthly"][0]["books"], 2);
+    assert_eq!(pace["weekly"][1]["period"], "2024-W02");
+
+    for format in ["csv", "tsv"] {
+        libro(&["--format", format, "report", "--pace"])
+            .assert()
+            .code(2)
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::str::contains(format!(
+                "--format {} is not supported by report --pace",
+                format
+            )));
+    }
+}
+
+#[test]
+fn test_report_wrapped() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("libro.db");
+    let libro = |args: &[&str]| {
+        let mut cmd = libro_cli(dir.path());
+        cmd.env("LIBRO_DB_PATH", &db).args(args);
+        cmd
+    };
+
+    for (title, pages, rating, date) in [
+        ("Dune", "600", "5", "2025-03-02"),
+        ("Solaris", "200", "4", "2025-08-09"),
+        ("Kindred", "260", "3", "2024-05-01"),
+    ] {
+        libro(&[
+            "add", "--title", title, "--author", "Someone", "--genre", "SF", "--pages", pages,
+            "--rating", rating, "--review", "Good", "--date", date,
+        ])
+        .assert()
+        .success();
+    }
+
+    libro(&["report", "--wrapped", "2025"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2025 in Books"))
+        .stdout(predicate::str::contains("🌱 First: Dune (2025-03-02)"))
+        .stdout(predicate::str::contains("Compared with 2024"));
+
+    libro(&["report", "--wrapped", "2025", "--format", "markdown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("# 2025 in Books\n"))
+        .stdout(predicate::str::contains("| Books | 1 | 2 | +1 |"));
+
+    let output = libro(&["report", "--wrapped", "2025", "--format", "json"])
+        .output()
+        .unwrap();
+    let wrapped: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(wrapped["totals"]["total_books"], 2);
+    assert_eq!(wrapped["totals"]["total_pages"], 800);
+    assert_eq!(wrapped["top_rated"][0]["title"], "Dune");
+    assert_eq!(wrapped["longest"]["title"], "Dune");
+    assert_eq!(wrapped["last_book"]["title"], "Solaris");
+    assert_eq!(wrapped["previous_year"]["total_books"], 1);
+
+    for format in ["csv", "tsv"] {
+        libro(&["--format", format, "report", "--wrapped", "2025"])
+            .assert()
+            .code(2)
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::str::contains(format!(
+                "--format {} is not supported by report --wrapped",
+                format
+            )));
+    }
+
+    libro(&["report", "--wrapped", "2025", "--year", "2024"])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,372 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use crate::errors::{LibroError, LibroResult};
+use crate::models::{Book, BookWriter, Writer};
+
+/// Identifier written into every archive so foreign JSON is rejected early
+pub const ARCHIVE_FORMAT: &str = "libro-archive";
+
+/// Current archive format version
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Raw review row as stored in the `reviews` table.
+///
+/// Unlike `Review`, every nullable column stays optional and `date_read` is
+/// kept as the stored text so that an export never loses information.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewRecord {
+    pub id: i64,
+    pub book_id: i64,
+    pub date_read: Option<String>,
+    pub rating: Option<i32>,
+    pub review: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    /// When the review was moved to the trash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<String>,
+}
+
+/// Versioned, table-by-table snapshot of a libro database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Archive {
+    pub format: String,
+    pub version: u32,
+    pub books: Vec<Book>,
+    pub writers: Vec<Writer>,
+    pub book_writers: Vec<BookWriter>,
+    pub reviews: Vec<ReviewRecord>,
+}
+
+/// Summary of an archive import
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportSummary {
+    pub books: usize,
+    pub writers_created: usize,
+    pub writers_reused: usize,
+    pub reviews: usize,
+}
+
+/// Supported serialized archive encodings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveEncoding {
+    /// A single pretty-printed JSON document
+    Json,
+    /// One JSON record per line, starting with a header record
+    Ndjson,
+}
+
+/// One line of an NDJSON archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ArchiveRecord {
+    Header { format: String, version: u32 },
+    Book(Book),
+    Writer(Writer),
+    BookWriter(BookWriter),
+    Review(ReviewRecord),
+}
+
+impl Archive {
+    /// Create an empty archive with the current format header
+    pub fn new() -> Self {
+        Archive {
+            format: ARCHIVE_FORMAT.to_string(),
+            version: ARCHIVE_VERSION,
+            books: Vec::new(),
+            writers: Vec::new(),
+            book_writers: Vec::new(),
+            reviews: Vec::new(),
+        }
+    }
+
+    /// Check that the archive header is one this build can read
+    pub fn validate_header(&self) -> LibroResult<()> {
+        validate_header(&self.format, self.version)
+    }
+
+    /// Write the archive in the given encoding
+    pub fn write_to<W: Write>(&self, writer: &mut W, encoding: ArchiveEncoding) -> LibroResult<()> {
+        match encoding {
+            ArchiveEncoding::Json => {
+                serde_json::to_writer_pretty(&mut *writer, self)?;
+                writeln!(writer)?;
+            }
+            ArchiveEncoding::Ndjson => {
+                let header = ArchiveRecord::Header {
+                    format: self.format.clone(),
+                    version: self.version,
+                };
+                write_record(writer, &header)?;
+                for writer_row in &self.writers {
+                    write_record(writer, &ArchiveRecord::Writer(writer_row.clone()))?;
+                }
+                for book in &self.books {
+                    write_record(writer, &ArchiveRecord::Book(book.clone()))?;
+                }
+                for link in &self.book_writers {
+                    write_record(writer, &ArchiveRecord::BookWriter(link.clone()))?;
+                }
+                for review in &self.reviews {
+                    write_record(writer, &ArchiveRecord::Review(review.clone()))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Read an archive in the given encoding and validate its header
+    pub fn read_from<R: BufRead>(reader: R, encoding: ArchiveEncoding) -> LibroResult<Self> {
+        let archive = match encoding {
+            ArchiveEncoding::Json => serde_json::from_reader::<_, Archive>(reader)?,
+            ArchiveEncoding::Ndjson => read_ndjson(reader)?,
+        };
+        archive.validate_header()?;
+        Ok(archive)
+    }
+}
+
+impl Default for Archive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn validate_header(format: &str, version: u32) -> LibroResult<()> {
+    if format != ARCHIVE_FORMAT {
+        return Err(LibroError::invalid_input(format!(
+            "Not a libro archive (format '{}')",
+            format
+        )));
+    }
+    if version == 0 || version > ARCHIVE_VERSION {
+        return Err(LibroError::invalid_input(format!(
+            "Unsupported archive version {} (this build reads up to {})",
+            version, ARCHIVE_VERSION
+        )));
+    }
+    Ok(())
+}
+
+fn write_record<W: Write>(writer: &mut W, record: &ArchiveRecord) -> LibroResult<()> {
+    serde_json::to_writer(&mut *writer, record)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn read_ndjson<R: BufRead>(reader: R) -> LibroResult<Archive> {
+    let mut archive = Archive::new();
+    let mut seen_header = false;
+
+    for (line_no, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record: ArchiveRecord = serde_json::from_str(&line)
+            .map_err(|e| LibroError::invalid_input(format!("Line {}: {}", line_no + 1, e)))?;
+
+        match record {
+            ArchiveRecord::Header { format, version } => {
+                if seen_header {
+                    return Err(LibroError::invalid_input(format!(
+                        "Line {}: duplicate archive header",
+                        line_no + 1
+                    )));
+                }
+                validate_header(&format, version)?;
+                archive.format = format;
+                archive.version = version;
+                seen_header = true;
+            }
+            _ if !seen_header => {
+                return Err(LibroError::invalid_input(
+                    "NDJSON archive must start with a header record",
+                ));
+            }
+            ArchiveRecord::Book(book) => archive.books.push(book),
+            ArchiveRecord::Writer(writer) => archive.writers.push(writer),
+            ArchiveRecord::BookWriter(link) => archive.book_writers.push(link),
+            ArchiveRecord::Review(review) => archive.reviews.push(review),
+        }
+    }
+
+    if !seen_header {
+        return Err(LibroError::invalid_input("Archive is empty"));
+    }
+
+    Ok(archive)
+}
+
+/// Translate an archive ID into the ID assigned during import
+pub(crate) fn remap(ids: &HashMap<i64, i64>, old_id: i64, kind: &str) -> LibroResult<i64> {
+    ids.get(&old_id).copied().ok_or_else(|| {
+        LibroError::invalid_input(format!("Archive references unknown {} ID {}", kind, old_id))
+    })
+}
+
+/// An archive row's ID, which every row must have
+pub(crate) fn archive_id(id: Option<i64>, kind: &str) -> LibroResult<i64> {
+    id.ok_or_else(|| LibroError::invalid_input(format!("Archive {} is missing an ID", kind)))
+}
+
+/// Error for loading an archive into a library that already has rows
+pub(crate) fn not_empty() -> LibroError {
+    LibroError::invalid_input("Archives can only be loaded into an empty library")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_operations::Database;
+    use crate::models::{NewBook, NewBookWithReview, NewReview};
+    use crate::store::LibraryStore;
+    use chrono::NaiveDate;
+    use proptest::prelude::*;
+
+    const AUTHORS: [&str; 5] = [
+        "한강",
+        "Ursula K. Le Guin",
+        "Italo Calvino",
+        "김영하",
+        "Ted Chiang",
+    ];
+    const TRANSLATORS: [&str; 3] = ["Deborah Smith", "William Weaver", "정영목"];
+
+    fn arb_book() -> impl Strategy<Value = NewBookWithReview> {
+        (
+            "[A-Za-z가-힣 ]{1,20}",
+            proptest::sample::subsequence(AUTHORS.to_vec(), 1..=3),
+            proptest::sample::subsequence(TRANSLATORS.to_vec(), 0..=2),
+            proptest::option::of(1..2000i32),
+            proptest::option::of(1900..2024i32),
+            proptest::sample::select(vec!["Fiction", "Essay", "SF"]),
+            proptest::option::of((1..=5i32, 0..3650u32, "[a-z ]{1,40}")),
+        )
+            .prop_map(
+                |(title, authors, translators, pages, pub_year, genre, review)| NewBookWithReview {
+                    book: NewBook {
+                        title,
+                        authors: authors.into_iter().map(String::from).collect(),
+                        translators: translators.into_iter().map(String::from).collect(),
+                        pages,
+                        pub_year,
+                        genre: genre.to_string(),
+                    },
+                    review: review.map(|(rating, days, text)| NewReview {
+                        book_id: 0,
+                        date_read: NaiveDate::from_ymd_opt(2015, 1, 1)
+                            .map(|d| d + chrono::Duration::days(days as i64)),
+                        rating,
+                        review: text,
+                    }),
+                },
+            )
+    }
+
+    /// What to move to the trash after adding a book
+    #[derive(Debug, Clone, Copy)]
+    enum Trash {
+        Nothing,
+        Book,
+        Review,
+    }
+
+    fn arb_trash() -> impl Strategy<Value = Trash> {
+        proptest::sample::select(vec![Trash::Nothing, Trash::Book, Trash::Review])
+    }
+
+    fn export_string(db: &Database, encoding: ArchiveEncoding) -> String {
+        let mut buffer = Vec::new();
+        db.export_archive()
+            .unwrap()
+            .write_to(&mut buffer, encoding)
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn assert_round_trip(books: &[(NewBookWithReview, Trash)], encoding: ArchiveEncoding) {
+        let mut source = Database::new(":memory:").unwrap();
+        for (book, trash) in books {
+            let created = source.add_book_with_review(book).unwrap();
+            match (trash, created.review_id) {
+                (Trash::Book, _) => source.delete_book(created.book_id).unwrap(),
+                (Trash::Review, Some(review_id)) => source.delete_review(review_id).unwrap(),
+                _ => {}
+            }
+        }
+        let first = export_string(&source, encoding);
+
+        let archive = Archive::read_from(first.as_bytes(), encoding).unwrap();
+        let mut target = Database::new(":memory:").unwrap();
+        target.import_archive(&archive).unwrap();
+        let second = export_string(&target, encoding);
+
+        assert_eq!(first, second);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn json_export_import_round_trip(
+            books in proptest::collection::vec((arb_book(), arb_trash()), 0..8)
+        ) {
+            assert_round_trip(&books, ArchiveEncoding::Json);
+        }
+
+        #[test]
+        fn ndjson_export_import_round_trip(
+            books in proptest::collection::vec((arb_book(), arb_trash()), 0..8)
+        ) {
+            assert_round_trip(&books, ArchiveEncoding::Ndjson);
+        }
+    }
+
+    #[test]
+    fn test_import_remaps_ids_into_populated_library() {
+        let mut source = Database::new(":memory:").unwrap();
+        let book = NewBookWithReview {
+            book: NewBook {
+                title: "Exhalation".to_string(),
+                authors: vec!["Ted Chiang".to_string()],
+                translators: vec![],
+                pages: Some(350),
+                pub_year: Some(2019),
+                genre: "SF".to_string(),
+            },
+            review: None,
+        };
+        source.add_book_with_review(&book).unwrap();
+        let archive = source.export_archive().unwrap();
+
+        // Importing the same archive twice must not collide on IDs or writer names
+        let summary = source.import_archive(&archive).unwrap();
+        assert_eq!(summary.books, 1);
+        assert_eq!(summary.writers_created, 0);
+        assert_eq!(summary.writers_reused, 1);
+
+        let exported = source.export_archive().unwrap();
+        assert_eq!(exported.books.len(), 2);
+        assert_eq!(exported.writers.len(), 1);
+        assert_eq!(exported.book_writers.len(), 2);
+    }
+
+    #[test]
+    fn test_rejects_unknown_format_and_version() {
+        let foreign = r#"{"format":"other","version":1,"books":[],"writers":[],"book_writers":[],"reviews":[]}"#;
+        assert!(Archive::read_from(foreign.as_bytes(), ArchiveEncoding::Json).is_err());
+
+        let future = r#"{"type":"header","format":"libro-archive","version":99}"#;
+        assert!(Archive::read_from(future.as_bytes(), ArchiveEncoding::Ndjson).is_err());
+
+        let headerless =
+            r#"{"type":"review","id":1,"book_id":1,"date_read":null,"rating":null,"review":null}"#;
+        assert!(Archive::read_from(headerless.as_bytes(), ArchiveEncoding::Ndjson).is_err());
+    }
+}
@@ -0,0 +1,343 @@
+use chrono::NaiveDateTime;
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::db::{schema_version, REQUIRED_TABLES, SCHEMA_VERSION};
+use crate::errors::{LibroError, LibroResult};
+
+/// File name prefix for timestamped backups
+pub const BACKUP_PREFIX: &str = "libro-";
+
+/// File name extension for timestamped backups
+pub const BACKUP_EXTENSION: &str = ".db";
+
+/// Timestamp layout embedded in backup file names
+const STAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Result of a rotated backup
+#[derive(Debug, Clone)]
+pub struct BackupSummary {
+    pub path: PathBuf,
+    pub removed: Vec<PathBuf>,
+}
+
+/// Result of a restore
+#[derive(Debug, Clone)]
+pub struct RestoreSummary {
+    pub schema_version: i32,
+    /// Safety copy of the database that was replaced, if there was one
+    pub previous: Option<PathBuf>,
+}
+
+/// Copy a live database to `dest` using SQLite's online backup API.
+///
+/// The backup is taken page by page from a consistent snapshot, so it is safe
+/// while another process (such as the TUI) has the database open.
+pub fn backup_database(db_path: &Path, dest: &Path) -> LibroResult<()> {
+    if !db_path.exists() {
+        return Err(LibroError::invalid_input(format!(
+            "Database not found: {}",
+            db_path.display()
+        )));
+    }
+    if let Some(parent) = dest.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    conn.backup(DatabaseName::Main, dest, None)?;
+    Ok(())
+}
+
+/// Build the file name for a backup taken at `now`
+pub fn backup_file_name(now: NaiveDateTime) -> String {
+    format!(
+        "{}{}{}",
+        BACKUP_PREFIX,
+        now.format(STAMP_FORMAT),
+        BACKUP_EXTENSION
+    )
+}
+
+/// Take a timestamped backup into `dir` and prune old ones.
+///
+/// Only files named like `libro-YYYYMMDD-HHMMSS.db` are considered for
+/// rotation; `keep == 0` keeps every backup.
+pub fn create_rotated_backup(
+    db_path: &Path,
+    dir: &Path,
+    keep: usize,
+    now: NaiveDateTime,
+) -> LibroResult<BackupSummary> {
+    let path = dir.join(backup_file_name(now));
+    backup_database(db_path, &path)?;
+    let removed = rotate_backups(dir, keep)?;
+    Ok(BackupSummary { path, removed })
+}
+
+/// Delete the oldest timestamped backups in `dir`, leaving the newest `keep`
+pub fn rotate_backups(dir: &Path, keep: usize) -> LibroResult<Vec<PathBuf>> {
+    if keep == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = list_backups(dir)?;
+    if backups.len() <= keep {
+        return Ok(Vec::new());
+    }
+
+    // Names sort chronologically, so the oldest come first
+    let excess = backups.len() - keep;
+    let removed: Vec<PathBuf> = backups.drain(..excess).collect();
+    for path in &removed {
+        fs::remove_file(path)?;
+    }
+    Ok(removed)
+}
+
+/// List timestamped backups in `dir`, oldest first
+pub fn list_backups(dir: &Path) -> LibroResult<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_backup = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(BACKUP_PREFIX))
+            .and_then(|name| name.strip_suffix(BACKUP_EXTENSION))
+            .map(|stamp| NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT).is_ok())
+            .unwrap_or(false);
+        if is_backup && path.is_file() {
+            backups.push(path);
+        }
+    }
+    backups.sort();
+    Ok(backups)
+}
+
+/// Check that `path` is an intact libro database this build can open.
+///
+/// Returns the schema version recorded in the file. Databases created before
+/// versioning report version 0 and are accepted when all tables are present.
+pub fn validate_backup(path: &Path) -> LibroResult<i32> {
+    if !path.is_file() {
+        return Err(LibroError::invalid_input(format!(
+            "Backup file not found: {}",
+            path.display()
+        )));
+    }
+
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| not_a_backup(path, e))?;
+
+    let integrity: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| not_a_backup(path, e))?;
+    if integrity != "ok" {
+        return Err(LibroError::validation(format!(
+            "Integrity check failed for {}: {}",
+            path.display(),
+            integrity
+        )));
+    }
+
+    let version = schema_version(&conn)?;
+    if version > SCHEMA_VERSION {
+        return Err(LibroError::validation(format!(
+            "Backup uses schema version {} but this build supports up to {}",
+            version, SCHEMA_VERSION
+        )));
+    }
+
+    for table in REQUIRED_TABLES {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)",
+            [table],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(LibroError::validation(format!(
+                "{} is not a libro database (missing table '{}')",
+                path.display(),
+                table
+            )));
+        }
+    }
+
+    Ok(version)
+}
+
+/// Replace the database at `db_path` with a validated backup.
+///
+/// The backup is first copied next to the target and then renamed over it, so
+/// the live database is never left half-written. The replaced database is kept
+/// as `<db>.pre-restore`.
+pub fn restore_database(backup: &Path, db_path: &Path) -> LibroResult<RestoreSummary> {
+    let schema_version = validate_backup(backup)?;
+
+    let previous = if db_path.exists() {
+        let safety = sibling_path(db_path, "pre-restore");
+        backup_database(db_path, &safety)?;
+        Some(safety)
+    } else {
+        None
+    };
+
+    let staging = sibling_path(db_path, "restore-tmp");
+    if staging.exists() {
+        fs::remove_file(&staging)?;
+    }
+    backup_database(backup, &staging)?;
+    fs::rename(&staging, db_path)?;
+
+    Ok(RestoreSummary {
+        schema_version,
+        previous,
+    })
+}
+
+/// `<file>.<suffix>` in the same directory as `path`
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+fn not_a_backup(path: &Path, error: rusqlite::Error) -> LibroError {
+    LibroError::validation(format!(
+        "{} is not a readable SQLite database: {}",
+        path.display(),
+        error
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_operations::Database;
+    use crate::models::{BookFilter, NewBook};
+    use crate::store::LibraryStore;
+    use chrono::NaiveDate;
+    use tempfile::tempdir;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn seed(path: &Path, title: &str) {
+        let mut db = Database::new(path.to_str().unwrap()).unwrap();
+        db.add_book(&NewBook {
+            title: title.to_string(),
+            authors: vec!["Ted Chiang".to_string()],
+            translators: vec![],
+            pages: None,
+            pub_year: None,
+            genre: "SF".to_string(),
+        })
+        .unwrap();
+    }
+
+    fn titles(path: &Path) -> Vec<String> {
+        let db = Database::new(path.to_str().unwrap()).unwrap();
+        db.get_books(&BookFilter::default())
+            .unwrap()
+            .into_iter()
+            .map(|b| b.book.title)
+            .collect()
+    }
+
+    #[test]
+    fn test_backup_while_connection_is_open() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("libro.db");
+        seed(&db_path, "Exhalation");
+
+        // Keep a writer connection open, as the TUI would
+        let _live = Database::new(db_path.to_str().unwrap()).unwrap();
+        let dest = dir.path().join("copy.db");
+        backup_database(&db_path, &dest).unwrap();
+
+        assert_eq!(validate_backup(&dest).unwrap(), SCHEMA_VERSION);
+        assert_eq!(titles(&dest), vec!["Exhalation"]);
+    }
+
+    #[test]
+    fn test_rotation_keeps_newest() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("libro.db");
+        seed(&db_path, "Exhalation");
+        let backups = dir.path().join("backups");
+        fs::create_dir_all(&backups).unwrap();
+        fs::write(backups.join("notes.txt"), "unrelated").unwrap();
+
+        for hour in 1..=4 {
+            create_rotated_backup(&db_path, &backups, 2, at(hour)).unwrap();
+        }
+
+        let names: Vec<String> = list_backups(&backups)
+            .unwrap()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["libro-20240301-030000.db", "libro-20240301-040000.db"]
+        );
+        assert!(backups.join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_restore_swaps_database_and_keeps_previous() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("libro.db");
+        let backup = dir.path().join("backup.db");
+        seed(&backup, "Stories of Your Life");
+        seed(&db_path, "Exhalation");
+
+        let summary = restore_database(&backup, &db_path).unwrap();
+
+        assert_eq!(summary.schema_version, SCHEMA_VERSION);
+        assert_eq!(titles(&db_path), vec!["Stories of Your Life"]);
+        assert_eq!(titles(&summary.previous.unwrap()), vec!["Exhalation"]);
+    }
+
+    #[test]
+    fn test_restore_rejects_invalid_files() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("libro.db");
+        seed(&db_path, "Exhalation");
+
+        let garbage = dir.path().join("garbage.db");
+        fs::write(&garbage, "definitely not sqlite").unwrap();
+        assert!(restore_database(&garbage, &db_path).is_err());
+
+        let foreign = dir.path().join("foreign.db");
+        Connection::open(&foreign)
+            .unwrap()
+            .execute_batch("CREATE TABLE notes (id INTEGER)")
+            .unwrap();
+        assert!(restore_database(&foreign, &db_path).is_err());
+
+        let future = dir.path().join("future.db");
+        seed(&future, "From the future");
+        Connection::open(&future)
+            .unwrap()
+            .pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+            .unwrap();
+        assert!(restore_database(&future, &db_path).is_err());
+
+        // The live database is untouched by failed restores
+        assert_eq!(titles(&db_path), vec!["Exhalation"]);
+    }
+}
@@ -0,0 +1,191 @@
+use rusqlite::{Connection, Result};
+
+use crate::history::{timestamp, HistoryTable};
+
+/// 현재 스키마 버전 (`PRAGMA user_version`에 기록)
+///
+/// 2: 변경 이력 테이블(`history`, `history_changes`)과 트리거 추가
+/// 3: 휴지통용 `books.deleted_at`, `reviews.deleted_at` 컬럼 추가
+/// 4: `books`, `reviews`에 `created_at`, `updated_at` 컬럼 추가
+pub const SCHEMA_VERSION: i32 = 4;
+
+/// 모든 libro 데이터베이스에 있어야 하는 테이블
+pub const REQUIRED_TABLES: [&str; 4] = ["books", "reviews", "writers", "book_writers"];
+
+/// DB 초기화: 파일 경로로 연결 후, 기본 테이블 생성
+pub fn init_db(path: &str) -> Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.execute_batch(
+        "
+        PRAGMA foreign_keys = ON;
+        BEGIN;
+        CREATE TABLE IF NOT EXISTS books (
+            id        INTEGER PRIMARY KEY AUTOINCREMENT,
+            title     TEXT    NOT NULL,
+            pages     INTEGER,
+            pub_year  INTEGER,
+            genre     TEXT    NOT NULL,
+            deleted_at TEXT,
+            created_at TEXT,
+            updated_at TEXT
+        );
+        CREATE TABLE IF NOT EXISTS reviews (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            book_id    INTEGER NOT NULL,
+            date_read  TEXT,
+            rating     INTEGER,
+            review     TEXT,
+            deleted_at TEXT,
+            created_at TEXT,
+            updated_at TEXT,
+            FOREIGN KEY(book_id) REFERENCES books(id)
+        );
+        CREATE TABLE IF NOT EXISTS writers (
+            id   INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            type TEXT NOT NULL CHECK (type IN ('author', 'translator'))
+        );
+        CREATE TABLE IF NOT EXISTS book_writers (
+            book_id   INTEGER NOT NULL,
+            writer_id INTEGER NOT NULL,
+            type      TEXT    NOT NULL CHECK (type IN ('author', 'translator')),
+            PRIMARY KEY (book_id, writer_id, type),
+            FOREIGN KEY(book_id) REFERENCES books(id) ON DELETE CASCADE,
+            FOREIGN KEY(writer_id) REFERENCES writers(id) ON DELETE CASCADE
+        );
+        CREATE TABLE IF NOT EXISTS history (
+            id          INTEGER PRIMARY KEY,
+            operation   TEXT    NOT NULL,
+            recorded_at TEXT    NOT NULL,
+            open        INTEGER NOT NULL DEFAULT 0,
+            undone      INTEGER NOT NULL DEFAULT 0
+        );
+        CREATE TABLE IF NOT EXISTS history_changes (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            history_id INTEGER NOT NULL,
+            table_name TEXT    NOT NULL,
+            before     TEXT,
+            after      TEXT,
+            FOREIGN KEY(history_id) REFERENCES history(id) ON DELETE CASCADE
+        );
+        COMMIT;
+        ",
+    )?;
+
+    // 빠진 테이블은 위에서 만들었으므로 컬럼과 트리거만 맞춘 뒤 버전 갱신
+    if schema_version(&conn)? < SCHEMA_VERSION {
+        migrate(&conn, &timestamp())?;
+    }
+    conn.execute_batch(&history_triggers())?;
+    if schema_version(&conn)? < SCHEMA_VERSION {
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    }
+    Ok(conn)
+}
+
+/// 이전 버전 DB에 빠진 컬럼을 추가하고, 컬럼 목록이 바뀐 이력 트리거를 지움
+fn migrate(conn: &Connection, now: &str) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    for table in ["books", "reviews"] {
+        if !has_column(&tx, table, "deleted_at")? {
+            tx.execute_batch(&format!("ALTER TABLE {table} ADD COLUMN deleted_at TEXT"))?;
+        }
+        if !has_column(&tx, table, "created_at")? {
+            tx.execute_batch(&format!(
+                "ALTER TABLE {table} ADD COLUMN created_at TEXT;
+                 ALTER TABLE {table} ADD COLUMN updated_at TEXT;"
+            ))?;
+            backfill_timestamps(&tx, table, now)?;
+        }
+    }
+    for table in HistoryTable::ALL {
+        for suffix in ["insert", "update", "delete"] {
+            tx.execute_batch(&format!(
+                "DROP TRIGGER IF EXISTS {}_history_{suffix}",
+                table.as_str()
+            ))?;
+        }
+    }
+    tx.commit()
+}
+
+/// 새로 추가한 `created_at`, `updated_at`을 채움.
+///
+/// 변경 이력에 행이 추가되거나 수정된 시각이 있으면 그 시각을, 없으면
+/// 읽은 날짜(도서는 가장 이른 리뷰의 날짜)를, 그마저 없으면 `now`를 씀
+fn backfill_timestamps(conn: &Connection, table: &str, now: &str) -> Result<()> {
+    let recorded = |changes: &str, aggregate: &str| {
+        format!(
+            "(SELECT {aggregate}(h.recorded_at)
+              FROM history_changes c JOIN history h ON h.id = c.history_id
+              WHERE c.table_name = '{table}' AND h.undone = 0 AND {changes}
+                AND json_extract(c.after, '$.id') = {table}.id)"
+        )
+    };
+    let date_read = match table {
+        "books" => {
+            "(SELECT MIN(r.date_read) || ' 00:00:00' FROM reviews r WHERE r.book_id = books.id)"
+        }
+        _ => "date_read || ' 00:00:00'",
+    };
+    conn.execute(
+        &format!(
+            "UPDATE {table} SET created_at = COALESCE({}, {date_read}, ?1)",
+            recorded("c.before IS NULL", "MIN")
+        ),
+        [now],
+    )?;
+    conn.execute_batch(&format!(
+        "UPDATE {table} SET updated_at = COALESCE({}, created_at)",
+        recorded("c.before IS NOT NULL AND c.after IS NOT NULL", "MAX")
+    ))?;
+    Ok(())
+}
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT 1 FROM pragma_table_info('{table}') WHERE name = ?"
+    ))?;
+    stmt.exists([column])
+}
+
+/// 각 테이블의 INSERT/UPDATE/DELETE를 `history_changes`에 기록하는 트리거.
+///
+/// `Database`가 작업을 시작하며 `open = 1`인 이력 행을 만들어 둔 동안에만
+/// 기록하므로, 되돌리기나 `doctor --fix` 같은 직접 수정은 기록되지 않습니다.
+fn history_triggers() -> String {
+    let mut sql = String::from("BEGIN;\n");
+    for table in HistoryTable::ALL {
+        let name = table.as_str();
+        let snapshot = |row: &str| {
+            let fields: Vec<String> = table
+                .columns()
+                .iter()
+                .map(|column| format!("'{column}', {row}.{column}"))
+                .collect();
+            format!("json_object({})", fields.join(", "))
+        };
+        for (event, before, after) in [
+            ("INSERT", "NULL".to_string(), snapshot("NEW")),
+            ("UPDATE", snapshot("OLD"), snapshot("NEW")),
+            ("DELETE", snapshot("OLD"), "NULL".to_string()),
+        ] {
+            sql.push_str(&format!(
+                "CREATE TRIGGER IF NOT EXISTS {name}_history_{suffix} AFTER {event} ON {name}
+                 WHEN EXISTS (SELECT 1 FROM history WHERE open = 1)
+                 BEGIN
+                     INSERT INTO history_changes (history_id, table_name, before, after)
+                     VALUES ((SELECT id FROM history WHERE open = 1), '{name}', {before}, {after});
+                 END;\n",
+                suffix = event.to_lowercase(),
+            ));
+        }
+    }
+    sql.push_str("COMMIT;");
+    sql
+}
+
+/// 연결된 DB의 스키마 버전 조회
+pub fn schema_version(conn: &Connection) -> Result<i32> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
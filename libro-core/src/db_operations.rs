@@ -0,0 +1,1395 @@
+use chrono::NaiveDate;
+use rusqlite::{params, params_from_iter, types::Value as SqlValue, Connection, OptionalExtension};
+use serde_json::Value;
+
+use crate::archive::{archive_id, not_empty, remap, Archive, ImportSummary, ReviewRecord};
+use crate::errors::{validation, LibroError, LibroResult};
+use crate::history::{
+    changed_since, same_row, stamps, timestamp, undo_candidates, HistoryEntry, HistoryTable,
+    PurgedRows, RowChange,
+};
+use crate::merge::{MergePlan, MergeSummary};
+use crate::models::*;
+use crate::store::LibraryStore;
+use crate::trash::{book_in_trash, sort_trash, TrashItem, TrashKind};
+use chrono::NaiveDateTime;
+use std::collections::HashMap;
+
+/// Reviews that are neither trashed nor on a trashed book
+const LIVE_REVIEW: &str =
+    "deleted_at IS NULL AND book_id IN (SELECT id FROM books WHERE deleted_at IS NULL)";
+
+/// Writers that are linked to a book outside the trash, or to no book at all
+const LIVE_WRITER: &str =
+    "(NOT EXISTS (SELECT 1 FROM book_writers bw WHERE bw.writer_id = writers.id)
+     OR EXISTS (
+         SELECT 1 FROM book_writers bw JOIN books b ON b.id = bw.book_id
+         WHERE bw.writer_id = writers.id AND b.deleted_at IS NULL
+     ))";
+
+/// Database operations struct that wraps a SQLite connection
+pub struct Database {
+    conn: Connection,
+    /// Set while an operation is being recorded, so nested calls join it
+    recording: bool,
+}
+
+impl Database {
+    /// Create a new database instance and initialize schema
+    pub fn new(path: &str) -> LibroResult<Self> {
+        let conn = crate::db::init_db(path)?;
+        Ok(Database {
+            conn,
+            recording: false,
+        })
+    }
+
+    /// Run `change` as one history entry, all or nothing.
+    ///
+    /// The schema's triggers record every row change while the entry is
+    /// open; an operation that changed nothing leaves no entry behind.
+    fn recorded<T>(
+        &mut self,
+        operation: &str,
+        change: impl FnOnce(&mut Self) -> LibroResult<T>,
+    ) -> LibroResult<T> {
+        if self.recording {
+            return change(self);
+        }
+
+        self.conn.execute_batch("SAVEPOINT recorded")?;
+        self.recording = true;
+        let result = self.record(operation, change);
+        self.recording = false;
+
+        match result {
+            Ok(value) => {
+                self.conn.execute_batch("RELEASE recorded")?;
+                Ok(value)
+            }
+            Err(e) => {
+                self.conn
+                    .execute_batch("ROLLBACK TO recorded; RELEASE recorded")?;
+                Err(e)
+            }
+        }
+    }
+
+    fn record<T>(
+        &mut self,
+        operation: &str,
+        change: impl FnOnce(&mut Self) -> LibroResult<T>,
+    ) -> LibroResult<T> {
+        self.conn.execute(
+            "INSERT INTO history (operation, recorded_at, open) VALUES (?, ?, 1)",
+            params![operation, timestamp()],
+        )?;
+        let history_id = self.conn.last_insert_rowid();
+
+        let value = change(self)?;
+
+        self.conn.execute(
+            "UPDATE history SET open = 0 WHERE id = ?",
+            params![history_id],
+        )?;
+        self.conn.execute(
+            "DELETE FROM history WHERE id = ?1
+               AND NOT EXISTS (SELECT 1 FROM history_changes WHERE history_id = ?1)",
+            params![history_id],
+        )?;
+        Ok(value)
+    }
+
+    /// Delete trashed rows for good, then their changes from the history
+    fn purge(&mut self, purged: &[TrashItem]) -> LibroResult<()> {
+        let mut removed = PurgedRows::default();
+        for item in purged {
+            match item.kind {
+                TrashKind::Review => {
+                    self.conn
+                        .execute("DELETE FROM reviews WHERE id = ?", params![item.id])?;
+                    removed.reviews.insert(item.id);
+                }
+                TrashKind::Book => {
+                    let review_ids: Vec<i64> = self
+                        .conn
+                        .prepare("SELECT id FROM reviews WHERE book_id = ?")?
+                        .query_map(params![item.id], |row| row.get(0))?
+                        .collect::<Result<_, _>>()?;
+                    removed.reviews.extend(review_ids);
+                    self.conn
+                        .execute("DELETE FROM reviews WHERE book_id = ?", params![item.id])?;
+                    let writer_ids: Vec<i64> = self
+                        .conn
+                        .prepare("SELECT writer_id FROM book_writers WHERE book_id = ?")?
+                        .query_map(params![item.id], |row| row.get(0))?
+                        .collect::<Result<_, _>>()?;
+                    self.conn.execute(
+                        "DELETE FROM book_writers WHERE book_id = ?",
+                        params![item.id],
+                    )?;
+                    // 어떤 책에도 남지 않은 작가는 마지막 책과 함께 삭제
+                    for writer_id in writer_ids {
+                        let deleted = self.conn.execute(
+                            "DELETE FROM writers
+                             WHERE id = ?1
+                               AND NOT EXISTS (SELECT 1 FROM book_writers WHERE writer_id = ?1)",
+                            params![writer_id],
+                        )?;
+                        if deleted > 0 {
+                            removed.writers.insert(writer_id);
+                        }
+                    }
+                    self.conn
+                        .execute("DELETE FROM books WHERE id = ?", params![item.id])?;
+                    removed.books.insert(item.id);
+                }
+            }
+        }
+
+        let mut purged_changes = Vec::new();
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, table_name, before, after FROM history_changes")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            if removed.recorded(&row_change(row, 1)?) {
+                purged_changes.push(row.get::<_, i64>(0)?);
+            }
+        }
+        for id in purged_changes {
+            self.conn
+                .execute("DELETE FROM history_changes WHERE id = ?", params![id])?;
+        }
+        Ok(())
+    }
+
+    /// Helper method to process book rows and add related data
+    fn process_book_rows(
+        &self,
+        book_rows: rusqlite::MappedRows<impl FnMut(&rusqlite::Row) -> rusqlite::Result<Book>>,
+    ) -> LibroResult<Vec<ExtendedBook>> {
+        let mut extended_books = Vec::new();
+
+        for book_result in book_rows {
+            let book = book_result?;
+            let book_id = book.id.unwrap();
+
+            // Get writers for this book
+            let all_writers = self.get_book_writers(book_id)?;
+            let authors: Vec<Writer> = all_writers
+                .iter()
+                .filter(|w| matches!(w.writer_type, WriterType::Author))
+                .cloned()
+                .collect();
+            let translators: Vec<Writer> = all_writers
+                .iter()
+                .filter(|w| matches!(w.writer_type, WriterType::Translator))
+                .cloned()
+                .collect();
+
+            // Get reviews for this book
+            let reviews = self.get_reviews(book_id)?;
+
+            extended_books.push(ExtendedBook {
+                book,
+                authors,
+                translators,
+                reviews,
+            });
+        }
+
+        Ok(extended_books)
+    }
+}
+
+impl LibraryStore for Database {
+    fn get_or_add_writer(&mut self, name: &str, writer_type: WriterType) -> LibroResult<i64> {
+        self.recorded("add writer", |db| {
+            validation::validate_non_empty(name, "Writer name")?;
+
+            // Check if writer already exists
+            let existing_id: Option<i64> = db
+                .conn
+                .query_row(
+                    "SELECT id FROM writers WHERE name = ? AND type = ?",
+                    params![name, writer_type.as_str()],
+                    |row| row.get(0),
+                )
+                .optional()?;
+
+            if let Some(id) = existing_id {
+                Ok(id)
+            } else {
+                // Add new writer
+                db.conn.execute(
+                    "INSERT INTO writers (name, type) VALUES (?, ?)",
+                    params![name, writer_type.as_str()],
+                )?;
+                Ok(db.conn.last_insert_rowid())
+            }
+        })
+    }
+
+    fn add_book_writer_link(
+        &mut self,
+        book_id: i64,
+        writer_id: i64,
+        writer_type: WriterType,
+    ) -> LibroResult<()> {
+        self.recorded("link writer", |db| {
+            db.conn.execute(
+                "INSERT INTO book_writers (book_id, writer_id, type) VALUES (?, ?, ?)",
+                params![book_id, writer_id, writer_type.as_str()],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn add_book(&mut self, book: &NewBook) -> LibroResult<i64> {
+        self.recorded("add book", |db| {
+            validation::validate_non_empty(&book.title, "Title")?;
+
+            if book.authors.is_empty() {
+                return Err(LibroError::invalid_field(
+                    "authors",
+                    "required",
+                    "At least one author is required",
+                ));
+            }
+
+            if let Some(pages) = book.pages {
+                validation::validate_pages(pages)?;
+            }
+
+            if let Some(year) = book.pub_year {
+                validation::validate_year(year)?;
+            }
+
+            // Insert book
+            db.conn.execute(
+                "INSERT INTO books (title, pages, pub_year, genre, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+                params![
+                    book.title,
+                    book.pages,
+                    book.pub_year,
+                    book.genre,
+                    timestamp()
+                ],
+            )?;
+            let book_id = db.conn.last_insert_rowid();
+
+            // Add authors
+            for author_name in &book.authors {
+                let author_id = db.get_or_add_writer(author_name, WriterType::Author)?;
+                db.add_book_writer_link(book_id, author_id, WriterType::Author)?;
+            }
+
+            // Add translators
+            for translator_name in &book.translators {
+                let translator_id =
+                    db.get_or_add_writer(translator_name, WriterType::Translator)?;
+                db.add_book_writer_link(book_id, translator_id, WriterType::Translator)?;
+            }
+
+            Ok(book_id)
+        })
+    }
+
+    fn add_review(&mut self, review: &NewReview) -> LibroResult<i64> {
+        self.recorded("add review", |db| {
+            validation::validate_rating(review.rating)?;
+            validation::validate_non_empty(&review.review, "Review text")?;
+
+            // Check if book exists
+            let book_exists: bool = db
+                .conn
+                .query_row(
+                    "SELECT 1 FROM books WHERE id = ? AND deleted_at IS NULL",
+                    params![review.book_id],
+                    |_| Ok(true),
+                )
+                .optional()?
+                .unwrap_or(false);
+
+            if !book_exists {
+                return Err(LibroError::BookNotFound { id: review.book_id });
+            }
+
+            let date_str = review
+                .date_read
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%d").to_string());
+
+            db.conn.execute(
+                "INSERT INTO reviews (book_id, date_read, rating, review, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+                params![
+                    review.book_id,
+                    date_str,
+                    review.rating,
+                    review.review,
+                    timestamp()
+                ],
+            )?;
+
+            Ok(db.conn.last_insert_rowid())
+        })
+    }
+
+    fn add_book_with_review(
+        &mut self,
+        input: &NewBookWithReview,
+    ) -> LibroResult<BookCreationResult> {
+        self.recorded("add book", |db| {
+            let tx = db.conn.savepoint()?;
+
+            let now = timestamp();
+            let book_id = {
+                // Insert book within transaction
+                tx.execute(
+                    "INSERT INTO books (title, pages, pub_year, genre, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+                    params![
+                        input.book.title,
+                        input.book.pages,
+                        input.book.pub_year,
+                        input.book.genre,
+                        now
+                    ],
+                )?;
+                tx.last_insert_rowid()
+            };
+
+            // Add authors within transaction
+            for author_name in &input.book.authors {
+                let author_id = {
+                    let existing_id: Option<i64> = tx
+                        .query_row(
+                            "SELECT id FROM writers WHERE name = ? AND type = ?",
+                            params![author_name, "author"],
+                            |row| row.get(0),
+                        )
+                        .optional()?;
+
+                    if let Some(id) = existing_id {
+                        id
+                    } else {
+                        tx.execute(
+                            "INSERT INTO writers (name, type) VALUES (?, ?)",
+                            params![author_name, "author"],
+                        )?;
+                        tx.last_insert_rowid()
+                    }
+                };
+
+                tx.execute(
+                    "INSERT INTO book_writers (book_id, writer_id, type) VALUES (?, ?, ?)",
+                    params![book_id, author_id, "author"],
+                )?;
+            }
+
+            // Add translators within transaction
+            for translator_name in &input.book.translators {
+                let translator_id = {
+                    let existing_id: Option<i64> = tx
+                        .query_row(
+                            "SELECT id FROM writers WHERE name = ? AND type = ?",
+                            params![translator_name, "translator"],
+                            |row| row.get(0),
+                        )
+                        .optional()?;
+
+                    if let Some(id) = existing_id {
+                        id
+                    } else {
+                        tx.execute(
+                            "INSERT INTO writers (name, type) VALUES (?, ?)",
+                            params![translator_name, "translator"],
+                        )?;
+                        tx.last_insert_rowid()
+                    }
+                };
+
+                tx.execute(
+                    "INSERT INTO book_writers (book_id, writer_id, type) VALUES (?, ?, ?)",
+                    params![book_id, translator_id, "translator"],
+                )?;
+            }
+
+            let review_id = if let Some(review) = &input.review {
+                let date_str = review
+                    .date_read
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%d").to_string());
+
+                tx.execute(
+                    "INSERT INTO reviews (book_id, date_read, rating, review, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+                    params![book_id, date_str, review.rating, review.review, now],
+                )?;
+                Some(tx.last_insert_rowid())
+            } else {
+                None
+            };
+
+            // Commit the transaction
+            tx.commit()?;
+
+            Ok(BookCreationResult { book_id, review_id })
+        })
+    }
+
+    fn get_books(&self, filter: &BookFilter) -> LibroResult<Vec<ExtendedBook>> {
+        if let Some(id) = filter.id {
+            let mut stmt = self.conn.prepare(
+                "SELECT DISTINCT b.id, b.title, b.pages, b.pub_year, b.genre, b.created_at, b.updated_at FROM books b WHERE b.id = ? AND b.deleted_at IS NULL ORDER BY b.id"
+            )?;
+            let book_rows = stmt.query_map(params![id], |row| {
+                Ok(Book {
+                    id: Some(row.get(0)?),
+                    title: row.get(1)?,
+                    pages: row.get(2)?,
+                    pub_year: row.get(3)?,
+                    genre: row.get(4)?,
+                    created_at: row.get(5)?,
+                    updated_at: row.get(6)?,
+                    deleted_at: None,
+                })
+            })?;
+            self.process_book_rows(book_rows)
+        } else if let Some(year) = filter.year {
+            let mut stmt = self.conn.prepare(
+                "SELECT DISTINCT b.id, b.title, b.pages, b.pub_year, b.genre, b.created_at, b.updated_at FROM books b WHERE b.pub_year = ? AND b.deleted_at IS NULL ORDER BY b.id"
+            )?;
+            let book_rows = stmt.query_map(params![year], |row| {
+                Ok(Book {
+                    id: Some(row.get(0)?),
+                    title: row.get(1)?,
+                    pages: row.get(2)?,
+                    pub_year: row.get(3)?,
+                    genre: row.get(4)?,
+                    created_at: row.get(5)?,
+                    updated_at: row.get(6)?,
+                    deleted_at: None,
+                })
+            })?;
+            self.process_book_rows(book_rows)
+        } else {
+            let mut stmt = self.conn.prepare(
+                "SELECT DISTINCT b.id, b.title, b.pages, b.pub_year, b.genre, b.created_at, b.updated_at FROM books b WHERE b.deleted_at IS NULL ORDER BY b.id"
+            )?;
+            let book_rows = stmt.query_map([], |row| {
+                Ok(Book {
+                    id: Some(row.get(0)?),
+                    title: row.get(1)?,
+                    pages: row.get(2)?,
+                    pub_year: row.get(3)?,
+                    genre: row.get(4)?,
+                    created_at: row.get(5)?,
+                    updated_at: row.get(6)?,
+                    deleted_at: None,
+                })
+            })?;
+            self.process_book_rows(book_rows)
+        }
+    }
+
+    fn get_book_writers(&self, book_id: i64) -> LibroResult<Vec<Writer>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT w.id, w.name, w.type
+             FROM writers w
+             JOIN book_writers bw ON w.id = bw.writer_id
+             WHERE bw.book_id = ?
+             ORDER BY w.type, w.name",
+        )?;
+
+        let writer_rows = stmt.query_map(params![book_id], |row| {
+            let writer_type_str: String = row.get(2)?;
+            let writer_type = WriterType::from_str(&writer_type_str).map_err(|_| {
+                rusqlite::Error::InvalidColumnType(
+                    2,
+                    "type".to_string(),
+                    rusqlite::types::Type::Text,
+                )
+            })?;
+
+            Ok(Writer {
+                id: Some(row.get(0)?),
+                name: row.get(1)?,
+                writer_type,
+            })
+        })?;
+
+        let mut writers = Vec::new();
+        for writer_result in writer_rows {
+            writers.push(writer_result?);
+        }
+
+        Ok(writers)
+    }
+
+    fn get_reviews(&self, book_id: i64) -> LibroResult<Vec<Review>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, book_id, date_read, rating, review, created_at, updated_at
+             FROM reviews
+             WHERE book_id = ? AND {}
+             ORDER BY date_read DESC",
+            LIVE_REVIEW
+        ))?;
+
+        let review_rows = stmt.query_map(params![book_id], |row| {
+            let date_str: Option<String> = row.get(2)?;
+            let date_read = date_str.and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+            Ok(Review {
+                id: Some(row.get(0)?),
+                book_id: row.get(1)?,
+                date_read,
+                rating: row.get(3)?,
+                review: row.get(4)?,
+                created_at: row.get(5)?,
+                updated_at: row.get(6)?,
+            })
+        })?;
+
+        let mut reviews = Vec::new();
+        for review_result in review_rows {
+            reviews.push(review_result?);
+        }
+
+        Ok(reviews)
+    }
+
+    fn update_book(&mut self, book_id: i64, updates: &Book) -> LibroResult<()> {
+        self.recorded("update book", |db| {
+            validation::validate_non_empty(&updates.title, "Title")?;
+
+            if let Some(pages) = updates.pages {
+                validation::validate_pages(pages)?;
+            }
+
+            if let Some(year) = updates.pub_year {
+                validation::validate_year(year)?;
+            }
+
+            let rows_affected = db.conn.execute(
+                "UPDATE books SET title = ?, pages = ?, pub_year = ?, genre = ?, updated_at = ?
+                 WHERE id = ? AND deleted_at IS NULL",
+                params![
+                    updates.title,
+                    updates.pages,
+                    updates.pub_year,
+                    updates.genre,
+                    timestamp(),
+                    book_id
+                ],
+            )?;
+
+            if rows_affected == 0 {
+                return Err(LibroError::BookNotFound { id: book_id });
+            }
+
+            Ok(())
+        })
+    }
+
+    fn update_review(&mut self, review_id: i64, updates: &Review) -> LibroResult<()> {
+        self.recorded("update review", |db| {
+            validation::validate_rating(updates.rating)?;
+            validation::validate_non_empty(&updates.review, "Review text")?;
+
+            let date_str = updates.date_read.map(|d| d.format("%Y-%m-%d").to_string());
+
+            let rows_affected = db.conn.execute(
+                &format!(
+                    "UPDATE reviews SET date_read = ?, rating = ?, review = ?, updated_at = ?
+                     WHERE id = ? AND {}",
+                    LIVE_REVIEW
+                ),
+                params![
+                    date_str,
+                    updates.rating,
+                    updates.review,
+                    timestamp(),
+                    review_id
+                ],
+            )?;
+
+            if rows_affected == 0 {
+                return Err(LibroError::ReviewIdNotFound { id: review_id });
+            }
+
+            Ok(())
+        })
+    }
+
+    fn delete_book(&mut self, book_id: i64) -> LibroResult<()> {
+        self.recorded("delete book", |db| {
+            // 리뷰와 작가 연결은 그대로 두고 도서만 휴지통으로
+            let rows_affected = db.conn.execute(
+                "UPDATE books SET deleted_at = ? WHERE id = ? AND deleted_at IS NULL",
+                params![timestamp(), book_id],
+            )?;
+
+            if rows_affected == 0 {
+                return Err(LibroError::BookNotFound { id: book_id });
+            }
+            Ok(())
+        })
+    }
+
+    fn orphaned_writers_for_book(&self, book_id: i64) -> LibroResult<Vec<Writer>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT w.id, w.name, w.type
+             FROM writers w
+             JOIN book_writers bw ON w.id = bw.writer_id
+             WHERE bw.book_id = ?1
+               AND NOT EXISTS (
+                   SELECT 1 FROM book_writers other
+                   JOIN books b ON b.id = other.book_id
+                   WHERE other.writer_id = w.id AND other.book_id != ?1
+                     AND b.deleted_at IS NULL
+               )
+             GROUP BY w.id
+             ORDER BY w.name",
+        )?;
+        let rows = stmt.query_map(params![book_id], |row| {
+            Ok(Writer {
+                id: Some(row.get(0)?),
+                name: row.get(1)?,
+                writer_type: writer_type_column(row, 2)?,
+            })
+        })?;
+
+        let mut writers = Vec::new();
+        for writer in rows {
+            writers.push(writer?);
+        }
+        Ok(writers)
+    }
+
+    fn get_review(&self, review_id: i64) -> LibroResult<Review> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT id, book_id, date_read, rating, review, created_at, updated_at
+                     FROM reviews WHERE id = ? AND {}",
+                    LIVE_REVIEW
+                ),
+                params![review_id],
+                |row| {
+                    let date_str: Option<String> = row.get(2)?;
+                    Ok(Review {
+                        id: Some(row.get(0)?),
+                        book_id: row.get(1)?,
+                        date_read: date_str
+                            .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()),
+                        rating: row.get(3)?,
+                        review: row.get(4)?,
+                        created_at: row.get(5)?,
+                        updated_at: row.get(6)?,
+                    })
+                },
+            )
+            .optional()?
+            .ok_or(LibroError::ReviewIdNotFound { id: review_id })
+    }
+
+    fn delete_review(&mut self, review_id: i64) -> LibroResult<()> {
+        self.recorded("delete review", |db| {
+            let rows_affected = db.conn.execute(
+                &format!(
+                    "UPDATE reviews SET deleted_at = ? WHERE id = ? AND {}",
+                    LIVE_REVIEW
+                ),
+                params![timestamp(), review_id],
+            )?;
+
+            if rows_affected == 0 {
+                return Err(LibroError::ReviewIdNotFound { id: review_id });
+            }
+
+            Ok(())
+        })
+    }
+
+    fn export_archive(&self) -> LibroResult<Archive> {
+        let mut archive = Archive::new();
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, title, pages, pub_year, genre, created_at, updated_at, deleted_at FROM books ORDER BY id")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(Book {
+                id: Some(row.get(0)?),
+                title: row.get(1)?,
+                pages: row.get(2)?,
+                pub_year: row.get(3)?,
+                genre: row.get(4)?,
+                created_at: row.get(5)?,
+                updated_at: row.get(6)?,
+                deleted_at: row.get(7)?,
+            })
+        })?;
+        for row in rows {
+            archive.books.push(row?);
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, name, type FROM writers ORDER BY id")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(Writer {
+                id: Some(row.get(0)?),
+                name: row.get(1)?,
+                writer_type: writer_type_column(row, 2)?,
+            })
+        })?;
+        for row in rows {
+            archive.writers.push(row?);
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT book_id, writer_id, type FROM book_writers ORDER BY book_id, writer_id, type",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(BookWriter {
+                book_id: row.get(0)?,
+                writer_id: row.get(1)?,
+                writer_type: writer_type_column(row, 2)?,
+            })
+        })?;
+        for row in rows {
+            archive.book_writers.push(row?);
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT id, book_id, date_read, rating, review, created_at, updated_at, deleted_at
+             FROM reviews ORDER BY id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(ReviewRecord {
+                id: row.get(0)?,
+                book_id: row.get(1)?,
+                date_read: row.get(2)?,
+                rating: row.get(3)?,
+                review: row.get(4)?,
+                created_at: row.get(5)?,
+                updated_at: row.get(6)?,
+                deleted_at: row.get(7)?,
+            })
+        })?;
+        for row in rows {
+            archive.reviews.push(row?);
+        }
+
+        Ok(archive)
+    }
+
+    fn import_archive(&mut self, archive: &Archive) -> LibroResult<ImportSummary> {
+        self.recorded("import archive", |db| {
+            archive.validate_header()?;
+
+            let tx = db.conn.savepoint()?;
+            let mut summary = ImportSummary::default();
+            let mut writer_ids: HashMap<i64, i64> = HashMap::new();
+            let mut book_ids: HashMap<i64, i64> = HashMap::new();
+
+            for writer in &archive.writers {
+                let old_id = archive_id(writer.id, "writer")?;
+
+                let existing_id: Option<i64> = tx
+                    .query_row(
+                        "SELECT id FROM writers WHERE name = ?",
+                        params![writer.name],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+
+                let new_id = if let Some(id) = existing_id {
+                    summary.writers_reused += 1;
+                    id
+                } else {
+                    tx.execute(
+                        "INSERT INTO writers (name, type) VALUES (?, ?)",
+                        params![writer.name, writer.writer_type.as_str()],
+                    )?;
+                    summary.writers_created += 1;
+                    tx.last_insert_rowid()
+                };
+                writer_ids.insert(old_id, new_id);
+            }
+
+            for book in &archive.books {
+                let old_id = archive_id(book.id, "book")?;
+
+                let (created_at, updated_at) =
+                    stamps(book.created_at.as_deref(), book.updated_at.as_deref());
+                tx.execute(
+                    "INSERT INTO books (title, pages, pub_year, genre, created_at, updated_at, deleted_at)
+                     VALUES (?, ?, ?, ?, ?, ?, ?)",
+                    params![
+                        book.title,
+                        book.pages,
+                        book.pub_year,
+                        book.genre,
+                        created_at,
+                        updated_at,
+                        book.deleted_at
+                    ],
+                )?;
+                book_ids.insert(old_id, tx.last_insert_rowid());
+                summary.books += 1;
+            }
+
+            for link in &archive.book_writers {
+                let book_id = remap(&book_ids, link.book_id, "book")?;
+                let writer_id = remap(&writer_ids, link.writer_id, "writer")?;
+                tx.execute(
+                    "INSERT INTO book_writers (book_id, writer_id, type) VALUES (?, ?, ?)",
+                    params![book_id, writer_id, link.writer_type.as_str()],
+                )?;
+            }
+
+            for review in &archive.reviews {
+                let book_id = remap(&book_ids, review.book_id, "book")?;
+                let (created_at, updated_at) =
+                    stamps(review.created_at.as_deref(), review.updated_at.as_deref());
+                tx.execute(
+                    "INSERT INTO reviews (book_id, date_read, rating, review, created_at, updated_at, deleted_at)
+                     VALUES (?, ?, ?, ?, ?, ?, ?)",
+                    params![
+                        book_id,
+                        review.date_read,
+                        review.rating,
+                        review.review,
+                        created_at,
+                        updated_at,
+                        review.deleted_at
+                    ],
+                )?;
+                summary.reviews += 1;
+            }
+
+            tx.commit()?;
+            Ok(summary)
+        })
+    }
+
+    fn load_archive(&mut self, archive: &Archive) -> LibroResult<()> {
+        archive.validate_header()?;
+
+        let tx = self.conn.transaction()?;
+        let rows: i64 = tx.query_row(
+            "SELECT (SELECT COUNT(*) FROM books) + (SELECT COUNT(*) FROM writers) + (SELECT COUNT(*) FROM reviews)",
+            [],
+            |row| row.get(0),
+        )?;
+        if rows > 0 {
+            return Err(not_empty());
+        }
+
+        for writer in &archive.writers {
+            tx.execute(
+                "INSERT INTO writers (id, name, type) VALUES (?, ?, ?)",
+                params![
+                    archive_id(writer.id, "writer")?,
+                    writer.name,
+                    writer.writer_type.as_str()
+                ],
+            )?;
+        }
+        for book in &archive.books {
+            tx.execute(
+                "INSERT INTO books (id, title, pages, pub_year, genre, created_at, updated_at, deleted_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    archive_id(book.id, "book")?,
+                    book.title,
+                    book.pages,
+                    book.pub_year,
+                    book.genre,
+                    book.created_at,
+                    book.updated_at,
+                    book.deleted_at
+                ],
+            )?;
+        }
+        for link in &archive.book_writers {
+            tx.execute(
+                "INSERT INTO book_writers (book_id, writer_id, type) VALUES (?, ?, ?)",
+                params![link.book_id, link.writer_id, link.writer_type.as_str()],
+            )?;
+        }
+        for review in &archive.reviews {
+            tx.execute(
+                "INSERT INTO reviews (id, book_id, date_read, rating, review, created_at, updated_at, deleted_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    review.id,
+                    review.book_id,
+                    review.date_read,
+                    review.rating,
+                    review.review,
+                    review.created_at,
+                    review.updated_at,
+                    review.deleted_at
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get_writers(&self) -> LibroResult<Vec<Writer>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, name, type FROM writers WHERE {} ORDER BY name",
+            LIVE_WRITER
+        ))?;
+        let rows = stmt.query_map([], |row| {
+            Ok(Writer {
+                id: Some(row.get(0)?),
+                name: row.get(1)?,
+                writer_type: writer_type_column(row, 2)?,
+            })
+        })?;
+
+        let mut writers = Vec::new();
+        for writer in rows {
+            writers.push(writer?);
+        }
+        Ok(writers)
+    }
+
+    fn apply_merge(&mut self, plan: &MergePlan) -> LibroResult<MergeSummary> {
+        self.recorded("merge", |db| {
+            let tx = db.conn.savepoint()?;
+            let mut summary = MergeSummary::default();
+
+            for book in &plan.new_books {
+                let (created_at, updated_at) = stamps(
+                    book.book.created_at.as_deref(),
+                    book.book.updated_at.as_deref(),
+                );
+                tx.execute(
+                    "INSERT INTO books (title, pages, pub_year, genre, created_at, updated_at)
+                     VALUES (?, ?, ?, ?, ?, ?)",
+                    params![
+                        book.book.title,
+                        book.book.pages,
+                        book.book.pub_year,
+                        book.book.genre,
+                        created_at,
+                        updated_at
+                    ],
+                )?;
+                let book_id = tx.last_insert_rowid();
+                summary.books_added += 1;
+
+                let writers = book
+                    .authors
+                    .iter()
+                    .map(|w| (w, WriterType::Author))
+                    .chain(book.translators.iter().map(|w| (w, WriterType::Translator)));
+                for (writer, writer_type) in writers {
+                    let writer_id = merge_writer(&tx, &writer.name, &writer_type, &mut summary)?;
+                    tx.execute(
+                        "INSERT OR IGNORE INTO book_writers (book_id, writer_id, type) VALUES (?, ?, ?)",
+                        params![book_id, writer_id, writer_type.as_str()],
+                    )?;
+                }
+
+                for review in &book.reviews {
+                    insert_merged_review(&tx, book_id, review)?;
+                    summary.reviews_added += 1;
+                }
+            }
+
+            for (book_id, book) in &plan.updated_books {
+                tx.execute(
+                    "UPDATE books SET title = ?, pages = ?, pub_year = ?, genre = ?, updated_at = ?
+                     WHERE id = ?",
+                    params![
+                        book.book.title,
+                        book.book.pages,
+                        book.book.pub_year,
+                        book.book.genre,
+                        timestamp(),
+                        book_id
+                    ],
+                )?;
+
+                // Authors are part of the match key, so only translators can differ
+                tx.execute(
+                    "DELETE FROM book_writers WHERE book_id = ? AND type = 'translator'",
+                    params![book_id],
+                )?;
+                for translator in &book.translators {
+                    let writer_id =
+                        merge_writer(&tx, &translator.name, &WriterType::Translator, &mut summary)?;
+                    tx.execute(
+                        "INSERT OR IGNORE INTO book_writers (book_id, writer_id, type) VALUES (?, ?, ?)",
+                        params![book_id, writer_id, "translator"],
+                    )?;
+                }
+                summary.books_updated += 1;
+            }
+
+            for (book_id, review) in &plan.new_reviews {
+                insert_merged_review(&tx, *book_id, review)?;
+                summary.reviews_added += 1;
+            }
+
+            for (review_id, review) in &plan.updated_reviews {
+                tx.execute(
+                    "UPDATE reviews SET rating = ?, review = ?, updated_at = ? WHERE id = ?",
+                    params![review.rating, review.review, timestamp(), review_id],
+                )?;
+                summary.reviews_updated += 1;
+            }
+
+            tx.commit()?;
+            Ok(summary)
+        })
+    }
+
+    fn history(&self, book_id: Option<i64>) -> LibroResult<Vec<HistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, operation, recorded_at, undone FROM history WHERE open = 0 ORDER BY id DESC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(HistoryEntry {
+                id: row.get(0)?,
+                operation: row.get(1)?,
+                recorded_at: row.get(2)?,
+                undone: row.get(3)?,
+                changes: Vec::new(),
+            })
+        })?;
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        let positions: HashMap<i64, usize> = entries
+            .iter()
+            .enumerate()
+            .map(|(position, entry)| (entry.id, position))
+            .collect();
+        let mut stmt = self.conn.prepare(
+            "SELECT history_id, table_name, before, after FROM history_changes ORDER BY id",
+        )?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let Some(&position) = positions.get(&row.get::<_, i64>(0)?) else {
+                continue;
+            };
+            entries[position].changes.push(row_change(row, 1)?);
+        }
+
+        if let Some(book_id) = book_id {
+            entries.retain(|entry| entry.touches_book(book_id));
+        }
+        Ok(entries)
+    }
+
+    fn undo(&mut self, count: usize) -> LibroResult<Vec<HistoryEntry>> {
+        let mut entries = undo_candidates(self.history(None)?, count)?;
+
+        let tx = self.conn.transaction()?;
+        // 되돌리는 중간에는 참조가 잠시 어긋날 수 있으므로 커밋 시점에 검사
+        tx.pragma_update(None, "defer_foreign_keys", true)?;
+        for entry in &mut entries {
+            for change in entry.changes.iter().rev() {
+                let current = current_row(&tx, change.table, change.row())?;
+                if !same_row(current.as_ref(), change.after.as_ref()) {
+                    return Err(changed_since(entry, change));
+                }
+                revert_row(&tx, &change.filled_from(current.as_ref()))?;
+            }
+            tx.execute(
+                "UPDATE history SET undone = 1 WHERE id = ?",
+                params![entry.id],
+            )?;
+            entry.undone = true;
+        }
+        tx.commit()?;
+        Ok(entries)
+    }
+
+    fn trash(&self) -> LibroResult<Vec<TrashItem>> {
+        let mut items = Vec::new();
+        let mut stmt = self.conn.prepare(
+            "SELECT b.id, b.title, b.deleted_at,
+                    (SELECT COUNT(*) FROM reviews r WHERE r.book_id = b.id AND r.deleted_at IS NULL)
+             FROM books b WHERE b.deleted_at IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(TrashItem {
+                kind: TrashKind::Book,
+                id: row.get(0)?,
+                book_id: row.get(0)?,
+                title: row.get(1)?,
+                deleted_at: row.get(2)?,
+                reviews: row.get(3)?,
+            })
+        })?;
+        for row in rows {
+            items.push(row?);
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT r.id, r.book_id, b.title, r.deleted_at
+             FROM reviews r JOIN books b ON b.id = r.book_id
+             WHERE r.deleted_at IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(TrashItem {
+                kind: TrashKind::Review,
+                id: row.get(0)?,
+                book_id: row.get(1)?,
+                title: row.get(2)?,
+                deleted_at: row.get(3)?,
+                reviews: 1,
+            })
+        })?;
+        for row in rows {
+            items.push(row?);
+        }
+
+        sort_trash(&mut items);
+        Ok(items)
+    }
+
+    fn restore_book(&mut self, book_id: i64) -> LibroResult<()> {
+        self.recorded("restore book", |db| {
+            let rows_affected = db.conn.execute(
+                "UPDATE books SET deleted_at = NULL WHERE id = ? AND deleted_at IS NOT NULL",
+                params![book_id],
+            )?;
+            if rows_affected == 0 {
+                return Err(LibroError::BookNotFound { id: book_id });
+            }
+            Ok(())
+        })
+    }
+
+    fn restore_review(&mut self, review_id: i64) -> LibroResult<()> {
+        self.recorded("restore review", |db| {
+            let book: Option<(i64, Option<String>)> = db
+                .conn
+                .query_row(
+                    "SELECT b.id, b.deleted_at FROM reviews r JOIN books b ON b.id = r.book_id
+                     WHERE r.id = ? AND r.deleted_at IS NOT NULL",
+                    params![review_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            match book {
+                None => Err(LibroError::ReviewIdNotFound { id: review_id }),
+                Some((book_id, Some(_))) => Err(book_in_trash(review_id, book_id)),
+                Some(_) => {
+                    db.conn.execute(
+                        "UPDATE reviews SET deleted_at = NULL WHERE id = ?",
+                        params![review_id],
+                    )?;
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    fn purge_trash(&mut self, cutoff: Option<NaiveDateTime>) -> LibroResult<Vec<TrashItem>> {
+        let purged: Vec<TrashItem> = self
+            .trash()?
+            .into_iter()
+            .filter(|item| item.deleted_before(cutoff))
+            .collect();
+
+        // 영구 삭제는 기록하지 않고, 기록에 남은 삭제된 행의 변경도 지웁니다
+        self.conn.execute_batch("SAVEPOINT purge")?;
+        match self.purge(&purged) {
+            Ok(()) => {
+                self.conn.execute_batch("RELEASE purge")?;
+                Ok(purged)
+            }
+            Err(e) => {
+                self.conn
+                    .execute_batch("ROLLBACK TO purge; RELEASE purge")?;
+                Err(e)
+            }
+        }
+    }
+}
+
+/// A `history_changes` row read from its `table_name`, `before` and `after`
+/// columns, starting at `first`
+fn row_change(row: &rusqlite::Row, first: usize) -> LibroResult<RowChange> {
+    let table_name: String = row.get(first)?;
+    let table = HistoryTable::from_name(&table_name).ok_or_else(|| {
+        LibroError::internal(format!("Unknown table '{}' in history", table_name))
+    })?;
+    let snapshot = |index: usize| -> LibroResult<Option<Value>> {
+        let json: Option<String> = row.get(index)?;
+        Ok(json.map(|json| serde_json::from_str(&json)).transpose()?)
+    };
+    Ok(RowChange {
+        table,
+        before: snapshot(first + 1)?,
+        after: snapshot(first + 2)?,
+    })
+}
+
+/// Find a writer by name (names are unique) or create it
+fn merge_writer(
+    tx: &Connection,
+    name: &str,
+    writer_type: &WriterType,
+    summary: &mut MergeSummary,
+) -> LibroResult<i64> {
+    let existing_id: Option<i64> = tx
+        .query_row(
+            "SELECT id FROM writers WHERE name = ?",
+            params![name],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    if let Some(id) = existing_id {
+        return Ok(id);
+    }
+
+    tx.execute(
+        "INSERT INTO writers (name, type) VALUES (?, ?)",
+        params![name, writer_type.as_str()],
+    )?;
+    summary.writers_created += 1;
+    Ok(tx.last_insert_rowid())
+}
+
+/// Insert a review copied from another database under a local book ID
+fn insert_merged_review(tx: &Connection, book_id: i64, review: &Review) -> LibroResult<()> {
+    let date_str = review.date_read.map(|d| d.format("%Y-%m-%d").to_string());
+    let (created_at, updated_at) =
+        stamps(review.created_at.as_deref(), review.updated_at.as_deref());
+    tx.execute(
+        "INSERT INTO reviews (book_id, date_read, rating, review, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?)",
+        params![
+            book_id,
+            date_str,
+            review.rating,
+            review.review,
+            created_at,
+            updated_at
+        ],
+    )?;
+    Ok(())
+}
+
+/// Read a `writers.type`/`book_writers.type` column into a `WriterType`
+fn writer_type_column(row: &rusqlite::Row, index: usize) -> rusqlite::Result<WriterType> {
+    let value: String = row.get(index)?;
+    WriterType::from_str(&value).map_err(|_| {
+        rusqlite::Error::InvalidColumnType(index, "type".to_string(), rusqlite::types::Type::Text)
+    })
+}
+
+/// A row as the history triggers would snapshot it, looked up by the key
+/// columns of `row`
+fn current_row(conn: &Connection, table: HistoryTable, row: &Value) -> LibroResult<Option<Value>> {
+    let fields: Vec<String> = table
+        .columns()
+        .iter()
+        .map(|column| format!("'{column}', {column}"))
+        .collect();
+    let sql = format!(
+        "SELECT json_object({}) FROM {} WHERE {}",
+        fields.join(", "),
+        table.as_str(),
+        key_condition(table)
+    );
+    let json: Option<String> = conn
+        .query_row(&sql, params_from_iter(key_values(table, row)), |r| r.get(0))
+        .optional()?;
+    Ok(json.map(|json| serde_json::from_str(&json)).transpose()?)
+}
+
+/// Put a row back the way it was before `change`
+fn revert_row(conn: &Connection, change: &RowChange) -> LibroResult<()> {
+    let table = change.table;
+    let columns = table.columns();
+    let values = |row: &Value| -> Vec<SqlValue> {
+        columns
+            .iter()
+            .map(|column| sql_value(&row[column]))
+            .collect()
+    };
+
+    match (&change.before, &change.after) {
+        (None, Some(after)) => {
+            let sql = format!(
+                "DELETE FROM {} WHERE {}",
+                table.as_str(),
+                key_condition(table)
+            );
+            conn.execute(&sql, params_from_iter(key_values(table, after)))?;
+        }
+        (Some(before), None) => {
+            let sql = format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                table.as_str(),
+                columns.join(", "),
+                vec!["?"; columns.len()].join(", ")
+            );
+            conn.execute(&sql, params_from_iter(values(before)))?;
+        }
+        (Some(before), Some(after)) => {
+            let assignments: Vec<String> = columns.iter().map(|c| format!("{c} = ?")).collect();
+            let sql = format!(
+                "UPDATE {} SET {} WHERE {}",
+                table.as_str(),
+                assignments.join(", "),
+                key_condition(table)
+            );
+            let mut params = values(before);
+            params.extend(key_values(table, after));
+            conn.execute(&sql, params_from_iter(params))?;
+        }
+        (None, None) => {}
+    }
+    Ok(())
+}
+
+fn key_condition(table: HistoryTable) -> String {
+    table
+        .key_columns()
+        .iter()
+        .map(|column| format!("{column} = ?"))
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+fn key_values(table: HistoryTable, row: &Value) -> Vec<SqlValue> {
+    table
+        .key_columns()
+        .iter()
+        .map(|column| sql_value(&row[column]))
+        .collect()
+}
+
+fn sql_value(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(i64::from(*b)),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => SqlValue::Text(s.clone()),
+        other => SqlValue::Text(other.to_string()),
+    }
+}
@@ -0,0 +1,386 @@
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::errors::{validation::max_rating, LibroResult};
+
+/// Group of checks run by `doctor`, in report order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckCategory {
+    /// `PRAGMA integrity_check`
+    Integrity,
+    /// `PRAGMA foreign_key_check`
+    ForeignKeys,
+    /// Writers not linked to any book
+    OrphanedWriters,
+    /// Reviews without a rating or rated outside the rating scale
+    InvalidRatings,
+    /// Books with no author linked
+    BooksWithoutAuthors,
+    /// The same genre spelled in different ways
+    GenreSpellings,
+}
+
+impl CheckCategory {
+    pub const ALL: [CheckCategory; 6] = [
+        CheckCategory::Integrity,
+        CheckCategory::ForeignKeys,
+        CheckCategory::OrphanedWriters,
+        CheckCategory::InvalidRatings,
+        CheckCategory::BooksWithoutAuthors,
+        CheckCategory::GenreSpellings,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            CheckCategory::Integrity => "Integrity",
+            CheckCategory::ForeignKeys => "Foreign keys",
+            CheckCategory::OrphanedWriters => "Writers without books",
+            CheckCategory::InvalidRatings => "Reviews with invalid ratings",
+            CheckCategory::BooksWithoutAuthors => "Books without authors",
+            CheckCategory::GenreSpellings => "Genre spellings",
+        }
+    }
+}
+
+/// A single problem found by `diagnose`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Issue {
+    pub category: CheckCategory,
+    pub message: String,
+    /// Whether `repair` fixes this issue
+    pub fixable: bool,
+}
+
+/// What `repair` changed
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RepairSummary {
+    pub dangling_links_removed: usize,
+    pub orphaned_writers_removed: usize,
+    pub genres_renamed: usize,
+}
+
+impl RepairSummary {
+    pub fn total(&self) -> usize {
+        self.dangling_links_removed + self.orphaned_writers_removed + self.genres_renamed
+    }
+}
+
+/// Run every check and list the problems found, grouped by category
+pub fn diagnose(conn: &Connection) -> LibroResult<Vec<Issue>> {
+    let mut issues = Vec::new();
+    check_integrity(conn, &mut issues)?;
+    check_foreign_keys(conn, &mut issues)?;
+    check_orphaned_writers(conn, &mut issues)?;
+    check_ratings(conn, &mut issues)?;
+    check_books_without_authors(conn, &mut issues)?;
+    check_genres(conn, &mut issues)?;
+    Ok(issues)
+}
+
+/// Apply the safe repairs in a single transaction.
+///
+/// Only changes that lose no information are made: links pointing at missing
+/// books or writers and writers without books are removed, and genre
+/// spellings are unified to the most common one. Everything else needs a
+/// person to decide and is left alone.
+pub fn repair(conn: &mut Connection) -> LibroResult<RepairSummary> {
+    let tx = conn.transaction()?;
+
+    let dangling_links_removed = tx.execute(
+        "DELETE FROM book_writers
+         WHERE book_id NOT IN (SELECT id FROM books)
+            OR writer_id NOT IN (SELECT id FROM writers)",
+        [],
+    )?;
+
+    let orphaned_writers_removed = tx.execute(
+        "DELETE FROM writers WHERE id NOT IN (SELECT writer_id FROM book_writers)",
+        [],
+    )?;
+
+    let mut summary = RepairSummary {
+        dangling_links_removed,
+        orphaned_writers_removed,
+        genres_renamed: 0,
+    };
+
+    for (canonical, variants) in genre_variants(&tx)? {
+        for variant in variants {
+            summary.genres_renamed += tx.execute(
+                "UPDATE books SET genre = ? WHERE genre = ?",
+                params![canonical, variant],
+            )?;
+        }
+    }
+
+    tx.commit()?;
+    Ok(summary)
+}
+
+fn check_integrity(conn: &Connection, issues: &mut Vec<Issue>) -> LibroResult<()> {
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    for row in rows {
+        let message = row?;
+        if message != "ok" {
+            issues.push(Issue {
+                category: CheckCategory::Integrity,
+                message,
+                fixable: false,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn check_foreign_keys(conn: &Connection, issues: &mut Vec<Issue>) -> LibroResult<()> {
+    let mut stmt = conn.prepare("PRAGMA foreign_key_check")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<i64>>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+    for row in rows {
+        let (table, rowid, parent) = row?;
+        let row = rowid.map_or_else(|| "a row".to_string(), |id| format!("row {}", id));
+        issues.push(Issue {
+            category: CheckCategory::ForeignKeys,
+            message: format!("{} {} points at a missing row in {}", table, row, parent),
+            // Dangling reviews would lose text, so only links are removed
+            fixable: table == "book_writers",
+        });
+    }
+    Ok(())
+}
+
+fn check_orphaned_writers(conn: &Connection, issues: &mut Vec<Issue>) -> LibroResult<()> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name FROM writers
+         WHERE id NOT IN (SELECT writer_id FROM book_writers)
+         ORDER BY name",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for row in rows {
+        let (id, name) = row?;
+        issues.push(Issue {
+            category: CheckCategory::OrphanedWriters,
+            message: format!("Writer #{} '{}' has no books", id, name),
+            fixable: true,
+        });
+    }
+    Ok(())
+}
+
+fn check_ratings(conn: &Connection, issues: &mut Vec<Issue>) -> LibroResult<()> {
+    let mut stmt = conn.prepare(
+        "SELECT r.id, r.rating, b.title
+         FROM reviews r LEFT JOIN books b ON b.id = r.book_id
+         WHERE r.rating IS NULL OR r.rating < 1 OR r.rating > ?
+         ORDER BY r.id",
+    )?;
+    let rows = stmt.query_map([max_rating()], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<i64>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+        ))
+    })?;
+    for row in rows {
+        let (id, rating, title) = row?;
+        let title = title.unwrap_or_else(|| "a missing book".to_string());
+        let message = match rating {
+            None => format!("Review #{} of '{}' has no rating", id, title),
+            Some(rating) => format!(
+                "Review #{} of '{}' is rated {} outside 1-{}",
+                id,
+                title,
+                rating,
+                max_rating()
+            ),
+        };
+        issues.push(Issue {
+            category: CheckCategory::InvalidRatings,
+            message,
+            fixable: false,
+        });
+    }
+    Ok(())
+}
+
+fn check_books_without_authors(conn: &Connection, issues: &mut Vec<Issue>) -> LibroResult<()> {
+    let mut stmt = conn.prepare(
+        "SELECT b.id, b.title FROM books b
+         WHERE NOT EXISTS (
+             SELECT 1 FROM book_writers bw
+             JOIN writers w ON w.id = bw.writer_id
+             WHERE bw.book_id = b.id AND bw.type = 'author'
+         )
+         ORDER BY b.id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for row in rows {
+        let (id, title) = row?;
+        issues.push(Issue {
+            category: CheckCategory::BooksWithoutAuthors,
+            message: format!("Book #{} '{}' has no author", id, title),
+            fixable: false,
+        });
+    }
+    Ok(())
+}
+
+fn check_genres(conn: &Connection, issues: &mut Vec<Issue>) -> LibroResult<()> {
+    for (canonical, variants) in genre_variants(conn)? {
+        let quoted: Vec<String> = variants.iter().map(|v| format!("'{}'", v)).collect();
+        issues.push(Issue {
+            category: CheckCategory::GenreSpellings,
+            message: format!("{} should be '{}'", quoted.join(", "), canonical),
+            fixable: true,
+        });
+    }
+    Ok(())
+}
+
+/// Genres that differ only in case or spacing: the most used spelling and the
+/// other spellings that should become it
+fn genre_variants(conn: &Connection) -> LibroResult<Vec<(String, Vec<String>)>> {
+    let mut stmt = conn.prepare("SELECT genre, COUNT(*) FROM books GROUP BY genre")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    })?;
+
+    let mut groups: BTreeMap<String, Vec<(String, i64)>> = BTreeMap::new();
+    for row in rows {
+        let (genre, count) = row?;
+        groups
+            .entry(genre_key(&genre))
+            .or_default()
+            .push((genre, count));
+    }
+
+    let mut variants = Vec::new();
+    for (key, mut spellings) in groups {
+        // A lone spelling with stray spaces still needs trimming
+        if spellings.len() == 1 && spellings[0].0 == spellings[0].0.trim() {
+            continue;
+        }
+        if key.is_empty() {
+            continue;
+        }
+        // Most used first; ties go to the tidiest, then alphabetical spelling
+        spellings.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then((a.0 != a.0.trim()).cmp(&(b.0 != b.0.trim())))
+                .then(a.0.cmp(&b.0))
+        });
+        let canonical = spellings[0].0.trim().to_string();
+        let others: Vec<String> = spellings
+            .into_iter()
+            .map(|(genre, _)| genre)
+            .filter(|genre| *genre != canonical)
+            .collect();
+        variants.push((canonical, others));
+    }
+    Ok(variants)
+}
+
+/// Case- and whitespace-insensitive key for a genre
+fn genre_key(genre: &str) -> String {
+    genre
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    fn seeded() -> Connection {
+        let conn = init_db(":memory:").unwrap();
+        conn.execute_batch(
+            "
+            INSERT INTO books (id, title, genre) VALUES
+                (1, 'Solaris', 'Science Fiction'),
+                (2, 'Dune', 'science fiction'),
+                (3, 'Hyperion', 'Science Fiction'),
+                (4, 'Anonymous', ' Poetry ');
+            INSERT INTO writers (id, name, type) VALUES
+                (1, 'Stanisław Lem', 'author'),
+                (2, 'Frank Herbert', 'author'),
+                (3, 'Dan Simmons', 'author'),
+                (4, 'Nobody', 'author');
+            INSERT INTO book_writers (book_id, writer_id, type) VALUES
+                (1, 1, 'author'), (2, 2, 'author'), (3, 3, 'author');
+            INSERT INTO reviews (book_id, date_read, rating, review) VALUES
+                (1, '2024-01-01', 5, 'Great'),
+                (2, '2024-01-02', NULL, 'No rating'),
+                (3, '2024-01-03', 9, 'Off the scale');
+            PRAGMA foreign_keys = OFF;
+            INSERT INTO book_writers (book_id, writer_id, type) VALUES (99, 1, 'author');
+            ",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn count(issues: &[Issue], category: CheckCategory) -> usize {
+        issues.iter().filter(|i| i.category == category).count()
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let conn = seeded();
+        let issues = diagnose(&conn).unwrap();
+
+        assert_eq!(count(&issues, CheckCategory::Integrity), 0);
+        assert_eq!(count(&issues, CheckCategory::ForeignKeys), 1);
+        assert_eq!(count(&issues, CheckCategory::OrphanedWriters), 1);
+        assert_eq!(count(&issues, CheckCategory::InvalidRatings), 2);
+        assert_eq!(count(&issues, CheckCategory::BooksWithoutAuthors), 1);
+        assert_eq!(count(&issues, CheckCategory::GenreSpellings), 2);
+
+        let genre = issues
+            .iter()
+            .find(|i| i.message.contains("'science fiction'"))
+            .unwrap();
+        assert_eq!(
+            genre.message,
+            "'science fiction' should be 'Science Fiction'"
+        );
+    }
+
+    #[test]
+    fn test_repair_fixes_only_safe_issues() {
+        let mut conn = seeded();
+        let summary = repair(&mut conn).unwrap();
+        assert_eq!(
+            summary,
+            RepairSummary {
+                dangling_links_removed: 1,
+                orphaned_writers_removed: 1,
+                genres_renamed: 2,
+            }
+        );
+
+        let remaining = diagnose(&conn).unwrap();
+        assert!(remaining.iter().all(|issue| !issue.fixable));
+        assert_eq!(count(&remaining, CheckCategory::InvalidRatings), 2);
+        assert_eq!(count(&remaining, CheckCategory::BooksWithoutAuthors), 1);
+
+        let poetry: String = conn
+            .query_row("SELECT genre FROM books WHERE id = 4", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(poetry, "Poetry");
+    }
+}
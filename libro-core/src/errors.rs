@@ -0,0 +1,227 @@
+use thiserror::Error;
+
+/// Main error type for libro
+#[derive(Error, Debug)]
+pub enum LibroError {
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("Validation error: {message}")]
+    Validation {
+        message: String,
+        /// Field that failed, when the error is about a single field
+        field: Option<String>,
+        /// Rule the value broke, such as `required` or `range:1-5`
+        constraint: Option<String>,
+    },
+
+    #[error("Book not found with ID: {id}")]
+    BookNotFound { id: i64 },
+
+    #[error("Review not found for book ID: {book_id}")]
+    ReviewNotFound { book_id: i64 },
+
+    #[error("Review not found with ID: {id}")]
+    ReviewIdNotFound { id: i64 },
+
+    #[error("Writer not found: {name}")]
+    WriterNotFound { name: String },
+
+    #[error("Invalid input: {message}")]
+    InvalidInput { message: String },
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Date parsing error: {0}")]
+    DateParse(#[from] chrono::ParseError),
+
+    #[error("JSON serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("User cancelled operation")]
+    UserCancelled,
+
+    #[error("Internal error: {message}")]
+    Internal { message: String },
+}
+
+/// Result type alias for convenience
+pub type LibroResult<T> = Result<T, LibroError>;
+
+impl LibroError {
+    /// Create a validation error
+    pub fn validation(message: impl Into<String>) -> Self {
+        LibroError::Validation {
+            message: message.into(),
+            field: None,
+            constraint: None,
+        }
+    }
+
+    /// Create a validation error for one field and the constraint it broke
+    pub fn invalid_field(
+        field: impl Into<String>,
+        constraint: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        LibroError::Validation {
+            message: message.into(),
+            field: Some(field.into()),
+            constraint: Some(constraint.into()),
+        }
+    }
+
+    /// Create an invalid input error
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        LibroError::InvalidInput {
+            message: message.into(),
+        }
+    }
+
+    /// Create an internal error
+    pub fn internal(message: impl Into<String>) -> Self {
+        LibroError::Internal {
+            message: message.into(),
+        }
+    }
+
+    /// Stable snake_case error code for scripts.
+    ///
+    /// Codes are part of the CLI's interface: new ones may be added, but
+    /// existing codes keep their meaning.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LibroError::Database(error) if is_locked(error) => "database_locked",
+            LibroError::Database(_) => "database",
+            LibroError::Validation { .. } => "validation",
+            LibroError::BookNotFound { .. } => "book_not_found",
+            LibroError::ReviewNotFound { .. } => "review_not_found",
+            LibroError::ReviewIdNotFound { .. } => "review_not_found",
+            LibroError::WriterNotFound { .. } => "writer_not_found",
+            LibroError::InvalidInput { .. } => "invalid_input",
+            LibroError::Io(_) => "io",
+            LibroError::DateParse(_) => "date_parse",
+            LibroError::Json(_) => "json",
+            LibroError::UserCancelled => "cancelled",
+            LibroError::Internal { .. } => "internal",
+        }
+    }
+
+    /// Check if this error is user-recoverable
+    pub fn is_user_error(&self) -> bool {
+        matches!(
+            self,
+            LibroError::Validation { .. }
+                | LibroError::InvalidInput { .. }
+                | LibroError::BookNotFound { .. }
+                | LibroError::ReviewNotFound { .. }
+                | LibroError::ReviewIdNotFound { .. }
+                | LibroError::WriterNotFound { .. }
+                | LibroError::UserCancelled
+        )
+    }
+}
+
+/// Whether SQLite gave up because another connection holds the database
+fn is_locked(error: &rusqlite::Error) -> bool {
+    matches!(
+        error.sqlite_error_code(),
+        Some(rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked)
+    )
+}
+
+/// Validation helper functions
+pub mod validation {
+    use super::LibroError;
+    use chrono::Datelike;
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    static MAX_RATING: AtomicI32 = AtomicI32::new(5);
+
+    /// Highest rating accepted by `validate_rating`
+    pub fn max_rating() -> i32 {
+        MAX_RATING.load(Ordering::Relaxed)
+    }
+
+    /// Change the rating scale (from the config file)
+    pub fn set_max_rating(max: i32) {
+        MAX_RATING.store(max, Ordering::Relaxed);
+    }
+
+    /// Field identifier for a display name: "Review text" becomes `review_text`
+    fn field_id(field_name: &str) -> String {
+        field_name.trim().to_lowercase().replace(' ', "_")
+    }
+
+    /// Validate that a string is not empty
+    pub fn validate_non_empty(value: &str, field_name: &str) -> Result<(), LibroError> {
+        if value.trim().is_empty() {
+            Err(LibroError::invalid_field(
+                field_id(field_name),
+                "required",
+                format!("{} cannot be empty", field_name),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Validate rating is within the rating scale (1-5 by default)
+    pub fn validate_rating(rating: i32) -> Result<(), LibroError> {
+        if !(1..=max_rating()).contains(&rating) {
+            Err(LibroError::invalid_field(
+                "rating",
+                format!("range:1-{}", max_rating()),
+                format!("Rating must be between 1 and {}", max_rating()),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Validate year is reasonable
+    pub fn validate_year(year: i32) -> Result<(), LibroError> {
+        let current_year = chrono::Utc::now().year();
+        if year < 1000 || year > current_year + 10 {
+            Err(LibroError::invalid_field(
+                "year",
+                format!("range:1000-{}", current_year + 10),
+                format!("Year must be between 1000 and {}", current_year + 10),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Validate pages is positive
+    pub fn validate_pages(pages: i32) -> Result<(), LibroError> {
+        if pages <= 0 {
+            Err(LibroError::invalid_field(
+                "pages",
+                "positive",
+                "Pages must be a positive number",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// Additional From implementations for external crates
+#[cfg(feature = "dialoguer")]
+impl From<dialoguer::Error> for LibroError {
+    fn from(error: dialoguer::Error) -> Self {
+        match error {
+            dialoguer::Error::IO(io_error) => LibroError::Io(io_error),
+            #[allow(unreachable_patterns)]
+            _ => LibroError::UserCancelled,
+        }
+    }
+}
+
+impl From<std::num::ParseIntError> for LibroError {
+    fn from(error: std::num::ParseIntError) -> Self {
+        LibroError::invalid_input(format!("Invalid number: {}", error))
+    }
+}
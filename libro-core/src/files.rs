@@ -0,0 +1,656 @@
+//! Plain-text library store: one TOML file per book in a directory.
+//!
+//! ```text
+//! library.toml          format, version and the last IDs handed out
+//! writers.toml          every writer with its ID and type
+//! books/0001-dune.toml  a book, its writers by name and its reviews, with
+//!                       `created_at`/`updated_at`; trashed books and
+//!                       reviews carry `deleted_at`
+//! history.jsonl         one change history entry per line, oldest first
+//! ```
+//!
+//! Files are sorted and only rewritten when their contents change, so the
+//! directory diffs cleanly under version control.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::archive::{Archive, ImportSummary, ReviewRecord};
+use crate::errors::{LibroError, LibroResult};
+use crate::history::HistoryEntry;
+use crate::memory::{MemoryStore, Sequences};
+use crate::merge::{MergePlan, MergeSummary};
+use crate::models::*;
+use crate::store::LibraryStore;
+use crate::trash::TrashItem;
+use chrono::NaiveDateTime;
+
+/// Format name written to `library.toml`
+pub const FILES_FORMAT: &str = "libro-files";
+
+/// Layout version written to `library.toml`
+pub const FILES_VERSION: u32 = 1;
+
+const LIBRARY_FILE: &str = "library.toml";
+const WRITERS_FILE: &str = "writers.toml";
+const BOOKS_DIR: &str = "books";
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// A [`LibraryStore`] kept as TOML files in a directory.
+///
+/// The whole library is loaded into a [`MemoryStore`] on open. Every change
+/// is applied to a copy first and the files are written before the copy
+/// replaces the loaded library, so a failed write leaves both unchanged.
+#[derive(Debug)]
+pub struct FileStore {
+    dir: PathBuf,
+    store: MemoryStore,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LibraryFile {
+    format: String,
+    version: u32,
+    sequences: Sequences,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WritersFile {
+    #[serde(default)]
+    writers: Vec<WriterEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WriterEntry {
+    id: i64,
+    name: String,
+    #[serde(rename = "type")]
+    writer_type: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BookFile {
+    id: i64,
+    title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pages: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub_year: Option<i32>,
+    genre: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_at: Option<String>,
+    #[serde(default)]
+    authors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    translators: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    reviews: Vec<ReviewEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deleted_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReviewEntry {
+    id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date_read: Option<String>,
+    rating: i32,
+    review: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deleted_at: Option<String>,
+}
+
+impl FileStore {
+    /// Open the library in `dir`, creating it if the directory is missing or
+    /// empty. A non-empty directory without `library.toml` is refused, so a
+    /// wrong path never gets library files scattered into it.
+    pub fn open(dir: impl AsRef<Path>) -> LibroResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        if !dir.join(LIBRARY_FILE).is_file() {
+            if dir.exists() && fs::read_dir(&dir)?.next().is_some() {
+                return Err(LibroError::invalid_input(format!(
+                    "{} is not empty and has no {}",
+                    dir.display(),
+                    LIBRARY_FILE
+                )));
+            }
+            let store = MemoryStore::new();
+            write_files(&dir, &store)?;
+            return Ok(Self { dir, store });
+        }
+
+        let store = read_files(&dir)?;
+        Ok(Self { dir, store })
+    }
+
+    /// Whether `dir` holds a library written by this store
+    pub fn exists(dir: impl AsRef<Path>) -> bool {
+        dir.as_ref().join(LIBRARY_FILE).is_file()
+    }
+
+    /// Directory the library lives in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Apply `change` to a copy of the library, write it, then keep it
+    fn change<T>(
+        &mut self,
+        change: impl FnOnce(&mut MemoryStore) -> LibroResult<T>,
+    ) -> LibroResult<T> {
+        let mut next = self.store.clone();
+        let value = change(&mut next)?;
+        write_files(&self.dir, &next)?;
+        self.store = next;
+        Ok(value)
+    }
+}
+
+impl LibraryStore for FileStore {
+    fn get_or_add_writer(&mut self, name: &str, writer_type: WriterType) -> LibroResult<i64> {
+        self.change(|store| store.get_or_add_writer(name, writer_type))
+    }
+
+    fn add_book_writer_link(
+        &mut self,
+        book_id: i64,
+        writer_id: i64,
+        writer_type: WriterType,
+    ) -> LibroResult<()> {
+        self.change(|store| store.add_book_writer_link(book_id, writer_id, writer_type))
+    }
+
+    fn add_book(&mut self, book: &NewBook) -> LibroResult<i64> {
+        self.change(|store| store.add_book(book))
+    }
+
+    fn add_review(&mut self, review: &NewReview) -> LibroResult<i64> {
+        self.change(|store| store.add_review(review))
+    }
+
+    fn add_book_with_review(
+        &mut self,
+        input: &NewBookWithReview,
+    ) -> LibroResult<BookCreationResult> {
+        self.change(|store| store.add_book_with_review(input))
+    }
+
+    fn get_books(&self, filter: &BookFilter) -> LibroResult<Vec<ExtendedBook>> {
+        self.store.get_books(filter)
+    }
+
+    fn get_book_writers(&self, book_id: i64) -> LibroResult<Vec<Writer>> {
+        self.store.get_book_writers(book_id)
+    }
+
+    fn get_reviews(&self, book_id: i64) -> LibroResult<Vec<Review>> {
+        self.store.get_reviews(book_id)
+    }
+
+    fn update_book(&mut self, book_id: i64, updates: &Book) -> LibroResult<()> {
+        self.change(|store| store.update_book(book_id, updates))
+    }
+
+    fn update_review(&mut self, review_id: i64, updates: &Review) -> LibroResult<()> {
+        self.change(|store| store.update_review(review_id, updates))
+    }
+
+    fn delete_book(&mut self, book_id: i64) -> LibroResult<()> {
+        self.change(|store| store.delete_book(book_id))
+    }
+
+    fn orphaned_writers_for_book(&self, book_id: i64) -> LibroResult<Vec<Writer>> {
+        self.store.orphaned_writers_for_book(book_id)
+    }
+
+    fn get_review(&self, review_id: i64) -> LibroResult<Review> {
+        self.store.get_review(review_id)
+    }
+
+    fn delete_review(&mut self, review_id: i64) -> LibroResult<()> {
+        self.change(|store| store.delete_review(review_id))
+    }
+
+    fn export_archive(&self) -> LibroResult<Archive> {
+        self.store.export_archive()
+    }
+
+    fn import_archive(&mut self, archive: &Archive) -> LibroResult<ImportSummary> {
+        self.change(|store| store.import_archive(archive))
+    }
+
+    fn load_archive(&mut self, archive: &Archive) -> LibroResult<()> {
+        self.change(|store| store.load_archive(archive))
+    }
+
+    fn get_writers(&self) -> LibroResult<Vec<Writer>> {
+        self.store.get_writers()
+    }
+
+    fn apply_merge(&mut self, plan: &MergePlan) -> LibroResult<MergeSummary> {
+        self.change(|store| store.apply_merge(plan))
+    }
+
+    fn history(&self, book_id: Option<i64>) -> LibroResult<Vec<HistoryEntry>> {
+        self.store.history(book_id)
+    }
+
+    fn undo(&mut self, count: usize) -> LibroResult<Vec<HistoryEntry>> {
+        self.change(|store| store.undo(count))
+    }
+
+    fn trash(&self) -> LibroResult<Vec<TrashItem>> {
+        self.store.trash()
+    }
+
+    fn restore_book(&mut self, book_id: i64) -> LibroResult<()> {
+        self.change(|store| store.restore_book(book_id))
+    }
+
+    fn restore_review(&mut self, review_id: i64) -> LibroResult<()> {
+        self.change(|store| store.restore_review(review_id))
+    }
+
+    fn purge_trash(&mut self, cutoff: Option<NaiveDateTime>) -> LibroResult<Vec<TrashItem>> {
+        self.change(|store| store.purge_trash(cutoff))
+    }
+}
+
+/// Load every file in `dir` into a memory store
+fn read_files(dir: &Path) -> LibroResult<MemoryStore> {
+    let library: LibraryFile = read_toml(&dir.join(LIBRARY_FILE))?;
+    if library.format != FILES_FORMAT {
+        return Err(LibroError::invalid_input(format!(
+            "Unknown library format '{}' (expected '{}')",
+            library.format, FILES_FORMAT
+        )));
+    }
+    if library.version > FILES_VERSION {
+        return Err(LibroError::invalid_input(format!(
+            "Library version {} is newer than supported version {}",
+            library.version, FILES_VERSION
+        )));
+    }
+
+    let writers_path = dir.join(WRITERS_FILE);
+    let writers: WritersFile = if writers_path.is_file() {
+        read_toml(&writers_path)?
+    } else {
+        WritersFile::default()
+    };
+
+    let mut archive = Archive::new();
+    let mut writer_ids: HashMap<String, i64> = HashMap::new();
+    for entry in writers.writers {
+        let writer_type = WriterType::from_str(&entry.writer_type).map_err(|message| {
+            LibroError::invalid_input(format!("{}: {}", writers_path.display(), message))
+        })?;
+        writer_ids.insert(entry.name.clone(), entry.id);
+        archive.writers.push(Writer {
+            id: Some(entry.id),
+            name: entry.name,
+            writer_type,
+        });
+    }
+
+    for path in book_paths(dir)? {
+        let book: BookFile = read_toml(&path)?;
+        let links = book
+            .authors
+            .iter()
+            .map(|name| (name, WriterType::Author))
+            .chain(
+                book.translators
+                    .iter()
+                    .map(|name| (name, WriterType::Translator)),
+            );
+        for (name, writer_type) in links {
+            let writer_id = writer_ids.get(name).copied().ok_or_else(|| {
+                LibroError::invalid_input(format!(
+                    "{}: '{}' is not listed in {}",
+                    path.display(),
+                    name,
+                    WRITERS_FILE
+                ))
+            })?;
+            archive.book_writers.push(BookWriter {
+                book_id: book.id,
+                writer_id,
+                writer_type,
+            });
+        }
+        for review in book.reviews {
+            archive.reviews.push(ReviewRecord {
+                id: review.id,
+                book_id: book.id,
+                date_read: review.date_read,
+                rating: Some(review.rating),
+                review: Some(review.review),
+                created_at: review.created_at,
+                updated_at: review.updated_at,
+                deleted_at: review.deleted_at,
+            });
+        }
+        archive.books.push(Book {
+            id: Some(book.id),
+            title: book.title,
+            pages: book.pages,
+            pub_year: book.pub_year,
+            genre: book.genre,
+            created_at: book.created_at,
+            updated_at: book.updated_at,
+            deleted_at: book.deleted_at,
+        });
+    }
+
+    let mut history = Vec::new();
+    let history_path = dir.join(HISTORY_FILE);
+    if history_path.is_file() {
+        for (number, line) in fs::read_to_string(&history_path)?.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(line).map_err(|e| {
+                LibroError::invalid_input(format!(
+                    "{}:{}: {}",
+                    history_path.display(),
+                    number + 1,
+                    e
+                ))
+            })?;
+            history.push(entry);
+        }
+    }
+
+    let mut store = MemoryStore::new();
+    store.load_archive(&archive)?;
+    store.reserve_ids(library.sequences);
+    store.set_history(history);
+    Ok(store)
+}
+
+/// Write `store` into `dir`, touching only files whose contents changed
+fn write_files(dir: &Path, store: &MemoryStore) -> LibroResult<()> {
+    let archive = store.export_archive()?;
+    let books_dir = dir.join(BOOKS_DIR);
+    fs::create_dir_all(&books_dir)?;
+
+    let writer_names: HashMap<i64, &str> = archive
+        .writers
+        .iter()
+        .filter_map(|w| Some((w.id?, w.name.as_str())))
+        .collect();
+
+    let mut files: BTreeMap<PathBuf, BookFile> = BTreeMap::new();
+    let mut paths: HashMap<i64, PathBuf> = HashMap::new();
+    for book in &archive.books {
+        let id = book.id.unwrap_or_default();
+        let path = books_dir.join(format!("{:04}-{}.toml", id, slug(&book.title)));
+        paths.insert(id, path.clone());
+        files.insert(
+            path,
+            BookFile {
+                id,
+                title: book.title.clone(),
+                pages: book.pages,
+                pub_year: book.pub_year,
+                genre: book.genre.clone(),
+                created_at: book.created_at.clone(),
+                updated_at: book.updated_at.clone(),
+                authors: Vec::new(),
+                translators: Vec::new(),
+                reviews: Vec::new(),
+                deleted_at: book.deleted_at.clone(),
+            },
+        );
+    }
+    for link in &archive.book_writers {
+        let (Some(path), Some(name)) =
+            (paths.get(&link.book_id), writer_names.get(&link.writer_id))
+        else {
+            continue;
+        };
+        let book = files.get_mut(path).expect("path was just inserted");
+        match link.writer_type {
+            WriterType::Author => book.authors.push(name.to_string()),
+            WriterType::Translator => book.translators.push(name.to_string()),
+        }
+    }
+    for review in &archive.reviews {
+        let Some(path) = paths.get(&review.book_id) else {
+            continue;
+        };
+        files
+            .get_mut(path)
+            .expect("path was just inserted")
+            .reviews
+            .push(ReviewEntry {
+                id: review.id,
+                date_read: review.date_read.clone(),
+                rating: review.rating.unwrap_or_default(),
+                review: review.review.clone().unwrap_or_default(),
+                created_at: review.created_at.clone(),
+                updated_at: review.updated_at.clone(),
+                deleted_at: review.deleted_at.clone(),
+            });
+    }
+
+    for (path, book) in &files {
+        write_toml(path, book)?;
+    }
+    for path in book_paths(dir)? {
+        if !files.contains_key(&path) {
+            fs::remove_file(path)?;
+        }
+    }
+
+    let writers = WritersFile {
+        writers: archive
+            .writers
+            .iter()
+            .map(|w| WriterEntry {
+                id: w.id.unwrap_or_default(),
+                name: w.name.clone(),
+                writer_type: w.writer_type.as_str().to_string(),
+            })
+            .collect(),
+    };
+    write_toml(&dir.join(WRITERS_FILE), &writers)?;
+
+    let mut history = String::new();
+    for entry in store.history(None)?.iter().rev() {
+        history.push_str(&serde_json::to_string(entry)?);
+        history.push('\n');
+    }
+    write_text(&dir.join(HISTORY_FILE), &history)?;
+
+    // Written last: a directory is only a library once this file exists
+    let library = LibraryFile {
+        format: FILES_FORMAT.to_string(),
+        version: FILES_VERSION,
+        sequences: store.sequences(),
+    };
+    write_toml(&dir.join(LIBRARY_FILE), &library)
+}
+
+/// Book files in `dir`, sorted by name
+fn book_paths(dir: &Path) -> LibroResult<Vec<PathBuf>> {
+    let books_dir = dir.join(BOOKS_DIR);
+    if !books_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(books_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn read_toml<T: for<'de> Deserialize<'de>>(path: &Path) -> LibroResult<T> {
+    let content = fs::read_to_string(path)?;
+    toml::from_str(&content)
+        .map_err(|e| LibroError::invalid_input(format!("{}: {}", path.display(), e)))
+}
+
+fn write_toml<T: Serialize>(path: &Path, value: &T) -> LibroResult<()> {
+    let content = toml::to_string(value)
+        .map_err(|e| LibroError::internal(format!("{}: {}", path.display(), e)))?;
+    write_text(path, &content)
+}
+
+/// Write through a temporary file, skipping files that already match
+fn write_text(path: &Path, content: &str) -> LibroResult<()> {
+    if fs::read_to_string(path).is_ok_and(|old| old == content) {
+        return Ok(());
+    }
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    fs::write(&temp, content)?;
+    fs::rename(&temp, path)?;
+    Ok(())
+}
+
+/// Lowercase title with runs of other characters turned into single dashes
+fn slug(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.chars().count() >= 40 {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "book".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn new_book(title: &str, author: &str) -> NewBook {
+        NewBook {
+            title: title.to_string(),
+            authors: vec![author.to_string()],
+            translators: vec![],
+            pages: None,
+            pub_year: Some(1965),
+            genre: "SF".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(
+            slug("The Left Hand of Darkness"),
+            "the-left-hand-of-darkness"
+        );
+        assert_eq!(slug("  Dune: Messiah!  "), "dune-messiah");
+        assert_eq!(slug("채식주의자"), "채식주의자");
+        assert_eq!(slug("?!"), "book");
+    }
+
+    #[test]
+    fn test_changes_are_written_and_reloaded() {
+        let dir = TempDir::new().unwrap();
+        let mut store = FileStore::open(dir.path()).unwrap();
+        let dune = store.add_book(&new_book("Dune", "Frank Herbert")).unwrap();
+        let gone = store.add_book(&new_book("Gone", "Someone")).unwrap();
+        store.delete_book(gone).unwrap();
+
+        let book_file = dir.path().join("books/0001-dune.toml");
+        let content = fs::read_to_string(&book_file).unwrap();
+        assert!(content.contains("authors = [\"Frank Herbert\"]"));
+        let gone_file = dir.path().join("books/0002-gone.toml");
+        assert!(fs::read_to_string(&gone_file)
+            .unwrap()
+            .contains("deleted_at = "));
+
+        let mut reopened = FileStore::open(dir.path()).unwrap();
+        assert_eq!(reopened.trash().unwrap()[0].title, "Gone");
+        reopened.purge_trash(None).unwrap();
+        assert!(!gone_file.exists());
+        let books = reopened.get_books(&BookFilter::default()).unwrap();
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].authors[0].name, "Frank Herbert");
+
+        // The deleted book's ID stays used after reopening
+        let next = reopened
+            .add_book(&new_book("Next", "Frank Herbert"))
+            .unwrap();
+        assert_eq!((dune, next), (1, 3));
+    }
+
+    #[test]
+    fn test_refuses_unrelated_directory() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("notes.txt"), "hello").unwrap();
+        assert!(FileStore::open(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_unknown_writer_name_is_reported() {
+        let dir = TempDir::new().unwrap();
+        let mut store = FileStore::open(dir.path()).unwrap();
+        store.add_book(&new_book("Dune", "Frank Herbert")).unwrap();
+        let book_file = dir.path().join("books/0001-dune.toml");
+        let content = fs::read_to_string(&book_file).unwrap();
+        fs::write(&book_file, content.replace("Frank Herbert", "F. Herbert")).unwrap();
+
+        let error = FileStore::open(dir.path()).unwrap_err();
+        assert!(error.to_string().contains("'F. Herbert' is not listed"));
+    }
+
+    #[test]
+    fn test_undo_refuses_hand_edited_rows() {
+        let dir = TempDir::new().unwrap();
+        let mut store = FileStore::open(dir.path()).unwrap();
+        store.add_book(&new_book("Dune", "Frank Herbert")).unwrap();
+        store
+            .add_book(&new_book("Solaris", "Stanisław Lem"))
+            .unwrap();
+
+        let book_file = dir.path().join("books/0001-dune.toml");
+        let content = fs::read_to_string(&book_file).unwrap();
+        fs::write(&book_file, content.replace("\"Dune\"", "\"Dune Messiah\"")).unwrap();
+
+        // Both entries are undone or neither is
+        let mut reopened = FileStore::open(dir.path()).unwrap();
+        let error = reopened.undo(2).unwrap_err();
+        assert!(error.to_string().contains("Cannot undo #1 (add book)"));
+        assert_eq!(reopened.get_books(&BookFilter::default()).unwrap().len(), 2);
+        assert!(reopened.history(None).unwrap().iter().all(|e| !e.undone));
+
+        let undone = reopened.undo(1).unwrap();
+        assert_eq!(undone[0].book_title(2), Some("Solaris"));
+        let history = FileStore::open(dir.path()).unwrap().history(None).unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(history[0].undone);
+    }
+}
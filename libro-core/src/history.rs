@@ -0,0 +1,350 @@
+//! Change history kept by every backend, behind `history` and `undo`.
+//!
+//! Each mutation through a [`LibraryStore`](crate::store::LibraryStore) is
+//! recorded as one [`HistoryEntry`] holding a JSON snapshot of every row it
+//! touched, before and after. Snapshots use the SQLite column names, so all
+//! backends record the same entries for the same operations.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+use crate::errors::{LibroError, LibroResult};
+use crate::merge::DiffKind;
+
+/// Table a recorded row belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryTable {
+    Books,
+    Writers,
+    BookWriters,
+    Reviews,
+}
+
+impl HistoryTable {
+    /// Every table, parents before the rows that refer to them
+    pub const ALL: [HistoryTable; 4] = [
+        HistoryTable::Books,
+        HistoryTable::Writers,
+        HistoryTable::BookWriters,
+        HistoryTable::Reviews,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryTable::Books => "books",
+            HistoryTable::Writers => "writers",
+            HistoryTable::BookWriters => "book_writers",
+            HistoryTable::Reviews => "reviews",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|table| table.as_str() == name)
+    }
+
+    /// Columns kept in a row snapshot
+    pub fn columns(&self) -> &'static [&'static str] {
+        match self {
+            HistoryTable::Books => &[
+                "id",
+                "title",
+                "pages",
+                "pub_year",
+                "genre",
+                "deleted_at",
+                "created_at",
+                "updated_at",
+            ],
+            HistoryTable::Writers => &["id", "name", "type"],
+            HistoryTable::BookWriters => &["book_id", "writer_id", "type"],
+            HistoryTable::Reviews => &[
+                "id",
+                "book_id",
+                "date_read",
+                "rating",
+                "review",
+                "deleted_at",
+                "created_at",
+                "updated_at",
+            ],
+        }
+    }
+
+    /// Columns that identify a row
+    pub fn key_columns(&self) -> &'static [&'static str] {
+        match self {
+            HistoryTable::BookWriters => &["book_id", "writer_id", "type"],
+            _ => &["id"],
+        }
+    }
+
+    fn nouns(&self) -> (&'static str, &'static str) {
+        match self {
+            HistoryTable::Books => ("book", "books"),
+            HistoryTable::Writers => ("writer", "writers"),
+            HistoryTable::BookWriters => ("writer link", "writer links"),
+            HistoryTable::Reviews => ("review", "reviews"),
+        }
+    }
+}
+
+/// One row before and after a change; `None` means the row did not exist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RowChange {
+    pub table: HistoryTable,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl RowChange {
+    pub fn kind(&self) -> DiffKind {
+        match (&self.before, &self.after) {
+            (None, _) => DiffKind::Added,
+            (_, None) => DiffKind::Removed,
+            _ => DiffKind::Changed,
+        }
+    }
+
+    /// The snapshot that still identifies the row: after, or before for deletions
+    pub fn row(&self) -> &Value {
+        self.after
+            .as_ref()
+            .or(self.before.as_ref())
+            .unwrap_or(&Value::Null)
+    }
+
+    /// Book the row belongs to; writers belong to none
+    pub fn book_id(&self) -> Option<i64> {
+        let column = match self.table {
+            HistoryTable::Books => "id",
+            HistoryTable::BookWriters | HistoryTable::Reviews => "book_id",
+            HistoryTable::Writers => return None,
+        };
+        self.row()[column].as_i64()
+    }
+
+    /// This change with columns its `before` snapshot predates taken from
+    /// the row as it is now, so undoing it leaves them alone
+    pub(crate) fn filled_from(&self, current: Option<&Value>) -> RowChange {
+        let mut change = self.clone();
+        if let (Some(Value::Object(before)), Some(Value::Object(current))) =
+            (change.before.as_mut(), current)
+        {
+            for (column, value) in current {
+                before
+                    .entry(column.clone())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+        change
+    }
+}
+
+/// One recorded operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: i64,
+    /// What was done, such as "delete book"
+    pub operation: String,
+    /// Local time, `YYYY-MM-DD HH:MM:SS`
+    pub recorded_at: String,
+    pub undone: bool,
+    /// Row changes in the order they were made
+    pub changes: Vec<RowChange>,
+}
+
+impl HistoryEntry {
+    /// Books whose rows, reviews or writer links this entry changed
+    pub fn book_ids(&self) -> BTreeSet<i64> {
+        self.changes.iter().filter_map(RowChange::book_id).collect()
+    }
+
+    /// Whether `undo` can still revert this entry. An entry loses its
+    /// changes only when every row it recorded was purged from the trash.
+    pub fn can_undo(&self) -> bool {
+        !self.undone && !self.changes.is_empty()
+    }
+
+    pub fn touches_book(&self, book_id: i64) -> bool {
+        self.changes.iter().any(|c| c.book_id() == Some(book_id))
+    }
+
+    /// Title of a book as this entry recorded it, if it changed the book row
+    pub fn book_title(&self, book_id: i64) -> Option<&str> {
+        self.changes
+            .iter()
+            .filter(|c| c.table == HistoryTable::Books && c.book_id() == Some(book_id))
+            .find_map(|c| c.row()["title"].as_str())
+    }
+
+    /// Counts such as "1 book removed, 2 reviews removed"
+    pub fn describe_changes(&self) -> String {
+        if self.changes.is_empty() {
+            return "rows purged from the trash, cannot be undone".to_string();
+        }
+        let mut parts = Vec::new();
+        for table in HistoryTable::ALL {
+            for (kind, verb) in [
+                (DiffKind::Added, "added"),
+                (DiffKind::Changed, "changed"),
+                (DiffKind::Removed, "removed"),
+            ] {
+                let count = self
+                    .changes
+                    .iter()
+                    .filter(|c| c.table == table && c.kind() == kind)
+                    .count();
+                if count > 0 {
+                    let (one, many) = table.nouns();
+                    let noun = if count == 1 { one } else { many };
+                    parts.push(format!("{} {} {}", count, noun, verb));
+                }
+            }
+        }
+        parts.join(", ")
+    }
+}
+
+/// Rows a trash purge removed for good. Their changes are stripped from
+/// every entry, so a purge leaves neither a way back nor copies of the rows,
+/// while the rest of each entry can still be undone.
+#[derive(Debug, Default)]
+pub(crate) struct PurgedRows {
+    pub books: BTreeSet<i64>,
+    pub reviews: BTreeSet<i64>,
+    pub writers: BTreeSet<i64>,
+}
+
+impl PurgedRows {
+    /// Whether `change` is a snapshot of a purged row
+    pub fn recorded(&self, change: &RowChange) -> bool {
+        let id = change.row()["id"].as_i64();
+        change
+            .book_id()
+            .is_some_and(|book_id| self.books.contains(&book_id))
+            || match change.table {
+                HistoryTable::Reviews => id.is_some_and(|id| self.reviews.contains(&id)),
+                HistoryTable::Writers => id.is_some_and(|id| self.writers.contains(&id)),
+                HistoryTable::BookWriters => change.row()["writer_id"]
+                    .as_i64()
+                    .is_some_and(|id| self.writers.contains(&id)),
+                HistoryTable::Books => false,
+            }
+    }
+}
+
+/// Current local time in the `recorded_at` format
+pub fn timestamp() -> String {
+    chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// `created_at` and `updated_at` for a row copied from elsewhere: its own,
+/// or now when it has none
+pub(crate) fn stamps(created_at: Option<&str>, updated_at: Option<&str>) -> (String, String) {
+    let created_at = created_at.map_or_else(timestamp, str::to_string);
+    let updated_at = updated_at.map_or_else(|| created_at.clone(), str::to_string);
+    (created_at, updated_at)
+}
+
+/// The newest `count` entries that can still be undone, newest first;
+/// `history` must be newest first as well
+pub(crate) fn undo_candidates(
+    history: Vec<HistoryEntry>,
+    count: usize,
+) -> LibroResult<Vec<HistoryEntry>> {
+    let entries: Vec<HistoryEntry> = history
+        .into_iter()
+        .filter(HistoryEntry::can_undo)
+        .take(count)
+        .collect();
+    if entries.is_empty() {
+        return Err(LibroError::invalid_input("Nothing to undo"));
+    }
+    Ok(entries)
+}
+
+/// Whether a row's current snapshot matches a recorded one. Columns added
+/// after the entry was recorded are missing from it and are not compared.
+pub(crate) fn same_row(current: Option<&Value>, recorded: Option<&Value>) -> bool {
+    match (current, recorded) {
+        (Some(Value::Object(current)), Some(Value::Object(recorded))) => current
+            .iter()
+            .all(|(column, value)| recorded.get(column).is_none_or(|v| v == value)),
+        (current, recorded) => current == recorded,
+    }
+}
+
+/// Error for a row that no longer looks the way an entry left it
+pub(crate) fn changed_since(entry: &HistoryEntry, change: &RowChange) -> LibroError {
+    LibroError::invalid_input(format!(
+        "Cannot undo #{} ({}): a row in {} was changed outside the history",
+        entry.id,
+        entry.operation,
+        change.table.as_str()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(changes: Vec<RowChange>) -> HistoryEntry {
+        HistoryEntry {
+            id: 1,
+            operation: "delete book".to_string(),
+            recorded_at: "2024-03-01 12:00:00".to_string(),
+            undone: false,
+            changes,
+        }
+    }
+
+    #[test]
+    fn test_entry_books_and_description() {
+        let entry = entry(vec![
+            RowChange {
+                table: HistoryTable::Reviews,
+                before: Some(json!({"id": 4, "book_id": 2, "rating": 5})),
+                after: None,
+            },
+            RowChange {
+                table: HistoryTable::Writers,
+                before: Some(json!({"id": 3, "name": "Lem", "type": "author"})),
+                after: None,
+            },
+            RowChange {
+                table: HistoryTable::Books,
+                before: Some(json!({"id": 2, "title": "Solaris"})),
+                after: None,
+            },
+        ]);
+
+        assert_eq!(entry.book_ids(), BTreeSet::from([2]));
+        assert!(entry.touches_book(2));
+        assert!(!entry.touches_book(3));
+        assert_eq!(entry.book_title(2), Some("Solaris"));
+        assert_eq!(
+            entry.describe_changes(),
+            "1 book removed, 1 writer removed, 1 review removed"
+        );
+    }
+
+    #[test]
+    fn test_snapshots_older_than_a_column() {
+        let current = json!({"id": 2, "title": "Solaris", "created_at": "2024-03-01 12:00:00"});
+        let change = RowChange {
+            table: HistoryTable::Books,
+            before: Some(json!({"id": 2, "title": "Solyaris"})),
+            after: Some(json!({"id": 2, "title": "Solaris"})),
+        };
+
+        assert!(same_row(Some(&current), change.after.as_ref()));
+        assert!(!same_row(Some(&current), change.before.as_ref()));
+        assert_eq!(
+            change.filled_from(Some(&current)).before,
+            Some(json!({"id": 2, "title": "Solyaris", "created_at": "2024-03-01 12:00:00"}))
+        );
+    }
+}
@@ -0,0 +1,41 @@
+//! Core library behind `libro-cli`: book and review models, the
+//! [`LibraryStore`](store::LibraryStore) trait with SQLite
+//! ([`Database`](db_operations::Database)), plain-text
+//! ([`FileStore`](files::FileStore)) and in-memory
+//! ([`MemoryStore`](memory::MemoryStore)) backends, the change
+//! [`history`] behind undo, the [`trash`] behind soft deletes, validation
+//! errors, date helpers and the reading [`stats`] every report renders from.
+//!
+//! ```no_run
+//! use libro_core::db_operations::Database;
+//! use libro_core::models::BookFilter;
+//! use libro_core::stats::LibraryStats;
+//! use libro_core::store::LibraryStore;
+//!
+//! # fn main() -> libro_core::errors::LibroResult<()> {
+//! let db = Database::new("libro.db")?;
+//! let books = db.get_books(&BookFilter::default())?;
+//! let stats = LibraryStats::from_books(&books);
+//! println!("{} books read", stats.totals.total_books);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Enable the `dialoguer` feature to convert prompt errors into
+//! [`LibroError`](errors::LibroError) with `?`.
+
+pub mod archive;
+pub mod backup;
+pub mod date;
+pub mod db;
+pub mod db_operations;
+pub mod doctor;
+pub mod errors;
+pub mod files;
+pub mod history;
+pub mod memory;
+pub mod merge;
+pub mod models;
+pub mod stats;
+pub mod store;
+pub mod trash;
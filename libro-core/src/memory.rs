@@ -0,0 +1,1265 @@
+//! In-memory library store for tests and tools that never touch disk
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::archive::{archive_id, not_empty, remap, Archive, ImportSummary, ReviewRecord};
+use crate::errors::{validation, LibroError, LibroResult};
+use crate::history::{
+    changed_since, same_row, stamps, timestamp, undo_candidates, HistoryEntry, HistoryTable,
+    PurgedRows, RowChange,
+};
+use crate::merge::{MergePlan, MergeSummary};
+use crate::models::*;
+use crate::store::LibraryStore;
+use crate::trash::{book_in_trash, sort_trash, TrashItem, TrashKind};
+
+/// A [`LibraryStore`] kept entirely in memory.
+///
+/// It follows the SQLite schema's rules: IDs are never reused, writer names
+/// are unique, links need an existing book and writer, and multi-step
+/// operations either apply completely or not at all. Archive reviews must
+/// have a rating and text, since [`Review`] cannot hold the NULLs SQLite
+/// would store.
+///
+/// Each operation's history entry is found by comparing the tables before
+/// and after it.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    tables: Tables,
+    /// Oldest first
+    history: Vec<HistoryEntry>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Tables {
+    books: BTreeMap<i64, Book>,
+    writers: BTreeMap<i64, Writer>,
+    book_writers: BTreeSet<(i64, i64, WriterType)>,
+    reviews: BTreeMap<i64, Review>,
+    trash: TrashMarks,
+    sequences: Sequences,
+}
+
+/// When each trashed book and review was deleted
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct TrashMarks {
+    pub(crate) books: BTreeMap<i64, String>,
+    pub(crate) reviews: BTreeMap<i64, String>,
+}
+
+/// Last ID handed out for each table, so deleted IDs are never reused
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Sequences {
+    pub books: i64,
+    pub writers: i64,
+    pub reviews: i64,
+}
+
+impl Sequences {
+    /// The larger of each pair of sequence values
+    pub fn max(self, other: Sequences) -> Sequences {
+        Sequences {
+            books: self.books.max(other.books),
+            writers: self.writers.max(other.writers),
+            reviews: self.reviews.max(other.reviews),
+        }
+    }
+}
+
+impl MemoryStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Last IDs handed out so far
+    pub fn sequences(&self) -> Sequences {
+        self.tables.sequences
+    }
+
+    /// Never hand out IDs at or below `sequences`, even if those rows are gone
+    pub fn reserve_ids(&mut self, sequences: Sequences) {
+        self.tables.sequences = self.tables.sequences.max(sequences);
+    }
+
+    /// Replace the history, oldest entry first
+    pub(crate) fn set_history(&mut self, history: Vec<HistoryEntry>) {
+        self.history = history;
+    }
+
+    /// Run `change` on a copy of the tables and keep it only if it succeeds,
+    /// recording what it changed as one history entry
+    fn recorded<T>(
+        &mut self,
+        operation: &str,
+        change: impl FnOnce(&mut Tables) -> LibroResult<T>,
+    ) -> LibroResult<T> {
+        let mut tables = self.tables.clone();
+        let result = change(&mut tables)?;
+
+        let changes = changes_between(&self.tables, &tables);
+        if !changes.is_empty() {
+            self.history.push(HistoryEntry {
+                id: self.history.last().map_or(1, |entry| entry.id + 1),
+                operation: operation.to_string(),
+                recorded_at: timestamp(),
+                undone: false,
+                changes,
+            });
+        }
+        self.tables = tables;
+        Ok(result)
+    }
+}
+
+impl Tables {
+    /// Add a book under a new ID, stamped now unless it has timestamps
+    fn insert_book(&mut self, book: &Book) -> i64 {
+        self.sequences.books += 1;
+        let id = self.sequences.books;
+        let (created_at, updated_at) =
+            stamps(book.created_at.as_deref(), book.updated_at.as_deref());
+        self.books.insert(
+            id,
+            Book {
+                id: Some(id),
+                created_at: Some(created_at),
+                updated_at: Some(updated_at),
+                deleted_at: None,
+                ..book.clone()
+            },
+        );
+        id
+    }
+
+    fn insert_writer(&mut self, name: &str, writer_type: WriterType) -> LibroResult<i64> {
+        if self.writer_named(name).is_some() {
+            return Err(LibroError::invalid_field(
+                "name",
+                "unique",
+                format!("A writer named '{}' already exists", name),
+            ));
+        }
+        self.sequences.writers += 1;
+        let id = self.sequences.writers;
+        self.writers.insert(
+            id,
+            Writer {
+                id: Some(id),
+                name: name.to_string(),
+                writer_type,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Add a review under a new ID, stamped now unless it has timestamps
+    fn insert_review(&mut self, review: Review) -> LibroResult<i64> {
+        if !self.books.contains_key(&review.book_id) {
+            return Err(LibroError::BookNotFound { id: review.book_id });
+        }
+        self.sequences.reviews += 1;
+        let id = self.sequences.reviews;
+        let (created_at, updated_at) =
+            stamps(review.created_at.as_deref(), review.updated_at.as_deref());
+        self.reviews.insert(
+            id,
+            Review {
+                id: Some(id),
+                created_at: Some(created_at),
+                updated_at: Some(updated_at),
+                ..review
+            },
+        );
+        Ok(id)
+    }
+
+    fn link(&mut self, book_id: i64, writer_id: i64, writer_type: WriterType) -> LibroResult<()> {
+        if !self.books.contains_key(&book_id) {
+            return Err(LibroError::BookNotFound { id: book_id });
+        }
+        if !self.writers.contains_key(&writer_id) {
+            return Err(LibroError::invalid_input(format!(
+                "No writer with ID {}",
+                writer_id
+            )));
+        }
+        if !self.book_writers.insert((book_id, writer_id, writer_type)) {
+            return Err(LibroError::validation(format!(
+                "Writer {} is already linked to book {}",
+                writer_id, book_id
+            )));
+        }
+        Ok(())
+    }
+
+    fn writer_named(&self, name: &str) -> Option<i64> {
+        self.writers
+            .values()
+            .find(|w| w.name == name)
+            .and_then(|w| w.id)
+    }
+
+    fn get_or_add_writer(&mut self, name: &str, writer_type: WriterType) -> LibroResult<i64> {
+        let existing = self
+            .writers
+            .values()
+            .find(|w| w.name == name && w.writer_type == writer_type)
+            .and_then(|w| w.id);
+        match existing {
+            Some(id) => Ok(id),
+            None => self.insert_writer(name, writer_type),
+        }
+    }
+
+    /// A book that is not in the trash
+    fn live_book(&self, id: i64) -> Option<&Book> {
+        self.books
+            .get(&id)
+            .filter(|_| !self.trash.books.contains_key(&id))
+    }
+
+    /// A review that is neither trashed nor on a trashed book
+    fn live_review(&self, id: i64) -> Option<&Review> {
+        self.reviews.get(&id).filter(|review| {
+            !self.trash.reviews.contains_key(&id) && self.live_book(review.book_id).is_some()
+        })
+    }
+
+    /// A writer linked to a book outside the trash, or to no book at all
+    fn is_live_writer(&self, id: i64) -> bool {
+        let mut books = self
+            .book_writers
+            .iter()
+            .filter(|(_, w, _)| *w == id)
+            .map(|(b, _, _)| *b)
+            .peekable();
+        books.peek().is_none() || books.any(|b| self.live_book(b).is_some())
+    }
+
+    /// Every row as an archive, trashed ones with the time they were deleted
+    fn archive(&self) -> Archive {
+        let mut archive = Archive::new();
+        archive.books = self
+            .books
+            .iter()
+            .map(|(id, book)| Book {
+                deleted_at: self.trash.books.get(id).cloned(),
+                ..book.clone()
+            })
+            .collect();
+        archive.writers = self.writers.values().cloned().collect();
+        archive.book_writers = self
+            .book_writers
+            .iter()
+            .map(|(book_id, writer_id, writer_type)| BookWriter {
+                book_id: *book_id,
+                writer_id: *writer_id,
+                writer_type: writer_type.clone(),
+            })
+            .collect();
+        archive.reviews = self
+            .reviews
+            .iter()
+            .map(|(id, review)| ReviewRecord {
+                id: *id,
+                book_id: review.book_id,
+                date_read: review.date_read.map(|d| d.format("%Y-%m-%d").to_string()),
+                rating: Some(review.rating),
+                review: Some(review.review.clone()),
+                created_at: review.created_at.clone(),
+                updated_at: review.updated_at.clone(),
+                deleted_at: self.trash.reviews.get(id).cloned(),
+            })
+            .collect();
+        archive
+    }
+
+    fn is_empty(&self) -> bool {
+        self.books.is_empty() && self.writers.is_empty() && self.reviews.is_empty()
+    }
+
+    /// Insert every archive row under its own ID
+    fn load(&mut self, archive: &Archive) -> LibroResult<()> {
+        for writer in &archive.writers {
+            let id = archive_id(writer.id, "writer")?;
+            if self.writers.contains_key(&id) || self.writer_named(&writer.name).is_some() {
+                return Err(LibroError::invalid_input(format!(
+                    "Archive has writer {} '{}' twice",
+                    id, writer.name
+                )));
+            }
+            self.writers.insert(
+                id,
+                Writer {
+                    id: Some(id),
+                    ..writer.clone()
+                },
+            );
+            self.sequences.writers = self.sequences.writers.max(id);
+        }
+
+        for book in &archive.books {
+            let id = archive_id(book.id, "book")?;
+            if self.books.contains_key(&id) {
+                return Err(LibroError::invalid_input(format!(
+                    "Archive has book {} twice",
+                    id
+                )));
+            }
+            self.books.insert(
+                id,
+                Book {
+                    id: Some(id),
+                    deleted_at: None,
+                    ..book.clone()
+                },
+            );
+            if let Some(deleted_at) = &book.deleted_at {
+                self.trash.books.insert(id, deleted_at.clone());
+            }
+            self.sequences.books = self.sequences.books.max(id);
+        }
+
+        for link in &archive.book_writers {
+            self.link(link.book_id, link.writer_id, link.writer_type.clone())?;
+        }
+
+        for review in &archive.reviews {
+            if self.reviews.contains_key(&review.id) {
+                return Err(LibroError::invalid_input(format!(
+                    "Archive has review {} twice",
+                    review.id
+                )));
+            }
+            let (rating, text) = review_content(review)?;
+            if !self.books.contains_key(&review.book_id) {
+                return Err(LibroError::BookNotFound { id: review.book_id });
+            }
+            self.reviews.insert(
+                review.id,
+                Review {
+                    id: Some(review.id),
+                    book_id: review.book_id,
+                    date_read: parse_archive_date(review),
+                    rating,
+                    review: text.to_string(),
+                    created_at: review.created_at.clone(),
+                    updated_at: review.updated_at.clone(),
+                },
+            );
+            if let Some(deleted_at) = &review.deleted_at {
+                self.trash.reviews.insert(review.id, deleted_at.clone());
+            }
+            self.sequences.reviews = self.sequences.reviews.max(review.id);
+        }
+        Ok(())
+    }
+
+    /// Snapshots of every row in `table`, in key order
+    fn snapshots(&self, table: HistoryTable) -> Vec<Value> {
+        match table {
+            HistoryTable::Books => self.books.values().map(|b| self.book_row(b)).collect(),
+            HistoryTable::Writers => self.writers.values().map(writer_row).collect(),
+            HistoryTable::BookWriters => self.book_writers.iter().map(link_row).collect(),
+            HistoryTable::Reviews => self.reviews.values().map(|r| self.review_row(r)).collect(),
+        }
+    }
+
+    /// Snapshot of the row in `table` with the same key as `key`
+    fn row(&self, table: HistoryTable, key: &Value) -> Option<Value> {
+        let id = key["id"].as_i64();
+        match table {
+            HistoryTable::Books => self.books.get(&id?).map(|b| self.book_row(b)),
+            HistoryTable::Writers => self.writers.get(&id?).map(writer_row),
+            HistoryTable::BookWriters => {
+                let link = link_key(key)?;
+                self.book_writers.contains(&link).then(|| link_row(&link))
+            }
+            HistoryTable::Reviews => self.reviews.get(&id?).map(|r| self.review_row(r)),
+        }
+    }
+
+    /// Put a row back the way it was before `change`
+    fn revert(&mut self, change: &RowChange) -> LibroResult<()> {
+        let table = change.table;
+        if let Some(after) = &change.after {
+            let id = after["id"].as_i64().unwrap_or_default();
+            match table {
+                HistoryTable::Books => {
+                    self.books.remove(&id);
+                    self.trash.books.remove(&id);
+                }
+                HistoryTable::Writers => {
+                    self.writers.remove(&id);
+                }
+                HistoryTable::BookWriters => {
+                    if let Some(link) = link_key(after) {
+                        self.book_writers.remove(&link);
+                    }
+                }
+                HistoryTable::Reviews => {
+                    self.reviews.remove(&id);
+                    self.trash.reviews.remove(&id);
+                }
+            }
+        }
+
+        let Some(before) = change.before.clone() else {
+            return Ok(());
+        };
+        let deleted_at = before["deleted_at"].as_str().map(str::to_string);
+        match table {
+            HistoryTable::Books => {
+                let book: Book = serde_json::from_value(before)?;
+                let id = book.id.unwrap_or_default();
+                self.books.insert(id, book);
+                if let Some(deleted_at) = deleted_at {
+                    self.trash.books.insert(id, deleted_at);
+                }
+            }
+            HistoryTable::Writers => {
+                let (Some(id), Some(name), Some(writer_type)) = (
+                    before["id"].as_i64(),
+                    before["name"].as_str(),
+                    before["type"].as_str().and_then(|t| t.parse().ok()),
+                ) else {
+                    return Err(LibroError::internal("Malformed writer in history"));
+                };
+                self.writers.insert(
+                    id,
+                    Writer {
+                        id: Some(id),
+                        name: name.to_string(),
+                        writer_type,
+                    },
+                );
+            }
+            HistoryTable::BookWriters => {
+                let link = link_key(&before)
+                    .ok_or_else(|| LibroError::internal("Malformed writer link in history"))?;
+                self.book_writers.insert(link);
+            }
+            HistoryTable::Reviews => {
+                let review: Review = serde_json::from_value(before)?;
+                let id = review.id.unwrap_or_default();
+                self.reviews.insert(id, review);
+                if let Some(deleted_at) = deleted_at {
+                    self.trash.reviews.insert(id, deleted_at);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn book_row(&self, book: &Book) -> Value {
+        let id = book.id.unwrap_or_default();
+        json!({
+            "id": book.id,
+            "title": book.title,
+            "pages": book.pages,
+            "pub_year": book.pub_year,
+            "genre": book.genre,
+            "deleted_at": self.trash.books.get(&id),
+            "created_at": book.created_at,
+            "updated_at": book.updated_at,
+        })
+    }
+
+    fn review_row(&self, review: &Review) -> Value {
+        let id = review.id.unwrap_or_default();
+        json!({
+            "id": review.id,
+            "book_id": review.book_id,
+            "date_read": review.date_read.map(|d| d.format("%Y-%m-%d").to_string()),
+            "rating": review.rating,
+            "review": review.review,
+            "deleted_at": self.trash.reviews.get(&id),
+            "created_at": review.created_at,
+            "updated_at": review.updated_at,
+        })
+    }
+
+    /// Writer IDs linked to any book outside the trash other than `book_id`
+    fn writers_used_elsewhere(&self, book_id: i64) -> BTreeSet<i64> {
+        self.book_writers
+            .iter()
+            .filter(|(b, _, _)| *b != book_id && self.live_book(*b).is_some())
+            .map(|(_, w, _)| *w)
+            .collect()
+    }
+}
+
+impl LibraryStore for MemoryStore {
+    fn get_or_add_writer(&mut self, name: &str, writer_type: WriterType) -> LibroResult<i64> {
+        validation::validate_non_empty(name, "Writer name")?;
+        self.recorded("add writer", |tables| {
+            tables.get_or_add_writer(name, writer_type)
+        })
+    }
+
+    fn add_book_writer_link(
+        &mut self,
+        book_id: i64,
+        writer_id: i64,
+        writer_type: WriterType,
+    ) -> LibroResult<()> {
+        self.recorded("link writer", |tables| {
+            tables.link(book_id, writer_id, writer_type)
+        })
+    }
+
+    fn add_book(&mut self, book: &NewBook) -> LibroResult<i64> {
+        validation::validate_non_empty(&book.title, "Title")?;
+
+        if book.authors.is_empty() {
+            return Err(LibroError::invalid_field(
+                "authors",
+                "required",
+                "At least one author is required",
+            ));
+        }
+
+        if let Some(pages) = book.pages {
+            validation::validate_pages(pages)?;
+        }
+
+        if let Some(year) = book.pub_year {
+            validation::validate_year(year)?;
+        }
+
+        self.recorded("add book", |tables| {
+            let book_id = tables.insert_book(&Book {
+                id: None,
+                title: book.title.clone(),
+                pages: book.pages,
+                pub_year: book.pub_year,
+                genre: book.genre.clone(),
+                created_at: None,
+                updated_at: None,
+                deleted_at: None,
+            });
+
+            let writers = book
+                .authors
+                .iter()
+                .map(|name| (name, WriterType::Author))
+                .chain(
+                    book.translators
+                        .iter()
+                        .map(|name| (name, WriterType::Translator)),
+                );
+            for (name, writer_type) in writers {
+                validation::validate_non_empty(name, "Writer name")?;
+                let writer_id = tables.get_or_add_writer(name, writer_type.clone())?;
+                tables.link(book_id, writer_id, writer_type)?;
+            }
+
+            Ok(book_id)
+        })
+    }
+
+    fn add_review(&mut self, review: &NewReview) -> LibroResult<i64> {
+        validation::validate_rating(review.rating)?;
+        validation::validate_non_empty(&review.review, "Review text")?;
+
+        let date_read = review
+            .date_read
+            .unwrap_or_else(|| chrono::Utc::now().date_naive());
+        self.recorded("add review", |tables| {
+            if tables.live_book(review.book_id).is_none() {
+                return Err(LibroError::BookNotFound { id: review.book_id });
+            }
+            tables.insert_review(new_review(review.book_id, Some(date_read), review))
+        })
+    }
+
+    fn add_book_with_review(
+        &mut self,
+        input: &NewBookWithReview,
+    ) -> LibroResult<BookCreationResult> {
+        self.recorded("add book", |tables| {
+            let book_id = tables.insert_book(&Book {
+                id: None,
+                title: input.book.title.clone(),
+                pages: input.book.pages,
+                pub_year: input.book.pub_year,
+                genre: input.book.genre.clone(),
+                created_at: None,
+                updated_at: None,
+                deleted_at: None,
+            });
+
+            let writers = input
+                .book
+                .authors
+                .iter()
+                .map(|name| (name, WriterType::Author))
+                .chain(
+                    input
+                        .book
+                        .translators
+                        .iter()
+                        .map(|name| (name, WriterType::Translator)),
+                );
+            for (name, writer_type) in writers {
+                let writer_id = tables.get_or_add_writer(name, writer_type.clone())?;
+                tables.link(book_id, writer_id, writer_type)?;
+            }
+
+            let review_id = match &input.review {
+                Some(review) => {
+                    let date_read = review
+                        .date_read
+                        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+                    Some(tables.insert_review(new_review(book_id, Some(date_read), review))?)
+                }
+                None => None,
+            };
+
+            Ok(BookCreationResult { book_id, review_id })
+        })
+    }
+
+    fn get_books(&self, filter: &BookFilter) -> LibroResult<Vec<ExtendedBook>> {
+        let books = self.tables.books.values().filter(|book| {
+            if self
+                .tables
+                .trash
+                .books
+                .contains_key(&book.id.unwrap_or_default())
+            {
+                false
+            } else if let Some(id) = filter.id {
+                book.id == Some(id)
+            } else if let Some(year) = filter.year {
+                book.pub_year == Some(year)
+            } else {
+                true
+            }
+        });
+
+        let mut extended_books = Vec::new();
+        for book in books {
+            let book_id = book.id.unwrap_or_default();
+            let (authors, translators) = self
+                .get_book_writers(book_id)?
+                .into_iter()
+                .partition(|w| w.writer_type == WriterType::Author);
+            extended_books.push(ExtendedBook {
+                book: book.clone(),
+                authors,
+                translators,
+                reviews: self.get_reviews(book_id)?,
+            });
+        }
+        Ok(extended_books)
+    }
+
+    fn get_book_writers(&self, book_id: i64) -> LibroResult<Vec<Writer>> {
+        let mut writers: Vec<Writer> = self
+            .tables
+            .book_writers
+            .iter()
+            .filter(|(b, _, _)| *b == book_id)
+            .filter_map(|(_, w, _)| self.tables.writers.get(w).cloned())
+            .collect();
+        writers.sort_by(|a, b| {
+            a.writer_type
+                .cmp(&b.writer_type)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(writers)
+    }
+
+    fn get_reviews(&self, book_id: i64) -> LibroResult<Vec<Review>> {
+        let mut reviews: Vec<Review> = self
+            .tables
+            .reviews
+            .values()
+            .filter(|r| r.book_id == book_id)
+            .filter(|r| self.tables.live_review(r.id.unwrap_or_default()).is_some())
+            .cloned()
+            .collect();
+        // 날짜 없는 리뷰는 SQLite의 DESC 정렬처럼 마지막에
+        reviews.sort_by_key(|r| Reverse(r.date_read));
+        Ok(reviews)
+    }
+
+    fn update_book(&mut self, book_id: i64, updates: &Book) -> LibroResult<()> {
+        validation::validate_non_empty(&updates.title, "Title")?;
+
+        if let Some(pages) = updates.pages {
+            validation::validate_pages(pages)?;
+        }
+
+        if let Some(year) = updates.pub_year {
+            validation::validate_year(year)?;
+        }
+
+        self.recorded("update book", |tables| {
+            if tables.live_book(book_id).is_none() {
+                return Err(LibroError::BookNotFound { id: book_id });
+            }
+            let book = tables
+                .books
+                .get_mut(&book_id)
+                .ok_or(LibroError::BookNotFound { id: book_id })?;
+            *book = Book {
+                id: Some(book_id),
+                created_at: book.created_at.take(),
+                updated_at: Some(timestamp()),
+                deleted_at: None,
+                ..updates.clone()
+            };
+            Ok(())
+        })
+    }
+
+    fn update_review(&mut self, review_id: i64, updates: &Review) -> LibroResult<()> {
+        validation::validate_rating(updates.rating)?;
+        validation::validate_non_empty(&updates.review, "Review text")?;
+
+        self.recorded("update review", |tables| {
+            if tables.live_review(review_id).is_none() {
+                return Err(LibroError::ReviewIdNotFound { id: review_id });
+            }
+            let review = tables
+                .reviews
+                .get_mut(&review_id)
+                .ok_or(LibroError::ReviewIdNotFound { id: review_id })?;
+            review.date_read = updates.date_read;
+            review.rating = updates.rating;
+            review.review = updates.review.clone();
+            review.updated_at = Some(timestamp());
+            Ok(())
+        })
+    }
+
+    fn delete_book(&mut self, book_id: i64) -> LibroResult<()> {
+        self.recorded("delete book", |tables| {
+            if tables.live_book(book_id).is_none() {
+                return Err(LibroError::BookNotFound { id: book_id });
+            }
+            tables.trash.books.insert(book_id, timestamp());
+            Ok(())
+        })
+    }
+
+    fn orphaned_writers_for_book(&self, book_id: i64) -> LibroResult<Vec<Writer>> {
+        let elsewhere = self.tables.writers_used_elsewhere(book_id);
+        let ids: BTreeSet<i64> = self
+            .tables
+            .book_writers
+            .iter()
+            .filter(|(b, w, _)| *b == book_id && !elsewhere.contains(w))
+            .map(|(_, w, _)| *w)
+            .collect();
+
+        let mut writers: Vec<Writer> = ids
+            .iter()
+            .filter_map(|id| self.tables.writers.get(id).cloned())
+            .collect();
+        writers.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(writers)
+    }
+
+    fn get_review(&self, review_id: i64) -> LibroResult<Review> {
+        self.tables
+            .live_review(review_id)
+            .cloned()
+            .ok_or(LibroError::ReviewIdNotFound { id: review_id })
+    }
+
+    fn delete_review(&mut self, review_id: i64) -> LibroResult<()> {
+        self.recorded("delete review", |tables| {
+            if tables.live_review(review_id).is_none() {
+                return Err(LibroError::ReviewIdNotFound { id: review_id });
+            }
+            tables.trash.reviews.insert(review_id, timestamp());
+            Ok(())
+        })
+    }
+
+    fn export_archive(&self) -> LibroResult<Archive> {
+        Ok(self.tables.archive())
+    }
+
+    fn import_archive(&mut self, archive: &Archive) -> LibroResult<ImportSummary> {
+        archive.validate_header()?;
+
+        self.recorded("import archive", |tables| {
+            let mut summary = ImportSummary::default();
+            let mut writer_ids: HashMap<i64, i64> = HashMap::new();
+            let mut book_ids: HashMap<i64, i64> = HashMap::new();
+
+            for writer in &archive.writers {
+                let old_id = archive_id(writer.id, "writer")?;
+
+                let new_id = if let Some(id) = tables.writer_named(&writer.name) {
+                    summary.writers_reused += 1;
+                    id
+                } else {
+                    summary.writers_created += 1;
+                    tables.insert_writer(&writer.name, writer.writer_type.clone())?
+                };
+                writer_ids.insert(old_id, new_id);
+            }
+
+            for book in &archive.books {
+                let old_id = archive_id(book.id, "book")?;
+                let new_id = tables.insert_book(book);
+                if let Some(deleted_at) = &book.deleted_at {
+                    tables.trash.books.insert(new_id, deleted_at.clone());
+                }
+                book_ids.insert(old_id, new_id);
+                summary.books += 1;
+            }
+
+            for link in &archive.book_writers {
+                let book_id = remap(&book_ids, link.book_id, "book")?;
+                let writer_id = remap(&writer_ids, link.writer_id, "writer")?;
+                tables.link(book_id, writer_id, link.writer_type.clone())?;
+            }
+
+            for review in &archive.reviews {
+                let book_id = remap(&book_ids, review.book_id, "book")?;
+                let (rating, text) = review_content(review)?;
+                let review_id = tables.insert_review(Review {
+                    id: None,
+                    book_id,
+                    date_read: parse_archive_date(review),
+                    rating,
+                    review: text.to_string(),
+                    created_at: review.created_at.clone(),
+                    updated_at: review.updated_at.clone(),
+                })?;
+                if let Some(deleted_at) = &review.deleted_at {
+                    tables.trash.reviews.insert(review_id, deleted_at.clone());
+                }
+                summary.reviews += 1;
+            }
+
+            Ok(summary)
+        })
+    }
+
+    fn load_archive(&mut self, archive: &Archive) -> LibroResult<()> {
+        archive.validate_header()?;
+        if !self.tables.is_empty() {
+            return Err(not_empty());
+        }
+        let mut tables = self.tables.clone();
+        tables.load(archive)?;
+        self.tables = tables;
+        Ok(())
+    }
+
+    fn history(&self, book_id: Option<i64>) -> LibroResult<Vec<HistoryEntry>> {
+        Ok(self
+            .history
+            .iter()
+            .rev()
+            .filter(|entry| book_id.is_none_or(|id| entry.touches_book(id)))
+            .cloned()
+            .collect())
+    }
+
+    fn undo(&mut self, count: usize) -> LibroResult<Vec<HistoryEntry>> {
+        let mut entries = undo_candidates(self.history(None)?, count)?;
+
+        let mut tables = self.tables.clone();
+        for entry in &mut entries {
+            for change in entry.changes.iter().rev() {
+                let current = tables.row(change.table, change.row());
+                if !same_row(current.as_ref(), change.after.as_ref()) {
+                    return Err(changed_since(entry, change));
+                }
+                tables.revert(&change.filled_from(current.as_ref()))?;
+            }
+            entry.undone = true;
+        }
+        self.tables = tables;
+
+        for entry in &mut self.history {
+            if entries.iter().any(|undone| undone.id == entry.id) {
+                entry.undone = true;
+            }
+        }
+        Ok(entries)
+    }
+
+    fn trash(&self) -> LibroResult<Vec<TrashItem>> {
+        let tables = &self.tables;
+        let title = |book_id: i64| {
+            tables
+                .books
+                .get(&book_id)
+                .map(|b| b.title.clone())
+                .unwrap_or_default()
+        };
+
+        let mut items: Vec<TrashItem> = tables
+            .trash
+            .books
+            .iter()
+            .map(|(id, deleted_at)| TrashItem {
+                kind: TrashKind::Book,
+                id: *id,
+                book_id: *id,
+                title: title(*id),
+                deleted_at: deleted_at.clone(),
+                reviews: tables
+                    .reviews
+                    .iter()
+                    .filter(|(r, review)| {
+                        review.book_id == *id && !tables.trash.reviews.contains_key(r)
+                    })
+                    .count(),
+            })
+            .collect();
+        for (id, deleted_at) in &tables.trash.reviews {
+            let book_id = tables.reviews.get(id).map_or(0, |r| r.book_id);
+            items.push(TrashItem {
+                kind: TrashKind::Review,
+                id: *id,
+                book_id,
+                title: title(book_id),
+                deleted_at: deleted_at.clone(),
+                reviews: 1,
+            });
+        }
+
+        sort_trash(&mut items);
+        Ok(items)
+    }
+
+    fn restore_book(&mut self, book_id: i64) -> LibroResult<()> {
+        self.recorded("restore book", |tables| {
+            tables
+                .trash
+                .books
+                .remove(&book_id)
+                .map(|_| ())
+                .ok_or(LibroError::BookNotFound { id: book_id })
+        })
+    }
+
+    fn restore_review(&mut self, review_id: i64) -> LibroResult<()> {
+        self.recorded("restore review", |tables| {
+            let book_id = tables
+                .reviews
+                .get(&review_id)
+                .filter(|_| tables.trash.reviews.contains_key(&review_id))
+                .map(|r| r.book_id)
+                .ok_or(LibroError::ReviewIdNotFound { id: review_id })?;
+            if tables.live_book(book_id).is_none() {
+                return Err(book_in_trash(review_id, book_id));
+            }
+            tables.trash.reviews.remove(&review_id);
+            Ok(())
+        })
+    }
+
+    fn purge_trash(&mut self, cutoff: Option<NaiveDateTime>) -> LibroResult<Vec<TrashItem>> {
+        let purged: Vec<TrashItem> = self
+            .trash()?
+            .into_iter()
+            .filter(|item| item.deleted_before(cutoff))
+            .collect();
+
+        // A purge is not recorded, and strips the purged rows from every
+        // entry that recorded them
+        let mut removed = PurgedRows::default();
+        let tables = &mut self.tables;
+        for item in &purged {
+            match item.kind {
+                TrashKind::Review => {
+                    tables.reviews.remove(&item.id);
+                    tables.trash.reviews.remove(&item.id);
+                    removed.reviews.insert(item.id);
+                }
+                TrashKind::Book => {
+                    let book_id = item.id;
+                    tables.books.remove(&book_id);
+                    tables.trash.books.remove(&book_id);
+                    removed.books.insert(book_id);
+                    let review_ids: Vec<i64> = tables
+                        .reviews
+                        .iter()
+                        .filter(|(_, r)| r.book_id == book_id)
+                        .map(|(id, _)| *id)
+                        .collect();
+                    for review_id in review_ids {
+                        tables.reviews.remove(&review_id);
+                        tables.trash.reviews.remove(&review_id);
+                        removed.reviews.insert(review_id);
+                    }
+                    let writer_ids: Vec<i64> = tables
+                        .book_writers
+                        .iter()
+                        .filter(|(b, _, _)| *b == book_id)
+                        .map(|(_, w, _)| *w)
+                        .collect();
+                    tables.book_writers.retain(|(b, _, _)| *b != book_id);
+                    for writer_id in writer_ids {
+                        if !tables.book_writers.iter().any(|(_, w, _)| *w == writer_id) {
+                            tables.writers.remove(&writer_id);
+                            removed.writers.insert(writer_id);
+                        }
+                    }
+                }
+            }
+        }
+        for entry in &mut self.history {
+            entry.changes.retain(|change| !removed.recorded(change));
+        }
+        Ok(purged)
+    }
+
+    fn get_writers(&self) -> LibroResult<Vec<Writer>> {
+        let mut writers: Vec<Writer> = self
+            .tables
+            .writers
+            .values()
+            .filter(|w| self.tables.is_live_writer(w.id.unwrap_or_default()))
+            .cloned()
+            .collect();
+        writers.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(writers)
+    }
+
+    fn apply_merge(&mut self, plan: &MergePlan) -> LibroResult<MergeSummary> {
+        self.recorded("merge", |tables| {
+            let mut summary = MergeSummary::default();
+
+            for book in &plan.new_books {
+                let book_id = tables.insert_book(&book.book);
+                summary.books_added += 1;
+
+                let writers = book
+                    .authors
+                    .iter()
+                    .map(|w| (w, WriterType::Author))
+                    .chain(book.translators.iter().map(|w| (w, WriterType::Translator)));
+                for (writer, writer_type) in writers {
+                    let writer_id = merge_writer(tables, &writer.name, &writer_type, &mut summary)?;
+                    tables
+                        .book_writers
+                        .insert((book_id, writer_id, writer_type));
+                }
+
+                for review in &book.reviews {
+                    tables.insert_review(Review {
+                        id: None,
+                        book_id,
+                        ..review.clone()
+                    })?;
+                    summary.reviews_added += 1;
+                }
+            }
+
+            for (book_id, book) in &plan.updated_books {
+                if let Some(local) = tables.books.get_mut(book_id) {
+                    *local = Book {
+                        id: Some(*book_id),
+                        created_at: local.created_at.take(),
+                        updated_at: Some(timestamp()),
+                        deleted_at: None,
+                        ..book.book.clone()
+                    };
+                }
+
+                // Authors are part of the match key, so only translators can differ
+                tables
+                    .book_writers
+                    .retain(|(b, _, t)| b != book_id || *t != WriterType::Translator);
+                for translator in &book.translators {
+                    let writer_id = merge_writer(
+                        tables,
+                        &translator.name,
+                        &WriterType::Translator,
+                        &mut summary,
+                    )?;
+                    tables
+                        .book_writers
+                        .insert((*book_id, writer_id, WriterType::Translator));
+                }
+                summary.books_updated += 1;
+            }
+
+            for (book_id, review) in &plan.new_reviews {
+                tables.insert_review(Review {
+                    id: None,
+                    book_id: *book_id,
+                    ..review.clone()
+                })?;
+                summary.reviews_added += 1;
+            }
+
+            for (review_id, review) in &plan.updated_reviews {
+                if let Some(local) = tables.reviews.get_mut(review_id) {
+                    local.rating = review.rating;
+                    local.review = review.review.clone();
+                    local.updated_at = Some(timestamp());
+                }
+                summary.reviews_updated += 1;
+            }
+
+            Ok(summary)
+        })
+    }
+}
+
+/// Row changes that turn `old` into `new`: removals children first, then
+/// updates, then additions parents first, as SQLite would make them
+fn changes_between(old: &Tables, new: &Tables) -> Vec<RowChange> {
+    let mut removed = Vec::new();
+    let mut changed = Vec::new();
+    let mut added = Vec::new();
+    for table in HistoryTable::ALL {
+        for before in old.snapshots(table) {
+            match new.row(table, &before) {
+                None => removed.push(RowChange {
+                    table,
+                    before: Some(before),
+                    after: None,
+                }),
+                Some(after) if after != before => changed.push(RowChange {
+                    table,
+                    before: Some(before),
+                    after: Some(after),
+                }),
+                Some(_) => {}
+            }
+        }
+        for after in new.snapshots(table) {
+            if old.row(table, &after).is_none() {
+                added.push(RowChange {
+                    table,
+                    before: None,
+                    after: Some(after),
+                });
+            }
+        }
+    }
+    removed.reverse();
+    removed.into_iter().chain(changed).chain(added).collect()
+}
+
+/// A review from input, not yet stamped
+fn new_review(book_id: i64, date_read: Option<NaiveDate>, review: &NewReview) -> Review {
+    Review {
+        id: None,
+        book_id,
+        date_read,
+        rating: review.rating,
+        review: review.review.clone(),
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+fn writer_row(writer: &Writer) -> Value {
+    json!({
+        "id": writer.id,
+        "name": writer.name,
+        "type": writer.writer_type.as_str(),
+    })
+}
+
+fn link_row((book_id, writer_id, writer_type): &(i64, i64, WriterType)) -> Value {
+    json!({
+        "book_id": book_id,
+        "writer_id": writer_id,
+        "type": writer_type.as_str(),
+    })
+}
+
+fn link_key(row: &Value) -> Option<(i64, i64, WriterType)> {
+    Some((
+        row["book_id"].as_i64()?,
+        row["writer_id"].as_i64()?,
+        row["type"].as_str()?.parse().ok()?,
+    ))
+}
+
+/// Rating and text of an archive review, which a `Review` cannot leave empty
+fn review_content(review: &ReviewRecord) -> LibroResult<(i32, &str)> {
+    match (review.rating, &review.review) {
+        (Some(rating), Some(text)) => Ok((rating, text)),
+        _ => Err(LibroError::invalid_input(format!(
+            "Archive review {} is missing its rating or text",
+            review.id
+        ))),
+    }
+}
+
+fn parse_archive_date(review: &ReviewRecord) -> Option<NaiveDate> {
+    review
+        .date_read
+        .as_deref()
+        .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+}
+
+/// Find a writer by name (names are unique) or create it
+fn merge_writer(
+    tables: &mut Tables,
+    name: &str,
+    writer_type: &WriterType,
+    summary: &mut MergeSummary,
+) -> LibroResult<i64> {
+    if let Some(id) = tables.writer_named(name) {
+        return Ok(id);
+    }
+    summary.writers_created += 1;
+    tables.insert_writer(name, writer_type.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_book(title: &str, author: &str) -> NewBook {
+        NewBook {
+            title: title.to_string(),
+            authors: vec![author.to_string()],
+            translators: vec![],
+            pages: Some(100),
+            pub_year: Some(2000),
+            genre: "Fiction".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_ids_are_not_reused() {
+        let mut store = MemoryStore::new();
+        let first = store.add_book(&new_book("One", "A")).unwrap();
+        store.delete_book(first).unwrap();
+        let second = store.add_book(&new_book("Two", "A")).unwrap();
+        assert!(second > first);
+    }
+
+    #[test]
+    fn test_failed_add_changes_nothing() {
+        let mut store = MemoryStore::new();
+        store.add_book(&new_book("One", "Shared Name")).unwrap();
+
+        // 같은 이름의 번역가는 UNIQUE(name)에 걸림
+        let input = NewBookWithReview {
+            book: NewBook {
+                translators: vec!["Shared Name".to_string()],
+                ..new_book("Two", "B")
+            },
+            review: None,
+        };
+        assert!(store.add_book_with_review(&input).is_err());
+
+        assert_eq!(store.get_books(&BookFilter::default()).unwrap().len(), 1);
+        assert_eq!(store.get_writers().unwrap().len(), 1);
+    }
+}
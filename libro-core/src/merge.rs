@@ -0,0 +1,555 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::errors::LibroResult;
+use crate::models::{ExtendedBook, Review, Writer};
+
+/// Which side of a merge wins a conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The database being merged into
+    Local,
+    /// The database being merged from
+    Other,
+}
+
+/// Kind of difference between two libraries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A single added, removed or changed row
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffEntry {
+    pub kind: DiffKind,
+    pub label: String,
+    pub details: Vec<String>,
+}
+
+/// Differences between two libraries, from the first to the second
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LibraryDiff {
+    pub books: Vec<DiffEntry>,
+    pub writers: Vec<DiffEntry>,
+    pub reviews: Vec<DiffEntry>,
+}
+
+impl LibraryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.books.is_empty() && self.writers.is_empty() && self.reviews.is_empty()
+    }
+}
+
+/// A book present on both sides whose details or reviews disagree
+#[derive(Debug)]
+pub struct BookConflict<'a> {
+    pub local: &'a ExtendedBook,
+    pub other: &'a ExtendedBook,
+    pub differences: Vec<String>,
+}
+
+impl BookConflict<'_> {
+    /// The side edited last: the latest `updated_at` of the book or any of
+    /// its reviews. Ties, and copies without timestamps, go to the local side.
+    pub fn newer_side(&self) -> Side {
+        if last_update(self.other) > last_update(self.local) {
+            Side::Other
+        } else {
+            Side::Local
+        }
+    }
+}
+
+/// Changes needed to bring another library's data into the local one
+#[derive(Debug, Clone, Default)]
+pub struct MergePlan {
+    /// Books missing locally, inserted with all of their reviews
+    pub new_books: Vec<ExtendedBook>,
+    /// Local book ID and the other side's copy whose details should win
+    pub updated_books: Vec<(i64, ExtendedBook)>,
+    /// Local book ID and a review missing locally
+    pub new_reviews: Vec<(i64, Review)>,
+    /// Local review ID and the other side's content that should win
+    pub updated_reviews: Vec<(i64, Review)>,
+    pub conflicts: usize,
+    pub kept_local: usize,
+}
+
+/// Summary of an applied merge
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MergeSummary {
+    pub books_added: usize,
+    pub books_updated: usize,
+    pub reviews_added: usize,
+    pub reviews_updated: usize,
+    pub writers_created: usize,
+}
+
+/// Key used to match books across databases, independent of row IDs.
+///
+/// There is no ISBN column, so books are matched by normalized title plus
+/// the sorted, normalized author names.
+pub fn book_key(book: &ExtendedBook) -> String {
+    let mut authors: Vec<String> = book.authors.iter().map(|a| normalize(&a.name)).collect();
+    authors.sort();
+    format!("{}|{}", normalize(&book.book.title), authors.join(","))
+}
+
+/// Compare two libraries; `Added` means present only in `b`
+pub fn diff_libraries(
+    a_books: &[ExtendedBook],
+    a_writers: &[Writer],
+    b_books: &[ExtendedBook],
+    b_writers: &[Writer],
+) -> LibraryDiff {
+    let mut diff = LibraryDiff::default();
+
+    let a_index = index_books(a_books);
+    let b_index = index_books(b_books);
+
+    for book in b_books {
+        match a_index.get(&book_key(book)) {
+            None => diff.books.push(DiffEntry {
+                kind: DiffKind::Added,
+                label: book_label(book),
+                details: review_count_detail(book),
+            }),
+            Some(a_book) => {
+                let details = book_differences(a_book, book);
+                if !details.is_empty() {
+                    diff.books.push(DiffEntry {
+                        kind: DiffKind::Changed,
+                        label: book_label(book),
+                        details,
+                    });
+                }
+                diff.reviews.extend(review_differences(a_book, book));
+            }
+        }
+    }
+    for book in a_books {
+        if !b_index.contains_key(&book_key(book)) {
+            diff.books.push(DiffEntry {
+                kind: DiffKind::Removed,
+                label: book_label(book),
+                details: review_count_detail(book),
+            });
+        }
+    }
+
+    let a_writers: HashMap<&str, &Writer> =
+        a_writers.iter().map(|w| (w.name.as_str(), w)).collect();
+    let b_writers_by_name: HashMap<&str, &Writer> =
+        b_writers.iter().map(|w| (w.name.as_str(), w)).collect();
+    for writer in b_writers {
+        match a_writers.get(writer.name.as_str()) {
+            None => diff.writers.push(DiffEntry {
+                kind: DiffKind::Added,
+                label: writer.name.clone(),
+                details: Vec::new(),
+            }),
+            Some(a_writer) if a_writer.writer_type.as_str() != writer.writer_type.as_str() => {
+                diff.writers.push(DiffEntry {
+                    kind: DiffKind::Changed,
+                    label: writer.name.clone(),
+                    details: vec![format!(
+                        "type: {} → {}",
+                        a_writer.writer_type.as_str(),
+                        writer.writer_type.as_str()
+                    )],
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, _) in a_writers
+        .iter()
+        .filter(|(name, _)| !b_writers_by_name.contains_key(*name))
+    {
+        diff.writers.push(DiffEntry {
+            kind: DiffKind::Removed,
+            label: name.to_string(),
+            details: Vec::new(),
+        });
+    }
+    diff.writers.sort_by(|x, y| x.label.cmp(&y.label));
+
+    diff
+}
+
+/// Work out what merging `other` into `local` would change.
+///
+/// Books and reviews that exist on only one side never conflict: missing
+/// ones are added and local-only ones are kept. Each book present on both
+/// sides with differing details or reviews is passed to `resolve` once.
+pub fn plan_merge<F>(
+    local: &[ExtendedBook],
+    other: &[ExtendedBook],
+    mut resolve: F,
+) -> LibroResult<MergePlan>
+where
+    F: FnMut(&BookConflict) -> LibroResult<Side>,
+{
+    let local_index = index_books(local);
+    let mut plan = MergePlan::default();
+
+    for other_book in other {
+        let Some(local_book) = local_index.get(&book_key(other_book)) else {
+            plan.new_books.push(other_book.clone());
+            continue;
+        };
+        let local_id = local_book.book.id.unwrap_or_default();
+
+        let local_reviews = keyed_reviews(&local_book.reviews);
+        let mut changed_reviews = Vec::new();
+        for (key, review) in keyed_reviews(&other_book.reviews) {
+            match local_reviews.iter().find(|(k, _)| *k == key) {
+                None => plan.new_reviews.push((local_id, review.clone())),
+                Some((_, local_review)) if !same_review(local_review, review) => {
+                    changed_reviews.push((local_review.id.unwrap_or_default(), review.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut differences = book_differences(local_book, other_book);
+        if !changed_reviews.is_empty() {
+            differences.push(format!("{} review(s) differ", changed_reviews.len()));
+        }
+        if differences.is_empty() {
+            continue;
+        }
+
+        plan.conflicts += 1;
+        let conflict = BookConflict {
+            local: local_book,
+            other: other_book,
+            differences,
+        };
+        match resolve(&conflict)? {
+            Side::Local => plan.kept_local += 1,
+            Side::Other => {
+                if !book_differences(local_book, other_book).is_empty() {
+                    plan.updated_books.push((local_id, other_book.clone()));
+                }
+                plan.updated_reviews.extend(changed_reviews);
+            }
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Lowercase, drop punctuation and collapse whitespace
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn index_books(books: &[ExtendedBook]) -> HashMap<String, &ExtendedBook> {
+    books.iter().map(|book| (book_key(book), book)).collect()
+}
+
+fn book_label(book: &ExtendedBook) -> String {
+    let authors: Vec<&str> = book.authors.iter().map(|a| a.name.as_str()).collect();
+    format!("{} — {}", book.book.title, authors.join(", "))
+}
+
+fn review_count_detail(book: &ExtendedBook) -> Vec<String> {
+    if book.reviews.is_empty() {
+        Vec::new()
+    } else {
+        vec![format!("{} review(s)", book.reviews.len())]
+    }
+}
+
+/// Latest `updated_at` of a book and its reviews; the `YYYY-MM-DD HH:MM:SS`
+/// format sorts like the times it holds
+fn last_update(book: &ExtendedBook) -> Option<&str> {
+    book.reviews
+        .iter()
+        .filter_map(|r| r.updated_at.as_deref())
+        .chain(book.book.updated_at.as_deref())
+        .max()
+}
+
+fn book_differences(a: &ExtendedBook, b: &ExtendedBook) -> Vec<String> {
+    let mut details = Vec::new();
+    if a.book.title != b.book.title {
+        details.push(format!("title: {} → {}", a.book.title, b.book.title));
+    }
+    if a.book.genre != b.book.genre {
+        details.push(format!("genre: {} → {}", a.book.genre, b.book.genre));
+    }
+    if a.book.pages != b.book.pages {
+        details.push(format!(
+            "pages: {} → {}",
+            optional(a.book.pages),
+            optional(b.book.pages)
+        ));
+    }
+    if a.book.pub_year != b.book.pub_year {
+        details.push(format!(
+            "pub_year: {} → {}",
+            optional(a.book.pub_year),
+            optional(b.book.pub_year)
+        ));
+    }
+    let a_translators = writer_names(&a.translators);
+    let b_translators = writer_names(&b.translators);
+    if a_translators != b_translators {
+        details.push(format!(
+            "translators: [{}] → [{}]",
+            a_translators.join(", "),
+            b_translators.join(", ")
+        ));
+    }
+    details
+}
+
+fn review_differences(a: &ExtendedBook, b: &ExtendedBook) -> Vec<DiffEntry> {
+    let a_reviews = keyed_reviews(&a.reviews);
+    let b_reviews = keyed_reviews(&b.reviews);
+    let mut entries = Vec::new();
+
+    for (key, review) in &b_reviews {
+        match a_reviews.iter().find(|(k, _)| k == key) {
+            None => entries.push(review_entry(DiffKind::Added, b, review, Vec::new())),
+            Some((_, a_review)) if !same_review(a_review, review) => {
+                let mut details = Vec::new();
+                if a_review.rating != review.rating {
+                    details.push(format!("rating: {} → {}", a_review.rating, review.rating));
+                }
+                if a_review.review != review.review {
+                    details.push("text changed".to_string());
+                }
+                entries.push(review_entry(DiffKind::Changed, b, review, details));
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, review) in &a_reviews {
+        if !b_reviews.iter().any(|(k, _)| k == key) {
+            entries.push(review_entry(DiffKind::Removed, a, review, Vec::new()));
+        }
+    }
+
+    entries
+}
+
+fn review_entry(
+    kind: DiffKind,
+    book: &ExtendedBook,
+    review: &Review,
+    details: Vec<String>,
+) -> DiffEntry {
+    let date = review
+        .date_read
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "undated".to_string());
+    DiffEntry {
+        kind,
+        label: format!("{} ({}, {}★)", book.book.title, date, review.rating),
+        details,
+    }
+}
+
+/// Reviews of one book keyed by read date plus position among same-day reviews
+fn keyed_reviews(reviews: &[Review]) -> Vec<((Option<NaiveDate>, usize), &Review)> {
+    let mut sorted: Vec<&Review> = reviews.iter().collect();
+    sorted.sort_by_key(|r| (r.date_read, r.id));
+
+    let mut seen: HashMap<Option<NaiveDate>, usize> = HashMap::new();
+    sorted
+        .into_iter()
+        .map(|review| {
+            let nth = seen.entry(review.date_read).or_insert(0);
+            let key = (review.date_read, *nth);
+            *nth += 1;
+            (key, review)
+        })
+        .collect()
+}
+
+fn same_review(a: &Review, b: &Review) -> bool {
+    a.rating == b.rating && a.review == b.review
+}
+
+fn writer_names(writers: &[Writer]) -> Vec<&str> {
+    let mut names: Vec<&str> = writers.iter().map(|w| w.name.as_str()).collect();
+    names.sort();
+    names
+}
+
+fn optional(value: Option<i32>) -> String {
+    value.map_or("-".to_string(), |v| v.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Book, WriterType};
+
+    fn book(id: i64, title: &str, authors: &[&str], pages: Option<i32>) -> ExtendedBook {
+        ExtendedBook {
+            book: Book {
+                id: Some(id),
+                title: title.to_string(),
+                pages,
+                pub_year: None,
+                genre: "Fiction".to_string(),
+                created_at: None,
+                updated_at: None,
+                deleted_at: None,
+            },
+            authors: authors
+                .iter()
+                .map(|name| Writer {
+                    id: None,
+                    name: name.to_string(),
+                    writer_type: WriterType::Author,
+                })
+                .collect(),
+            translators: Vec::new(),
+            reviews: Vec::new(),
+        }
+    }
+
+    fn review(id: i64, date: &str, rating: i32, text: &str) -> Review {
+        Review {
+            id: Some(id),
+            book_id: 0,
+            date_read: NaiveDate::parse_from_str(date, "%Y-%m-%d").ok(),
+            rating,
+            review: text.to_string(),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_book_key_ignores_ids_case_and_punctuation() {
+        let a = book(1, "The Dispossessed", &["Ursula K. Le Guin"], None);
+        let b = book(42, "the  dispossessed", &["ursula k le guin"], None);
+        assert_eq!(book_key(&a), book_key(&b));
+
+        let c = book(2, "The Dispossessed", &["Someone Else"], None);
+        assert_ne!(book_key(&a), book_key(&c));
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_changed() {
+        let mut kept = book(1, "Exhalation", &["Ted Chiang"], Some(350));
+        kept.reviews.push(review(1, "2023-01-05", 4, "Great"));
+        let a = vec![kept.clone(), book(2, "Removed", &["Nobody"], None)];
+
+        let mut changed = book(7, "Exhalation", &["Ted Chiang"], Some(352));
+        changed.reviews.push(review(9, "2023-01-05", 5, "Great"));
+        changed.reviews.push(review(10, "2024-02-01", 5, "Reread"));
+        let b = vec![changed, book(8, "Added", &["Somebody"], None)];
+
+        let diff = diff_libraries(&a, &[], &b, &[]);
+        let kinds: Vec<(DiffKind, &str)> = diff
+            .books
+            .iter()
+            .map(|e| (e.kind, e.label.split(" — ").next().unwrap()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (DiffKind::Changed, "Exhalation"),
+                (DiffKind::Added, "Added"),
+                (DiffKind::Removed, "Removed"),
+            ]
+        );
+        assert_eq!(diff.books[0].details, vec!["pages: 350 → 352"]);
+
+        let review_kinds: Vec<DiffKind> = diff.reviews.iter().map(|e| e.kind).collect();
+        assert_eq!(review_kinds, vec![DiffKind::Changed, DiffKind::Added]);
+    }
+
+    #[test]
+    fn test_plan_merge_respects_resolution() {
+        let mut local = book(1, "Exhalation", &["Ted Chiang"], Some(350));
+        local.reviews.push(review(1, "2023-01-05", 4, "Good"));
+        let mut other = book(5, "Exhalation", &["Ted Chiang"], Some(352));
+        other
+            .reviews
+            .push(review(3, "2023-01-05", 5, "Better on reread"));
+        other.reviews.push(review(4, "2024-06-01", 5, "Third time"));
+        let local = vec![local];
+        let mut other = vec![
+            other,
+            book(6, "Stories of Your Life", &["Ted Chiang"], None),
+        ];
+
+        let keep = plan_merge(&local, &other, |_| Ok(Side::Local)).unwrap();
+        assert_eq!(keep.new_books.len(), 1);
+        assert_eq!(keep.new_reviews.len(), 1);
+        assert_eq!(keep.conflicts, 1);
+        assert_eq!(keep.kept_local, 1);
+        assert!(keep.updated_books.is_empty());
+        assert!(keep.updated_reviews.is_empty());
+
+        other[0].book.updated_at = Some("2024-06-01 09:00:00".to_string());
+        let newer = plan_merge(&local, &other, |c| Ok(c.newer_side())).unwrap();
+        assert_eq!(newer.updated_books.len(), 1);
+        assert_eq!(newer.updated_books[0].0, 1);
+        assert_eq!(newer.updated_reviews.len(), 1);
+        assert_eq!(newer.updated_reviews[0].0, 1);
+    }
+
+    #[test]
+    fn test_newer_side_follows_updated_at_not_date_read() {
+        let mut local = book(1, "Exhalation", &["Ted Chiang"], Some(350));
+        let mut read_later = review(1, "2024-06-01", 4, "Good");
+        read_later.updated_at = Some("2024-06-02 08:00:00".to_string());
+        local.reviews.push(read_later);
+
+        // Read earlier, but edited after the local copy
+        let mut other = book(5, "Exhalation", &["Ted Chiang"], Some(352));
+        other.book.updated_at = Some("2025-01-10 20:30:00".to_string());
+        let mut read_earlier = review(3, "2023-01-05", 5, "Better on reread");
+        read_earlier.updated_at = Some("2023-01-05 21:00:00".to_string());
+        other.reviews.push(read_earlier);
+
+        let conflict = BookConflict {
+            local: &local,
+            other: &other,
+            differences: Vec::new(),
+        };
+        assert_eq!(conflict.newer_side(), Side::Other);
+
+        let conflict = BookConflict {
+            local: &other,
+            other: &local,
+            differences: Vec::new(),
+        };
+        assert_eq!(conflict.newer_side(), Side::Local);
+
+        // Without timestamps on either side the local copy is kept
+        let bare = book(9, "Exhalation", &["Ted Chiang"], Some(352));
+        let conflict = BookConflict {
+            local: &bare,
+            other: &bare,
+            differences: Vec::new(),
+        };
+        assert_eq!(conflict.newer_side(), Side::Local);
+    }
+}
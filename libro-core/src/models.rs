@@ -9,6 +9,16 @@ pub struct Book {
     pub pages: Option<i32>,
     pub pub_year: Option<i32>,
     pub genre: String, // Now required
+    /// When the book was added, local time `YYYY-MM-DD HH:MM:SS`
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// When the book's own fields last changed
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    /// When the book was moved to the trash. Reads leave trashed books out,
+    /// so only archives carry it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<String>,
 }
 
 /// Review entity for book reviews
@@ -19,6 +29,12 @@ pub struct Review {
     pub date_read: Option<NaiveDate>,
     pub rating: i32,
     pub review: String,
+    /// When the review was written, local time `YYYY-MM-DD HH:MM:SS`
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// When the review last changed
+    #[serde(default)]
+    pub updated_at: Option<String>,
 }
 
 /// Writer entity for authors and translators
@@ -30,7 +46,7 @@ pub struct Writer {
 }
 
 /// Type of writer (author or translator)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum WriterType {
     Author,
     Translator,
@@ -81,29 +97,36 @@ pub struct ExtendedBook {
 }
 
 /// Input struct for creating a new book
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewBook {
     pub title: String,
     pub authors: Vec<String>,
+    #[serde(default)]
     pub translators: Vec<String>,
+    #[serde(default)]
     pub pages: Option<i32>,
+    #[serde(default)]
     pub pub_year: Option<i32>,
     pub genre: String, // Now required
 }
 
 /// Input struct for creating a new review
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewReview {
+    /// Ignored when the review is created together with its book
+    #[serde(default)]
     pub book_id: i64,
+    #[serde(default)]
     pub date_read: Option<NaiveDate>,
     pub rating: i32,
     pub review: String,
 }
 
 /// Combined input for creating a book with an optional review
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewBookWithReview {
     pub book: NewBook,
+    #[serde(default)]
     pub review: Option<NewReview>,
 }
 
@@ -115,7 +138,7 @@ pub struct BookFilter {
 }
 
 /// Result of adding a book with optional review
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BookCreationResult {
     pub book_id: i64,
     pub review_id: Option<i64>,
@@ -0,0 +1,974 @@
+//! Reading statistics computed from a list of books
+//!
+//! Every function works on books already loaded with
+//! [`LibraryStore::get_books`](crate::store::LibraryStore::get_books), so the
+//! same numbers back the CLI reports, the TUI and anything else built on
+//! `libro-core`. [`LibraryStats`] bundles them for callers that show several
+//! at once.
+
+use crate::date::format_iso_date;
+use crate::errors::validation::max_rating;
+use crate::models::{ExtendedBook, Review};
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Books per author
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorCount {
+    pub author: String,
+    pub books: usize,
+}
+
+/// Books per genre, with their pages and average rating
+#[derive(Debug, Clone, Serialize)]
+pub struct GenreCount {
+    pub genre: String,
+    pub books: usize,
+    pub pages: i64,
+    pub average_rating: Option<f64>,
+}
+
+/// Books shorter than this many pages count as short
+pub const SHORT_BOOK_PAGES: i32 = 200;
+
+/// Books with at least this many pages count as long
+pub const LONG_BOOK_PAGES: i32 = 400;
+
+/// Books whose page count falls in a length bucket
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PageBucket {
+    /// `short`, `medium` or `long`
+    pub length: String,
+    /// The page range, such as `200-399`
+    pub pages: String,
+    pub books: usize,
+}
+
+/// Reviews with a given rating
+#[derive(Debug, Clone, Serialize)]
+pub struct RatingCount {
+    pub rating: i32,
+    pub reviews: usize,
+}
+
+/// Books read in a year, by review date
+#[derive(Debug, Clone, Serialize)]
+pub struct YearCount {
+    pub year: i32,
+    pub books: usize,
+}
+
+/// A review with its book
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewSummary {
+    pub id: Option<i64>,
+    pub book_id: i64,
+    pub title: String,
+    pub authors: String,
+    pub rating: i32,
+    pub date_read: Option<String>,
+    pub review: String,
+    pub created_at: Option<String>,
+}
+
+/// Books and pages finished in a month (`2024-03`) or ISO week (`2024-W09`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PeriodCount {
+    pub period: String,
+    pub books: usize,
+    pub pages: i64,
+}
+
+/// How steadily books get finished, from review `date_read` values
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadingPace {
+    /// Reviews with a reading date; rereads count again
+    pub finished: usize,
+    pub pages: i64,
+    pub first_finished: Option<String>,
+    pub last_finished: Option<String>,
+    pub average_days_between: Option<f64>,
+    /// Most consecutive weeks with at least one book finished
+    pub longest_streak_weeks: usize,
+    /// The streak still running this week or last week, else 0
+    pub current_streak_weeks: usize,
+    /// Every month from the first finish to today, oldest first
+    pub monthly: Vec<PeriodCount>,
+    /// Every week from the first finish to today, oldest first
+    pub weekly: Vec<PeriodCount>,
+}
+
+/// A book read during a year in review, with its best rating that year
+#[derive(Debug, Clone, Serialize)]
+pub struct YearBook {
+    pub id: Option<i64>,
+    pub title: String,
+    pub authors: String,
+    pub rating: i32,
+    pub date_read: String,
+}
+
+/// One year of reading summed up, compared with the year before
+#[derive(Debug, Clone, Serialize)]
+pub struct YearInReview {
+    pub year: i32,
+    /// Totals over the books with a review dated in `year`
+    pub totals: ReadingStats,
+    pub top_rated: Vec<YearBook>,
+    pub authors: Vec<AuthorCount>,
+    pub genres: Vec<GenreCount>,
+    pub longest: Option<BookPages>,
+    pub shortest: Option<BookPages>,
+    pub first_book: Option<YearBook>,
+    pub last_book: Option<YearBook>,
+    /// Translated books as a fraction of the year's books
+    pub translated_share: Option<f64>,
+    /// Totals for `year - 1`, or `None` when nothing was read then
+    pub previous_year: Option<ReadingStats>,
+}
+
+/// One figure of a year in review next to the same figure a year earlier
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct YearChange {
+    pub label: &'static str,
+    pub current: f64,
+    pub previous: f64,
+}
+
+impl ReadingPace {
+    /// Only the newest `count` months and weeks, as reports list them
+    pub fn recent(mut self, count: usize) -> Self {
+        for periods in [&mut self.monthly, &mut self.weekly] {
+            periods.drain(..periods.len().saturating_sub(count));
+        }
+        self
+    }
+}
+
+impl YearChange {
+    pub fn delta(&self) -> f64 {
+        self.current - self.previous
+    }
+}
+
+impl YearInReview {
+    /// Books, pages, authors, translations and the average rating against
+    /// the year before; empty when nothing was read then
+    pub fn changes(&self) -> Vec<YearChange> {
+        let Some(previous) = &self.previous_year else {
+            return Vec::new();
+        };
+        let current = &self.totals;
+        let mut changes: Vec<YearChange> = [
+            (
+                "Books",
+                current.total_books as f64,
+                previous.total_books as f64,
+            ),
+            (
+                "Pages",
+                current.total_pages as f64,
+                previous.total_pages as f64,
+            ),
+            (
+                "Authors",
+                current.total_authors as f64,
+                previous.total_authors as f64,
+            ),
+            (
+                "Translated",
+                current.translated_books as f64,
+                previous.translated_books as f64,
+            ),
+        ]
+        .into_iter()
+        .map(|(label, current, previous)| YearChange {
+            label,
+            current,
+            previous,
+        })
+        .collect();
+        if let (Some(current), Some(previous)) = (current.average_rating, previous.average_rating) {
+            changes.push(YearChange {
+                label: "Average rating",
+                current,
+                previous,
+            });
+        }
+        changes
+    }
+}
+
+/// How many books, authors and genres a year in review lists at most
+pub const YEAR_IN_REVIEW_TOP: usize = 5;
+
+/// Overall reading statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadingStats {
+    pub total_books: usize,
+    pub total_authors: usize,
+    pub total_pages: i64,
+    pub total_reviews: usize,
+    pub average_rating: Option<f64>,
+    pub rating_scale: i32,
+    /// Books with at least one translator; the rest are read in the original
+    pub translated_books: usize,
+}
+
+/// A book and its page count
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BookPages {
+    pub id: Option<i64>,
+    pub title: String,
+    pub pages: i32,
+}
+
+/// Page counts over the books that record them
+#[derive(Debug, Clone, Serialize)]
+pub struct PageStats {
+    pub books_with_pages: usize,
+    pub total_pages: i64,
+    pub average_pages: Option<f64>,
+    pub longest: Option<BookPages>,
+    pub shortest: Option<BookPages>,
+}
+
+/// Every statistic the reports show, computed once from the same books
+#[derive(Debug, Clone, Serialize)]
+pub struct LibraryStats {
+    pub totals: ReadingStats,
+    /// All authors, most read first
+    pub authors: Vec<AuthorCount>,
+    pub years: Vec<YearCount>,
+    pub genres: Vec<GenreCount>,
+    pub ratings: Vec<RatingCount>,
+    pub pages: PageStats,
+    pub page_lengths: Vec<PageBucket>,
+}
+
+impl LibraryStats {
+    /// Compute every statistic for `books`
+    pub fn from_books(books: &[ExtendedBook]) -> Self {
+        LibraryStats {
+            totals: reading_stats(books),
+            authors: author_counts(books, u32::MAX),
+            years: year_counts(books),
+            genres: genre_counts(books),
+            ratings: rating_counts(books),
+            pages: page_stats(books),
+            page_lengths: page_buckets(books),
+        }
+    }
+}
+
+/// Books read in each year by review date, oldest first
+///
+/// A book reviewed twice in one year counts once for that year.
+pub fn year_counts(books: &[ExtendedBook]) -> Vec<YearCount> {
+    let mut read: BTreeMap<i32, BTreeSet<usize>> = BTreeMap::new();
+    for (index, book) in books.iter().enumerate() {
+        for date_read in book.reviews.iter().filter_map(|r| r.date_read) {
+            read.entry(date_read.year()).or_default().insert(index);
+        }
+    }
+    read.into_iter()
+        .map(|(year, books)| YearCount {
+            year,
+            books: books.len(),
+        })
+        .collect()
+}
+
+/// Books per author, most read first, limited to `limit` authors
+pub fn author_counts(books: &[ExtendedBook], limit: u32) -> Vec<AuthorCount> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for author in books.iter().flat_map(|b| &b.authors) {
+        *counts.entry(&author.name).or_insert(0) += 1;
+    }
+    let mut sorted: Vec<_> = counts.into_iter().collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    sorted.truncate(limit as usize);
+    sorted
+        .into_iter()
+        .map(|(author, books)| AuthorCount {
+            author: author.to_string(),
+            books,
+        })
+        .collect()
+}
+
+/// Titles of the books by `author`, in the order given
+pub fn author_titles<'a>(books: &'a [ExtendedBook], author: &str) -> Vec<&'a str> {
+    books
+        .iter()
+        .filter(|book| book.authors.iter().any(|a| a.name == author))
+        .map(|book| book.book.title.as_str())
+        .collect()
+}
+
+/// Books per genre, largest first
+pub fn genre_counts(books: &[ExtendedBook]) -> Vec<GenreCount> {
+    let mut genres: BTreeMap<&str, Vec<&ExtendedBook>> = BTreeMap::new();
+    for book in books {
+        genres.entry(&book.book.genre).or_default().push(book);
+    }
+    let mut counts: Vec<GenreCount> = genres
+        .into_iter()
+        .map(|(genre, books)| {
+            let ratings: Vec<i32> = books
+                .iter()
+                .flat_map(|b| &b.reviews)
+                .map(|r| r.rating)
+                .collect();
+            GenreCount {
+                genre: genre.to_string(),
+                books: books.len(),
+                pages: books
+                    .iter()
+                    .filter_map(|b| b.book.pages)
+                    .map(i64::from)
+                    .sum(),
+                average_rating: average(&ratings),
+            }
+        })
+        .collect();
+    counts.sort_by(|a, b| b.books.cmp(&a.books).then(a.genre.cmp(&b.genre)));
+    counts
+}
+
+/// Books per length bucket, short to long; books without a page count are left out
+pub fn page_buckets(books: &[ExtendedBook]) -> Vec<PageBucket> {
+    let mut counts = [0usize; 3];
+    for pages in books.iter().filter_map(|b| b.book.pages) {
+        let bucket = if pages < SHORT_BOOK_PAGES {
+            0
+        } else if pages < LONG_BOOK_PAGES {
+            1
+        } else {
+            2
+        };
+        counts[bucket] += 1;
+    }
+    let ranges = [
+        ("short", format!("1-{}", SHORT_BOOK_PAGES - 1)),
+        (
+            "medium",
+            format!("{}-{}", SHORT_BOOK_PAGES, LONG_BOOK_PAGES - 1),
+        ),
+        ("long", format!("{}+", LONG_BOOK_PAGES)),
+    ];
+    ranges
+        .into_iter()
+        .zip(counts)
+        .map(|((length, pages), books)| PageBucket {
+            length: length.to_string(),
+            pages,
+            books,
+        })
+        .collect()
+}
+
+/// Number of reviews for each rating from the top of the scale down to 1
+pub fn rating_counts(books: &[ExtendedBook]) -> Vec<RatingCount> {
+    let max = max_rating();
+    let mut counts = vec![0usize; max as usize];
+    for review in books.iter().flat_map(|b| &b.reviews) {
+        if (1..=max).contains(&review.rating) {
+            counts[(review.rating - 1) as usize] += 1;
+        }
+    }
+    (1..=max)
+        .rev()
+        .map(|rating| RatingCount {
+            rating,
+            reviews: counts[(rating - 1) as usize],
+        })
+        .collect()
+}
+
+/// Which of a book's timestamps to sort or filter by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BookDate {
+    /// When the book was added
+    #[default]
+    Added,
+    /// When the book's own fields last changed
+    Modified,
+}
+
+impl BookDate {
+    /// The book's timestamp of this kind, if it has one
+    pub fn of(self, book: &ExtendedBook) -> Option<&str> {
+        match self {
+            BookDate::Added => book.book.created_at.as_deref(),
+            BookDate::Modified => book.book.updated_at.as_deref(),
+        }
+    }
+}
+
+/// Sort newest first by `date`, books without the timestamp last, ties by ID
+pub fn sort_by_date(books: &mut [ExtendedBook], date: BookDate) {
+    books.sort_by(|a, b| (date.of(b), b.book.id).cmp(&(date.of(a), a.book.id)));
+}
+
+/// Keep only books whose `date` falls on or after the day `since`
+pub fn retain_since(books: &mut Vec<ExtendedBook>, date: BookDate, since: NaiveDate) {
+    let since = format_iso_date(&since);
+    books.retain(|book| date.of(book).is_some_and(|stamp| stamp >= since.as_str()));
+}
+
+/// Most recently added (or modified) books first, limited to `limit`
+pub fn latest_books(books: &[ExtendedBook], date: BookDate, limit: u32) -> Vec<ExtendedBook> {
+    let mut sorted_books = books.to_vec();
+    sort_by_date(&mut sorted_books, date);
+    sorted_books.truncate(limit as usize);
+    sorted_books
+}
+
+/// Most recently written reviews first with the book they belong to, limited to `limit`
+pub fn latest_reviews(books: &[ExtendedBook], limit: u32) -> Vec<ReviewSummary> {
+    let mut reviews: Vec<ReviewSummary> = books
+        .iter()
+        .flat_map(|book| {
+            book.reviews.iter().map(move |review| ReviewSummary {
+                id: review.id,
+                book_id: review.book_id,
+                title: book.book.title.clone(),
+                authors: book
+                    .authors
+                    .iter()
+                    .map(|a| a.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                rating: review.rating,
+                date_read: review.date_read.as_ref().map(format_iso_date),
+                review: review.review.clone(),
+                created_at: review.created_at.clone(),
+            })
+        })
+        .collect();
+    reviews.sort_by(|a, b| (&b.created_at, b.id).cmp(&(&a.created_at, a.id)));
+    reviews.truncate(limit as usize);
+    reviews
+}
+
+/// Page totals, the average and the longest and shortest books
+///
+/// Books without a page count are left out; ties go to the lower ID.
+pub fn page_stats(books: &[ExtendedBook]) -> PageStats {
+    let paged: Vec<BookPages> = books
+        .iter()
+        .filter_map(|book| {
+            book.book.pages.map(|pages| BookPages {
+                id: book.book.id,
+                title: book.book.title.clone(),
+                pages,
+            })
+        })
+        .collect();
+    let total_pages: i64 = paged.iter().map(|b| i64::from(b.pages)).sum();
+    PageStats {
+        books_with_pages: paged.len(),
+        total_pages,
+        average_pages: (!paged.is_empty()).then(|| total_pages as f64 / paged.len() as f64),
+        longest: paged
+            .iter()
+            .min_by_key(|b| (std::cmp::Reverse(b.pages), b.id))
+            .cloned(),
+        shortest: paged.iter().min_by_key(|b| (b.pages, b.id)).cloned(),
+    }
+}
+
+/// Totals for the whole (or year-filtered) library
+pub fn reading_stats(books: &[ExtendedBook]) -> ReadingStats {
+    let ratings: Vec<i32> = books
+        .iter()
+        .flat_map(|b| &b.reviews)
+        .map(|r| r.rating)
+        .collect();
+    let authors: BTreeSet<&str> = books
+        .iter()
+        .flat_map(|b| &b.authors)
+        .map(|a| a.name.as_str())
+        .collect();
+    ReadingStats {
+        total_books: books.len(),
+        total_authors: authors.len(),
+        total_pages: books
+            .iter()
+            .filter_map(|b| b.book.pages)
+            .map(i64::from)
+            .sum(),
+        total_reviews: ratings.len(),
+        average_rating: average(&ratings),
+        rating_scale: max_rating(),
+        translated_books: books.iter().filter(|b| !b.translators.is_empty()).count(),
+    }
+}
+
+fn average(ratings: &[i32]) -> Option<f64> {
+    (!ratings.is_empty()).then(|| ratings.iter().sum::<i32>() as f64 / ratings.len() as f64)
+}
+
+/// Books finished on each day, counting a book once per day
+pub fn finishes_by_day(books: &[ExtendedBook]) -> BTreeMap<NaiveDate, usize> {
+    let mut days: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    for (date, _) in finishes(books) {
+        *days.entry(date).or_insert(0) += 1;
+    }
+    days
+}
+
+/// Monthly and weekly counts, streaks and spacing of finished books up to `today`
+pub fn reading_pace(books: &[ExtendedBook], today: NaiveDate) -> ReadingPace {
+    let finished = finishes(books);
+    let pages_of = |index: usize| i64::from(books[index].book.pages.unwrap_or(0));
+    let (Some(&(first, _)), Some(&(last, _))) = (finished.first(), finished.last()) else {
+        return ReadingPace {
+            finished: 0,
+            pages: 0,
+            first_finished: None,
+            last_finished: None,
+            average_days_between: None,
+            longest_streak_weeks: 0,
+            current_streak_weeks: 0,
+            monthly: Vec::new(),
+            weekly: Vec::new(),
+        };
+    };
+    let end = today.max(last);
+
+    let mut months: BTreeMap<NaiveDate, (usize, i64)> = BTreeMap::new();
+    let mut month = month_start(first);
+    while month <= end {
+        months.insert(month, (0, 0));
+        month = month
+            .checked_add_months(chrono::Months::new(1))
+            .expect("month in range");
+    }
+    let mut weeks: BTreeMap<NaiveDate, (usize, i64)> = BTreeMap::new();
+    let mut week = week_start(first);
+    while week <= end {
+        weeks.insert(week, (0, 0));
+        week += chrono::Duration::weeks(1);
+    }
+    for &(date, index) in &finished {
+        for entry in [
+            months.get_mut(&month_start(date)),
+            weeks.get_mut(&week_start(date)),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            entry.0 += 1;
+            entry.1 += pages_of(index);
+        }
+    }
+
+    // Runs of consecutive weeks with at least one finish
+    let mut longest = 0;
+    let mut run = 0;
+    for &(books, _) in weeks.values() {
+        run = if books > 0 { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    let this_week = week_start(today);
+    let mut current = 0;
+    for (&week, &(books, _)) in weeks.iter().rev() {
+        if books > 0 {
+            current += 1;
+        } else if current > 0 || week < this_week {
+            break;
+        }
+    }
+
+    let days: BTreeSet<NaiveDate> = finished.iter().map(|&(date, _)| date).collect();
+    let period = |start: &NaiveDate, counts: &(usize, i64), format: &str| PeriodCount {
+        period: start.format(format).to_string(),
+        books: counts.0,
+        pages: counts.1,
+    };
+    ReadingPace {
+        finished: finished.len(),
+        pages: finished.iter().map(|&(_, index)| pages_of(index)).sum(),
+        first_finished: Some(format_iso_date(&first)),
+        last_finished: Some(format_iso_date(&last)),
+        average_days_between: (days.len() > 1)
+            .then(|| (last - first).num_days() as f64 / (days.len() - 1) as f64),
+        longest_streak_weeks: longest,
+        current_streak_weeks: current,
+        monthly: months
+            .iter()
+            .map(|(start, counts)| period(start, counts, "%Y-%m"))
+            .collect(),
+        weekly: weeks
+            .iter()
+            .map(|(start, counts)| period(start, counts, "%G-W%V"))
+            .collect(),
+    }
+}
+
+/// Sum up the books read in `year` by review date and compare with the year before
+///
+/// Only reviews dated in `year` count towards ratings, so a book reread later
+/// keeps the rating it got that year.
+pub fn year_in_review(books: &[ExtendedBook], year: i32) -> YearInReview {
+    let read = read_in_year(books, year);
+    let totals = reading_stats(&read);
+    let pages = page_stats(&read);
+    let year_book = |book: &ExtendedBook, review: &Review, date: NaiveDate| YearBook {
+        id: book.book.id,
+        title: book.book.title.clone(),
+        authors: book
+            .authors
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>()
+            .join("; "),
+        rating: review.rating,
+        date_read: format_iso_date(&date),
+    };
+    // (reading date, book, review) for every review in the year
+    let finished: Vec<(NaiveDate, &ExtendedBook, &Review)> = read
+        .iter()
+        .flat_map(|book| {
+            book.reviews
+                .iter()
+                .filter_map(move |r| r.date_read.map(|date| (date, book, r)))
+        })
+        .collect();
+
+    let mut top_rated: Vec<YearBook> = read
+        .iter()
+        .filter_map(|book| {
+            finished
+                .iter()
+                .filter(|(_, b, _)| b.book.id == book.book.id)
+                .max_by_key(|(date, _, r)| (r.rating, std::cmp::Reverse(*date)))
+                .map(|&(date, b, r)| year_book(b, r, date))
+        })
+        .collect();
+    top_rated.sort_by(|a, b| (b.rating, &a.date_read, a.id).cmp(&(a.rating, &b.date_read, b.id)));
+    top_rated.truncate(YEAR_IN_REVIEW_TOP);
+
+    let mut genres = genre_counts(&read);
+    genres.truncate(YEAR_IN_REVIEW_TOP);
+    let previous = read_in_year(books, year - 1);
+    YearInReview {
+        year,
+        translated_share: (totals.total_books > 0)
+            .then(|| totals.translated_books as f64 / totals.total_books as f64),
+        totals,
+        top_rated,
+        authors: author_counts(&read, YEAR_IN_REVIEW_TOP as u32),
+        genres,
+        longest: pages.longest,
+        shortest: pages.shortest,
+        first_book: finished
+            .iter()
+            .min_by_key(|(date, b, _)| (*date, b.book.id))
+            .map(|&(date, b, r)| year_book(b, r, date)),
+        last_book: finished
+            .iter()
+            .max_by_key(|(date, b, _)| (*date, b.book.id))
+            .map(|&(date, b, r)| year_book(b, r, date)),
+        previous_year: (!previous.is_empty()).then(|| reading_stats(&previous)),
+    }
+}
+
+/// The books with a review dated in `year`, keeping only those reviews
+fn read_in_year(books: &[ExtendedBook], year: i32) -> Vec<ExtendedBook> {
+    books
+        .iter()
+        .filter_map(|book| {
+            let reviews: Vec<Review> = book
+                .reviews
+                .iter()
+                .filter(|r| r.date_read.is_some_and(|date| date.year() == year))
+                .cloned()
+                .collect();
+            (!reviews.is_empty()).then(|| ExtendedBook {
+                reviews,
+                ..book.clone()
+            })
+        })
+        .collect()
+}
+
+/// Every (reading date, book index) pair, oldest first, once per book and day
+fn finishes(books: &[ExtendedBook]) -> Vec<(NaiveDate, usize)> {
+    let finished: BTreeSet<(NaiveDate, usize)> = books
+        .iter()
+        .enumerate()
+        .flat_map(|(index, book)| {
+            book.reviews
+                .iter()
+                .filter_map(move |r| r.date_read.map(|date| (date, index)))
+        })
+        .collect();
+    finished.into_iter().collect()
+}
+
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("every month has a first day")
+}
+
+/// The Monday starting the ISO week of `date`
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - chrono::Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Book, Writer, WriterType};
+    use chrono::NaiveDate;
+
+    fn book(id: i64, author: &str, genre: &str, ratings: &[i32]) -> ExtendedBook {
+        ExtendedBook {
+            book: Book {
+                id: Some(id),
+                title: format!("Book {}", id),
+                pages: Some(100),
+                pub_year: Some(2020),
+                genre: genre.to_string(),
+                created_at: None,
+                updated_at: None,
+                deleted_at: None,
+            },
+            authors: vec![Writer {
+                id: None,
+                name: author.to_string(),
+                writer_type: WriterType::Author,
+            }],
+            translators: vec![],
+            reviews: ratings
+                .iter()
+                .enumerate()
+                .map(|(i, &rating)| Review {
+                    id: Some(id * 10 + i as i64),
+                    book_id: id,
+                    date_read: NaiveDate::from_ymd_opt(2023, 1, 1),
+                    rating,
+                    review: String::new(),
+                    created_at: None,
+                    updated_at: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let books = vec![
+            book(1, "Le Guin", "SF", &[5]),
+            book(2, "Le Guin", "Fantasy", &[4, 5]),
+            book(3, "Calvino", "SF", &[]),
+        ];
+
+        let authors = author_counts(&books, 10);
+        assert_eq!(authors[0].author, "Le Guin");
+        assert_eq!(authors[0].books, 2);
+
+        let genres = genre_counts(&books);
+        assert_eq!((genres[0].genre.as_str(), genres[0].books), ("SF", 2));
+
+        let ratings = rating_counts(&books);
+        assert_eq!((ratings[0].rating, ratings[0].reviews), (5, 2));
+
+        // Book 2 was reviewed twice in 2023 but counts once
+        let years = year_counts(&books);
+        assert_eq!((years[0].year, years[0].books), (2023, 2));
+
+        let stats = reading_stats(&books);
+        assert_eq!(stats.total_books, 3);
+        assert_eq!(stats.total_authors, 2);
+        assert_eq!(stats.total_pages, 300);
+        assert_eq!(stats.total_reviews, 3);
+        assert!((stats.average_rating.unwrap() - 14.0 / 3.0).abs() < 1e-9);
+
+        let reviews = latest_reviews(&books, 2);
+        assert_eq!(reviews.len(), 2);
+        assert_eq!(reviews[0].title, "Book 2");
+        assert_eq!(reviews[0].date_read.as_deref(), Some("2023-01-01"));
+    }
+
+    #[test]
+    fn test_sort_and_filter_by_date() {
+        let mut books = vec![
+            book(1, "A", "SF", &[]),
+            book(2, "B", "SF", &[]),
+            book(3, "C", "SF", &[]),
+        ];
+        books[0].book.created_at = Some("2024-03-01 09:00:00".to_string());
+        books[0].book.updated_at = Some("2024-06-01 09:00:00".to_string());
+        books[1].book.created_at = Some("2024-05-01 09:00:00".to_string());
+        books[1].book.updated_at = Some("2024-05-01 09:00:00".to_string());
+
+        let ids = |books: &[ExtendedBook]| books.iter().map(|b| b.book.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(&latest_books(&books, BookDate::Added, 10)),
+            [Some(2), Some(1), Some(3)]
+        );
+        sort_by_date(&mut books, BookDate::Modified);
+        assert_eq!(ids(&books), [Some(1), Some(2), Some(3)]);
+
+        retain_since(
+            &mut books,
+            BookDate::Added,
+            NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+        );
+        assert_eq!(ids(&books), [Some(2)]);
+    }
+
+    #[test]
+    fn test_page_stats_and_library_stats() {
+        let mut books = vec![
+            book(1, "Le Guin", "SF", &[5]),
+            book(2, "Calvino", "SF", &[]),
+            book(3, "Calvino", "Fantasy", &[]),
+        ];
+        books[0].book.pages = Some(300);
+        books[2].book.pages = None;
+
+        let pages = page_stats(&books);
+        assert_eq!(pages.books_with_pages, 2);
+        assert_eq!(pages.total_pages, 400);
+        assert_eq!(pages.average_pages, Some(200.0));
+        assert_eq!(pages.longest.unwrap().title, "Book 1");
+        assert_eq!(pages.shortest.unwrap().pages, 100);
+        assert!(page_stats(&[]).average_pages.is_none());
+
+        let stats = LibraryStats::from_books(&books);
+        assert_eq!(stats.totals.total_books, 3);
+        assert_eq!(stats.authors[0].author, "Calvino");
+        assert_eq!(stats.authors.len(), 2);
+        assert_eq!(stats.genres[0].genre, "SF");
+        assert_eq!(stats.pages.total_pages, stats.totals.total_pages);
+        assert_eq!(author_titles(&books, "Calvino"), ["Book 2", "Book 3"]);
+    }
+
+    #[test]
+    fn test_genre_page_and_translation_breakdowns() {
+        let mut books = vec![
+            book(1, "Le Guin", "SF", &[5, 3]),
+            book(2, "Calvino", "SF", &[]),
+            book(3, "Calvino", "Fantasy", &[2]),
+            book(4, "Lem", "SF", &[]),
+        ];
+        books[1].book.pages = Some(250);
+        books[2].book.pages = Some(400);
+        books[3].book.pages = None;
+        books[2].translators.push(Writer {
+            id: None,
+            name: "Weaver".to_string(),
+            writer_type: WriterType::Translator,
+        });
+
+        let genres = genre_counts(&books);
+        assert_eq!((genres[0].genre.as_str(), genres[0].books), ("SF", 3));
+        assert_eq!(genres[0].pages, 350);
+        assert_eq!(genres[0].average_rating, Some(4.0));
+        assert_eq!(genres[1].average_rating, Some(2.0));
+
+        let buckets = page_buckets(&books);
+        let lengths: Vec<(&str, usize)> = buckets
+            .iter()
+            .map(|b| (b.length.as_str(), b.books))
+            .collect();
+        assert_eq!(lengths, [("short", 1), ("medium", 1), ("long", 1)]);
+        assert_eq!(buckets[1].pages, "200-399");
+
+        assert_eq!(reading_stats(&books).translated_books, 1);
+    }
+
+    #[test]
+    fn test_reading_pace() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        let mut books = vec![
+            book(1, "Le Guin", "SF", &[5]),
+            book(2, "Calvino", "SF", &[4, 3]),
+            book(3, "Lem", "SF", &[4]),
+        ];
+        // Mondays 1 Jan, 8 Jan, 15 Jan, then 5 Feb after a two-week gap
+        books[0].reviews[0].date_read = Some(date(1, 2));
+        books[1].reviews[0].date_read = Some(date(1, 10));
+        books[1].reviews[1].date_read = Some(date(1, 16));
+        books[2].reviews[0].date_read = Some(date(2, 6));
+
+        let pace = reading_pace(&books, date(2, 14));
+        assert_eq!(pace.finished, 4);
+        assert_eq!(pace.pages, 400);
+        assert_eq!(pace.first_finished.as_deref(), Some("2024-01-02"));
+        assert_eq!(pace.average_days_between, Some(35.0 / 3.0));
+        assert_eq!(pace.longest_streak_weeks, 3);
+        assert_eq!(pace.current_streak_weeks, 1);
+
+        let months: Vec<(&str, usize)> = pace
+            .monthly
+            .iter()
+            .map(|m| (m.period.as_str(), m.books))
+            .collect();
+        assert_eq!(months, [("2024-01", 3), ("2024-02", 1)]);
+        assert_eq!(pace.weekly.len(), 7);
+        assert_eq!(pace.weekly[0].period, "2024-W01");
+        assert_eq!(pace.weekly[3].books, 0);
+
+        let recent = pace.clone().recent(2);
+        assert_eq!(recent.weekly.len(), 2);
+        assert_eq!(recent.weekly[1].period, "2024-W07");
+        assert_eq!(recent.monthly, pace.monthly);
+        assert_eq!(recent.finished, pace.finished);
+
+        // A quiet week ends the current streak
+        assert_eq!(reading_pace(&books, date(2, 21)).current_streak_weeks, 0);
+
+        assert_eq!(finishes_by_day(&books).get(&date(1, 10)), Some(&1));
+        assert_eq!(reading_pace(&[], date(2, 14)).finished, 0);
+    }
+
+    #[test]
+    fn test_year_in_review() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+        let mut books = vec![
+            book(1, "Le Guin", "SF", &[3, 5]),
+            book(2, "Le Guin", "Fantasy", &[4]),
+            book(3, "Calvino", "SF", &[5]),
+            book(4, "Calvino", "SF", &[2]),
+        ];
+        books[0].reviews[0].date_read = date(2024, 3, 1);
+        books[0].reviews[1].date_read = date(2025, 6, 1);
+        books[1].reviews[0].date_read = date(2024, 1, 20);
+        books[1].book.pages = Some(350);
+        books[1].translators = books[1].authors.clone();
+        books[2].reviews[0].date_read = date(2024, 11, 5);
+        books[2].book.pages = Some(80);
+        books[3].reviews[0].date_read = date(2023, 5, 5);
+
+        let wrapped = year_in_review(&books, 2024);
+        assert_eq!(wrapped.totals.total_books, 3);
+        assert_eq!(wrapped.totals.total_pages, 530);
+        // The 2025 reread of book 1 does not lift its 2024 rating
+        let top: Vec<(Option<i64>, i32)> =
+            wrapped.top_rated.iter().map(|b| (b.id, b.rating)).collect();
+        assert_eq!(top, [(Some(3), 5), (Some(2), 4), (Some(1), 3)]);
+        assert_eq!(wrapped.authors[0].author, "Le Guin");
+        assert_eq!(wrapped.genres[0].genre, "SF");
+        assert_eq!(wrapped.longest.as_ref().unwrap().id, Some(2));
+        assert_eq!(wrapped.shortest.as_ref().unwrap().id, Some(3));
+        assert_eq!(wrapped.first_book.as_ref().unwrap().date_read, "2024-01-20");
+        assert_eq!(wrapped.last_book.as_ref().unwrap().id, Some(3));
+        assert_eq!(wrapped.translated_share, Some(1.0 / 3.0));
+        assert_eq!(wrapped.previous_year.as_ref().unwrap().total_books, 1);
+        let changes = wrapped.changes();
+        assert_eq!((changes[0].label, changes[0].delta()), ("Books", 2.0));
+        assert_eq!(changes.last().unwrap().label, "Average rating");
+
+        let empty = year_in_review(&books, 2026);
+        assert_eq!(empty.totals.total_books, 0);
+        assert!(empty.first_book.is_none() && empty.translated_share.is_none());
+        assert_eq!(empty.previous_year.unwrap().total_books, 1);
+        assert!(year_in_review(&books, 2023).changes().is_empty());
+    }
+}
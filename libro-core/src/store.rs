@@ -0,0 +1,125 @@
+//! Storage backend trait shared by SQLite and in-memory libraries
+//!
+//! Code that only reads and writes books, writers and reviews should take a
+//! `&dyn LibraryStore` (or `&mut dyn LibraryStore`) instead of a concrete
+//! [`Database`](crate::db_operations::Database), so it can run against a
+//! [`MemoryStore`](crate::memory::MemoryStore) in tests.
+//!
+//! Deleted books and reviews go to the [`trash`](crate::trash) and are left
+//! out of every read until they are restored.
+
+use crate::archive::{Archive, ImportSummary};
+use crate::errors::LibroResult;
+use crate::history::HistoryEntry;
+use crate::merge::{MergePlan, MergeSummary};
+use crate::models::*;
+use crate::trash::TrashItem;
+use chrono::NaiveDateTime;
+
+/// Books, writers and reviews of one library
+pub trait LibraryStore {
+    /// Get or add a writer by name and type
+    fn get_or_add_writer(&mut self, name: &str, writer_type: WriterType) -> LibroResult<i64>;
+
+    /// Add a link between a book and a writer
+    fn add_book_writer_link(
+        &mut self,
+        book_id: i64,
+        writer_id: i64,
+        writer_type: WriterType,
+    ) -> LibroResult<()>;
+
+    /// Add a new book
+    fn add_book(&mut self, book: &NewBook) -> LibroResult<i64>;
+
+    /// Add a new review; the date defaults to today
+    fn add_review(&mut self, review: &NewReview) -> LibroResult<i64>;
+
+    /// Add a book with an optional review, all or nothing
+    fn add_book_with_review(
+        &mut self,
+        input: &NewBookWithReview,
+    ) -> LibroResult<BookCreationResult>;
+
+    /// Get books with optional filtering, ordered by ID
+    fn get_books(&self, filter: &BookFilter) -> LibroResult<Vec<ExtendedBook>>;
+
+    /// Get all writers for a specific book
+    fn get_book_writers(&self, book_id: i64) -> LibroResult<Vec<Writer>>;
+
+    /// Get all reviews for a specific book, latest reading first
+    fn get_reviews(&self, book_id: i64) -> LibroResult<Vec<Review>>;
+
+    /// Update an existing book
+    fn update_book(&mut self, book_id: i64, updates: &Book) -> LibroResult<()>;
+
+    /// Update an existing review
+    fn update_review(&mut self, review_id: i64, updates: &Review) -> LibroResult<()>;
+
+    /// Move a book to the trash, hiding its reviews and writer links with it
+    fn delete_book(&mut self, book_id: i64) -> LibroResult<()>;
+
+    /// Writers linked to no other book, which deleting it would hide and
+    /// purging it would remove
+    fn orphaned_writers_for_book(&self, book_id: i64) -> LibroResult<Vec<Writer>>;
+
+    /// Get a single review by its ID
+    fn get_review(&self, review_id: i64) -> LibroResult<Review>;
+
+    /// Move a review to the trash
+    fn delete_review(&mut self, review_id: i64) -> LibroResult<()>;
+
+    /// Export every table into a versioned archive, ordered by ID. Trashed
+    /// books and reviews are kept with their `deleted_at`, so importing the
+    /// archive puts them back in the trash.
+    fn export_archive(&self) -> LibroResult<Archive>;
+
+    /// Import an archive all or nothing, assigning fresh IDs.
+    ///
+    /// Books and reviews are always inserted as new rows. Writers are matched
+    /// by name so that importing into a populated library reuses existing
+    /// writers, since writer names are unique.
+    fn import_archive(&mut self, archive: &Archive) -> LibroResult<ImportSummary>;
+
+    /// Load an archive into an empty library, keeping every ID as it is.
+    ///
+    /// This is how a library moves between backends; use `import_archive`
+    /// to add books to a library that already has some. Loading is not
+    /// recorded in the history.
+    fn load_archive(&mut self, archive: &Archive) -> LibroResult<()>;
+
+    /// Get every writer not used only by trashed books, ordered by name
+    fn get_writers(&self) -> LibroResult<Vec<Writer>>;
+
+    /// Apply a merge plan produced by `merge::plan_merge`, all or nothing
+    fn apply_merge(&mut self, plan: &MergePlan) -> LibroResult<MergeSummary>;
+
+    /// Recorded operations, newest first, optionally only those that changed
+    /// a book, its reviews or its writer links
+    fn history(&self, book_id: Option<i64>) -> LibroResult<Vec<HistoryEntry>>;
+
+    /// Revert the last `count` operations that are not undone yet, all or
+    /// nothing, and return them newest first.
+    ///
+    /// Undoing is not recorded itself; the reverted entries are marked as
+    /// undone instead.
+    fn undo(&mut self, count: usize) -> LibroResult<Vec<HistoryEntry>>;
+
+    /// Trashed books and reviews, most recently deleted first
+    fn trash(&self) -> LibroResult<Vec<TrashItem>>;
+
+    /// Take a book out of the trash together with its reviews and writers
+    fn restore_book(&mut self, book_id: i64) -> LibroResult<()>;
+
+    /// Take a review out of the trash; its book must not be trashed
+    fn restore_review(&mut self, review_id: i64) -> LibroResult<()>;
+
+    /// Permanently delete what was trashed before `cutoff`, or everything
+    /// with `None`, and return what was removed. Writers left without any
+    /// book are removed with their last book.
+    ///
+    /// A purge is not recorded and cannot be undone. The removed rows'
+    /// changes are stripped from the history; entries left with no changes
+    /// stay listed but can no longer be undone.
+    fn purge_trash(&mut self, cutoff: Option<NaiveDateTime>) -> LibroResult<Vec<TrashItem>>;
+}
@@ -0,0 +1,68 @@
+//! Trash bin behind soft deletes.
+//!
+//! `delete_book` and `delete_review` only stamp a row with the time it was
+//! deleted. Trashed rows are hidden from every read; a book comes back from
+//! the trash with its reviews and writer links, and `purge_trash` removes
+//! rows for good, history included.
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+use crate::errors::LibroError;
+
+/// Format of `deleted_at`, the same local time format as the history
+pub const DELETED_AT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// What a trashed row is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashKind {
+    Book,
+    Review,
+}
+
+impl TrashKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrashKind::Book => "book",
+            TrashKind::Review => "review",
+        }
+    }
+}
+
+/// One trashed book or review
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrashItem {
+    pub kind: TrashKind,
+    pub id: i64,
+    pub book_id: i64,
+    /// Title of the book, or of the book a review belongs to
+    pub title: String,
+    /// Local time, `YYYY-MM-DD HH:MM:SS`
+    pub deleted_at: String,
+    /// Reviews that come back with a book; 1 for a review
+    pub reviews: usize,
+}
+
+impl TrashItem {
+    /// Whether the item was deleted before `cutoff`; every item is with `None`
+    pub fn deleted_before(&self, cutoff: Option<NaiveDateTime>) -> bool {
+        cutoff.is_none_or(|cutoff| self.deleted_at < cutoff.format(DELETED_AT_FORMAT).to_string())
+    }
+}
+
+/// Most recently deleted first, books before their reviews
+pub(crate) fn sort_trash(items: &mut [TrashItem]) {
+    items.sort_by(|a, b| {
+        (Reverse(&a.deleted_at), a.kind, a.id).cmp(&(Reverse(&b.deleted_at), b.kind, b.id))
+    });
+}
+
+/// Error for restoring a review whose book is still in the trash
+pub(crate) fn book_in_trash(review_id: i64, book_id: i64) -> LibroError {
+    LibroError::invalid_input(format!(
+        "Review {} belongs to book {}, which is in the trash; restore the book first",
+        review_id, book_id
+    ))
+}
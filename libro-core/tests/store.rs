@@ -0,0 +1,614 @@
+//! Behaviour every `LibraryStore` backend must share.
+//!
+//! Each check runs against SQLite (`Database` on `:memory:`), `MemoryStore`
+//! and a `FileStore` in a temporary directory, and the scenario tests compare
+//! the stores' archives row for row.
+
+use chrono::NaiveDate;
+use libro_core::db_operations::Database;
+use libro_core::errors::LibroError;
+use libro_core::files::FileStore;
+use libro_core::memory::MemoryStore;
+use libro_core::merge::{plan_merge, Side};
+use libro_core::models::*;
+use libro_core::store::LibraryStore;
+use libro_core::trash::TrashKind;
+use tempfile::TempDir;
+
+fn sqlite() -> Box<dyn LibraryStore> {
+    Box::new(Database::new(":memory:").expect("Failed to create test database"))
+}
+
+fn memory() -> Box<dyn LibraryStore> {
+    Box::new(MemoryStore::new())
+}
+
+fn new_book(title: &str, authors: &[&str], translators: &[&str]) -> NewBook {
+    NewBook {
+        title: title.to_string(),
+        authors: authors.iter().map(|a| a.to_string()).collect(),
+        translators: translators.iter().map(|t| t.to_string()).collect(),
+        pages: Some(300),
+        pub_year: Some(1972),
+        genre: "SF".to_string(),
+    }
+}
+
+fn new_review(book_id: i64, day: Option<u32>, rating: i32) -> NewReview {
+    NewReview {
+        book_id,
+        date_read: day.and_then(|d| NaiveDate::from_ymd_opt(2024, 3, d)),
+        rating,
+        review: format!("Rated {}", rating),
+    }
+}
+
+/// Run `check` against every backend
+fn for_each_store(check: impl Fn(&mut dyn LibraryStore)) {
+    check(sqlite().as_mut());
+    check(memory().as_mut());
+    let dir = TempDir::new().unwrap();
+    check(&mut FileStore::open(dir.path()).unwrap());
+}
+
+fn archive_json(store: &dyn LibraryStore) -> serde_json::Value {
+    serde_json::to_value(store.export_archive().unwrap()).unwrap()
+}
+
+/// `value` with every timestamp replaced, for comparing stores that did the
+/// same things at slightly different times
+fn without_times(mut value: serde_json::Value) -> serde_json::Value {
+    match &mut value {
+        serde_json::Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if ["created_at", "updated_at", "deleted_at"].contains(&key.as_str())
+                    && field.is_string()
+                {
+                    *field = "time".into();
+                } else {
+                    *field = without_times(field.take());
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items.iter_mut() {
+                *item = without_times(item.take());
+            }
+        }
+        _ => {}
+    }
+    value
+}
+
+/// Titles, writers and review texts, without row IDs
+fn contents(books: &[ExtendedBook]) -> Vec<(String, Vec<String>, Vec<String>)> {
+    books
+        .iter()
+        .map(|b| {
+            let writers = b.authors.iter().chain(&b.translators);
+            (
+                b.book.title.clone(),
+                writers.map(|w| w.name.clone()).collect(),
+                b.reviews.iter().map(|r| r.review.clone()).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Same operations on any backend, for comparing archives
+fn scenario(store: &mut dyn LibraryStore) {
+    let solaris = store
+        .add_book(&new_book("Solaris", &["Stanisław Lem"], &["Bill Johnston"]))
+        .unwrap();
+    let cyberiad = store
+        .add_book(&new_book(
+            "The Cyberiad",
+            &["Stanisław Lem"],
+            &["Michael Kandel"],
+        ))
+        .unwrap();
+    store
+        .add_book_with_review(&NewBookWithReview {
+            book: new_book("Invisible Cities", &["Italo Calvino"], &[]),
+            review: Some(new_review(0, Some(2), 5)),
+        })
+        .unwrap();
+
+    store.add_review(&new_review(solaris, Some(1), 4)).unwrap();
+    let later = store.add_review(&new_review(solaris, Some(9), 3)).unwrap();
+    store
+        .update_review(
+            later,
+            &Review {
+                id: Some(later),
+                book_id: solaris,
+                date_read: NaiveDate::from_ymd_opt(2024, 3, 10),
+                rating: 5,
+                review: "Better the second time".to_string(),
+                created_at: None,
+                updated_at: None,
+            },
+        )
+        .unwrap();
+
+    store.delete_book(cyberiad).unwrap();
+}
+
+#[test]
+fn test_backends_agree_on_a_scenario() {
+    let mut sqlite = sqlite();
+    let mut memory = memory();
+    scenario(sqlite.as_mut());
+    scenario(memory.as_mut());
+
+    assert_eq!(
+        without_times(archive_json(sqlite.as_ref())),
+        without_times(archive_json(memory.as_ref()))
+    );
+
+    let a = serde_json::to_value(sqlite.get_books(&BookFilter::default()).unwrap()).unwrap();
+    let b = serde_json::to_value(memory.get_books(&BookFilter::default()).unwrap()).unwrap();
+    assert_eq!(without_times(a), without_times(b));
+}
+
+#[test]
+fn test_reviews_latest_first_with_undated_last() {
+    for_each_store(|store| {
+        let book_id = store
+            .add_book(&new_book("Dune", &["Herbert"], &[]))
+            .unwrap();
+        store.add_review(&new_review(book_id, Some(1), 3)).unwrap();
+        store.add_review(&new_review(book_id, Some(20), 5)).unwrap();
+        let undated = store
+            .add_book_with_review(&NewBookWithReview {
+                book: new_book("Other", &["Herbert"], &[]),
+                review: None,
+            })
+            .unwrap();
+        assert_eq!(undated.review_id, None);
+
+        let ratings: Vec<i32> = store
+            .get_reviews(book_id)
+            .unwrap()
+            .iter()
+            .map(|r| r.rating)
+            .collect();
+        assert_eq!(ratings, vec![5, 3]);
+    });
+}
+
+#[test]
+fn test_writers_are_shared_and_orphans_removed() {
+    for_each_store(|store| {
+        let first = store
+            .add_book(&new_book("A", &["Le Guin"], &["Solo"]))
+            .unwrap();
+        let second = store.add_book(&new_book("B", &["Le Guin"], &[])).unwrap();
+
+        let orphaned: Vec<String> = store
+            .orphaned_writers_for_book(first)
+            .unwrap()
+            .into_iter()
+            .map(|w| w.name)
+            .collect();
+        assert_eq!(orphaned, vec!["Solo"]);
+
+        store.delete_book(first).unwrap();
+        let writers: Vec<String> = store
+            .get_writers()
+            .unwrap()
+            .into_iter()
+            .map(|w| w.name)
+            .collect();
+        assert_eq!(writers, vec!["Le Guin"]);
+        assert_eq!(store.get_book_writers(second).unwrap().len(), 1);
+    });
+}
+
+#[test]
+fn test_missing_rows_are_reported() {
+    for_each_store(|store| {
+        assert!(matches!(
+            store.delete_book(42),
+            Err(LibroError::BookNotFound { id: 42 })
+        ));
+        assert!(matches!(
+            store.add_review(&new_review(42, None, 3)),
+            Err(LibroError::BookNotFound { id: 42 })
+        ));
+        assert!(matches!(
+            store.get_review(7),
+            Err(LibroError::ReviewIdNotFound { id: 7 })
+        ));
+        assert!(matches!(
+            store.delete_review(7),
+            Err(LibroError::ReviewIdNotFound { id: 7 })
+        ));
+    });
+}
+
+#[test]
+fn test_filters() {
+    for_each_store(|store| {
+        let old = store.add_book(&new_book("Old", &["X"], &[])).unwrap();
+        let mut recent = new_book("Recent", &["X"], &[]);
+        recent.pub_year = Some(2020);
+        store.add_book(&recent).unwrap();
+
+        let by_year = store
+            .get_books(&BookFilter {
+                id: None,
+                year: Some(2020),
+            })
+            .unwrap();
+        assert_eq!(by_year.len(), 1);
+        assert_eq!(by_year[0].book.title, "Recent");
+
+        let by_id = store
+            .get_books(&BookFilter {
+                id: Some(old),
+                year: None,
+            })
+            .unwrap();
+        assert_eq!(by_id[0].book.title, "Old");
+    });
+}
+
+#[test]
+fn test_archive_round_trip_between_backends() {
+    let mut source = sqlite();
+    scenario(source.as_mut());
+    let archive = source.export_archive().unwrap();
+
+    for_each_store(|store| {
+        store
+            .add_book(&new_book("Already here", &["Italo Calvino"], &[]))
+            .unwrap();
+        let summary = store.import_archive(&archive).unwrap();
+        assert_eq!(summary.books, 3);
+        assert_eq!(summary.writers_reused, 1);
+        assert_eq!(summary.reviews, 3);
+        assert_eq!(store.get_books(&BookFilter::default()).unwrap().len(), 3);
+
+        // The trashed book comes along, still in the trash
+        let trash = store.trash().unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].title, "The Cyberiad");
+    });
+}
+
+#[test]
+fn test_load_keeps_ids_between_sqlite_and_files() {
+    let mut source = sqlite();
+    scenario(source.as_mut());
+    let archive = source.export_archive().unwrap();
+
+    let dir = TempDir::new().unwrap();
+    FileStore::open(dir.path())
+        .unwrap()
+        .load_archive(&archive)
+        .unwrap();
+    let files = FileStore::open(dir.path()).unwrap();
+    assert_eq!(archive_json(&files), archive_json(source.as_ref()));
+
+    let mut back = sqlite();
+    back.load_archive(&files.export_archive().unwrap()).unwrap();
+    assert_eq!(archive_json(back.as_ref()), archive_json(source.as_ref()));
+    assert_eq!(
+        serde_json::to_value(back.get_books(&BookFilter::default()).unwrap()).unwrap(),
+        serde_json::to_value(files.get_books(&BookFilter::default()).unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn test_load_requires_an_empty_library() {
+    let mut source = memory();
+    scenario(source.as_mut());
+    let archive = source.export_archive().unwrap();
+
+    for_each_store(|store| {
+        store
+            .add_book(&new_book("Dune", &["Herbert"], &[]))
+            .unwrap();
+        assert!(matches!(
+            store.load_archive(&archive),
+            Err(LibroError::InvalidInput { .. })
+        ));
+        assert_eq!(store.get_books(&BookFilter::default()).unwrap().len(), 1);
+    });
+}
+
+#[test]
+fn test_apply_merge() {
+    let mut other = memory();
+    scenario(other.as_mut());
+    let other_books = other.get_books(&BookFilter::default()).unwrap();
+
+    for_each_store(|store| {
+        let local = store.get_books(&BookFilter::default()).unwrap();
+        let plan = plan_merge(&local, &other_books, |_| Ok(Side::Other)).unwrap();
+        let summary = store.apply_merge(&plan).unwrap();
+        assert_eq!(summary.books_added, 2);
+        assert_eq!(summary.reviews_added, 3);
+
+        // IDs are reassigned, so compare what was read and said
+        let merged = store.get_books(&BookFilter::default()).unwrap();
+        assert_eq!(contents(&merged), contents(&other_books));
+
+        // Merging the same books again changes nothing
+        let plan = plan_merge(&merged, &other_books, |_| Ok(Side::Other)).unwrap();
+        let summary = store.apply_merge(&plan).unwrap();
+        assert_eq!(summary.books_added + summary.reviews_added, 0);
+    });
+}
+
+/// History without timestamps, with each entry's changes in a stable order
+fn history_json(store: &dyn LibraryStore) -> Vec<(String, bool, Vec<String>)> {
+    store
+        .history(None)
+        .unwrap()
+        .into_iter()
+        .map(|entry| {
+            let mut changes: Vec<String> = entry
+                .changes
+                .iter()
+                .map(|c| without_times(serde_json::to_value(c).unwrap()).to_string())
+                .collect();
+            changes.sort();
+            (entry.operation, entry.undone, changes)
+        })
+        .collect()
+}
+
+#[test]
+fn test_backends_record_the_same_history() {
+    let mut sqlite = sqlite();
+    let mut memory = memory();
+    scenario(sqlite.as_mut());
+    scenario(memory.as_mut());
+
+    let history = history_json(sqlite.as_ref());
+    assert_eq!(history.len(), 7);
+    assert_eq!(history[0].0, "delete book");
+    assert_eq!(history, history_json(memory.as_ref()));
+
+    let solaris = sqlite.history(Some(1)).unwrap();
+    assert_eq!(solaris.len(), 4);
+    assert_eq!(solaris.last().unwrap().book_title(1), Some("Solaris"));
+}
+
+#[test]
+fn test_undo_restores_earlier_state() {
+    for_each_store(|store| {
+        scenario(store);
+        let before = archive_json(store);
+
+        let solaris = 1;
+        store.delete_book(solaris).unwrap();
+        let dune = store
+            .add_book(&new_book("Dune", &["Herbert"], &[]))
+            .unwrap();
+        store.add_review(&new_review(dune, Some(3), 4)).unwrap();
+
+        let undone = store.undo(3).unwrap();
+        let operations: Vec<&str> = undone.iter().map(|e| e.operation.as_str()).collect();
+        assert_eq!(operations, vec!["add review", "add book", "delete book"]);
+        assert_eq!(archive_json(store), before);
+
+        // Undone entries stay in the history and are skipped next time
+        let history = store.history(None).unwrap();
+        assert!(history[..3].iter().all(|e| e.undone));
+        assert_eq!(store.undo(1).unwrap()[0].operation, "delete book");
+        assert_eq!(store.get_books(&BookFilter::default()).unwrap().len(), 3);
+    });
+}
+
+#[test]
+fn test_nothing_to_undo() {
+    for_each_store(|store| {
+        assert!(matches!(
+            store.undo(1),
+            Err(LibroError::InvalidInput { .. })
+        ));
+
+        let book_id = store
+            .add_book(&new_book("Dune", &["Herbert"], &[]))
+            .unwrap();
+        assert_eq!(store.undo(5).unwrap().len(), 1);
+        assert!(store.get_books(&BookFilter::default()).unwrap().is_empty());
+        assert!(store.history(Some(book_id)).unwrap()[0].undone);
+        assert!(matches!(
+            store.undo(1),
+            Err(LibroError::InvalidInput { .. })
+        ));
+    });
+}
+
+#[test]
+fn test_trash_hides_and_restores_with_reviews() {
+    for_each_store(|store| {
+        let book_id = store
+            .add_book(&new_book("Solaris", &["Lem"], &["Johnston"]))
+            .unwrap();
+        store.add_review(&new_review(book_id, Some(1), 4)).unwrap();
+        let trashed_review = store.add_review(&new_review(book_id, Some(2), 2)).unwrap();
+
+        store.delete_review(trashed_review).unwrap();
+        store.delete_book(book_id).unwrap();
+        assert!(store.get_books(&BookFilter::default()).unwrap().is_empty());
+        assert!(store.get_writers().unwrap().is_empty());
+        // Archives keep the trash, marked with when it was deleted
+        let archive = archive_json(store);
+        assert!(archive["books"][0]["deleted_at"].is_string());
+        assert!(archive["reviews"][0].get("deleted_at").is_none());
+        assert!(archive["reviews"][1]["deleted_at"].is_string());
+        assert!(matches!(
+            store.delete_book(book_id),
+            Err(LibroError::BookNotFound { .. })
+        ));
+
+        let trash = store.trash().unwrap();
+        let kinds: Vec<TrashKind> = trash.iter().map(|item| item.kind).collect();
+        assert_eq!(kinds.len(), 2);
+        assert!(kinds.contains(&TrashKind::Book) && kinds.contains(&TrashKind::Review));
+        assert!(trash.iter().all(|item| item.title == "Solaris"));
+
+        // A review only comes back once its book has
+        assert!(matches!(
+            store.restore_review(trashed_review),
+            Err(LibroError::InvalidInput { .. })
+        ));
+        store.restore_book(book_id).unwrap();
+        let books = store.get_books(&BookFilter::default()).unwrap();
+        assert_eq!(books[0].reviews.len(), 1);
+        assert_eq!(books[0].translators[0].name, "Johnston");
+
+        store.restore_review(trashed_review).unwrap();
+        assert_eq!(store.get_reviews(book_id).unwrap().len(), 2);
+        assert!(store.trash().unwrap().is_empty());
+    });
+}
+
+#[test]
+fn test_purge_trash() {
+    for_each_store(|store| {
+        let first = store
+            .add_book(&new_book("First", &["Shared"], &["Only First"]))
+            .unwrap();
+        store
+            .add_book(&new_book("Second", &["Shared"], &[]))
+            .unwrap();
+        store.add_review(&new_review(first, Some(1), 3)).unwrap();
+        store.delete_book(first).unwrap();
+
+        let long_ago = NaiveDate::from_ymd_opt(2000, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0);
+        assert!(store.purge_trash(long_ago).unwrap().is_empty());
+        assert_eq!(store.trash().unwrap().len(), 1);
+
+        let purged = store.purge_trash(None).unwrap();
+        assert_eq!((purged[0].id, purged[0].reviews), (first, 1));
+        assert!(store.trash().unwrap().is_empty());
+        assert!(matches!(
+            store.restore_book(first),
+            Err(LibroError::BookNotFound { .. })
+        ));
+
+        let writers: Vec<String> = store
+            .export_archive()
+            .unwrap()
+            .writers
+            .into_iter()
+            .map(|w| w.name)
+            .collect();
+        assert_eq!(writers, vec!["Shared"]);
+
+        // Purging is not recorded and strips the purged rows from the history;
+        // entries left with nothing to revert cannot be undone
+        let history = store.history(None).unwrap();
+        assert_eq!(history.len(), 4);
+        assert!(history.iter().all(|entry| !entry.touches_book(first)));
+        assert!(!format!("{:?}", history).contains("Only First"));
+        let undoable: Vec<&str> = history
+            .iter()
+            .filter(|entry| entry.can_undo())
+            .map(|entry| entry.operation.as_str())
+            .collect();
+        assert_eq!(undoable, vec!["add book", "add book"]);
+
+        store.undo(1).unwrap();
+        assert!(store.get_books(&BookFilter::default()).unwrap().is_empty());
+        assert!(store.trash().unwrap().is_empty());
+    });
+}
+
+#[test]
+fn test_purge_keeps_the_rest_of_an_entry_undoable() {
+    let mut source = memory();
+    source
+        .add_book(&new_book("Kept", &["Shared"], &[]))
+        .unwrap();
+    source
+        .add_book(&new_book("Purged", &["Shared"], &["Only Purged"]))
+        .unwrap();
+    let archive = source.export_archive().unwrap();
+
+    for_each_store(|store| {
+        store.import_archive(&archive).unwrap();
+        let purged = store
+            .get_books(&BookFilter::default())
+            .unwrap()
+            .into_iter()
+            .find(|b| b.book.title == "Purged")
+            .and_then(|b| b.book.id)
+            .unwrap();
+        store.delete_book(purged).unwrap();
+        store.purge_trash(None).unwrap();
+
+        let history = store.history(None).unwrap();
+        let import = history
+            .iter()
+            .find(|entry| entry.operation == "import archive")
+            .unwrap();
+        assert!(import.can_undo());
+        assert!(!import.touches_book(purged));
+
+        // Undoing the import still removes the book that was not purged
+        let undone = store.undo(1).unwrap();
+        assert_eq!(undone[0].operation, "import archive");
+        assert!(store.get_books(&BookFilter::default()).unwrap().is_empty());
+        assert!(store.get_writers().unwrap().is_empty());
+    });
+}
+
+#[test]
+fn test_timestamps_on_add_and_update() {
+    for_each_store(|store| {
+        let book_id = store.add_book(&new_book("Solaris", &["Lem"], &[])).unwrap();
+        let review_id = store.add_review(&new_review(book_id, Some(1), 4)).unwrap();
+
+        let book = store.get_books(&BookFilter::default()).unwrap().remove(0);
+        let created_at = book.book.created_at.clone().unwrap();
+        assert!(chrono::NaiveDateTime::parse_from_str(&created_at, "%Y-%m-%d %H:%M:%S").is_ok());
+        assert_eq!(book.book.updated_at.as_ref(), Some(&created_at));
+        assert!(book.reviews[0].created_at.is_some());
+
+        // Updates keep the creation time, whatever the caller passes
+        store
+            .update_book(
+                book_id,
+                &Book {
+                    title: "Solaris (2nd ed.)".to_string(),
+                    created_at: Some("1999-01-01 00:00:00".to_string()),
+                    ..book.book.clone()
+                },
+            )
+            .unwrap();
+        let review = store.get_review(review_id).unwrap();
+        store.update_review(review_id, &review).unwrap();
+
+        let book = store.get_books(&BookFilter::default()).unwrap().remove(0);
+        assert_eq!(book.book.created_at, Some(created_at.clone()));
+        assert!(book.book.updated_at.unwrap() >= created_at);
+        assert_eq!(book.reviews[0].created_at, review.created_at);
+    });
+}
+
+#[test]
+fn test_import_keeps_timestamps() {
+    let mut source = memory();
+    source
+        .add_book(&new_book("Solaris", &["Lem"], &[]))
+        .unwrap();
+    let mut archive = source.export_archive().unwrap();
+    archive.books[0].created_at = Some("2020-05-01 09:30:00".to_string());
+    archive.books[0].updated_at = None;
+
+    for_each_store(|store| {
+        store.import_archive(&archive).unwrap();
+        let book = store.get_books(&BookFilter::default()).unwrap().remove(0);
+        assert_eq!(book.book.created_at.as_deref(), Some("2020-05-01 09:30:00"));
+        assert_eq!(book.book.updated_at.as_deref(), Some("2020-05-01 09:30:00"));
+    });
+}